@@ -0,0 +1,36 @@
+pub struct Config {
+    pub(crate) width: usize,
+    pub(crate) colors: bool,
+    pub(crate) hyperlinks: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            colors: true,
+            hyperlinks: true,
+        }
+    }
+}
+
+impl Config {
+    pub fn with_width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Use SGR escape sequences for colors and text styles. Without them, headings
+    /// are underlined with `=` and `-`, inline code is wrapped in backticks and
+    /// strikethrough text in `~~`. Enabled by default, disable it when the output
+    /// is not a terminal or `NO_COLOR` is set.
+    pub fn with_colors(self, colors: bool) -> Self {
+        Self { colors, ..self }
+    }
+
+    /// Make links clickable with OSC 8 escape sequences. Without them, or without
+    /// colors, the destination is printed in parentheses after the link text.
+    /// Enabled by default.
+    pub fn with_hyperlinks(self, hyperlinks: bool) -> Self {
+        Self { hyperlinks, ..self }
+    }
+}
@@ -0,0 +1,461 @@
+//! Rendering of the AST for terminal display.
+//!
+//! Text styles and colors use SGR escape sequences and links use OSC 8 hyperlinks,
+//! both can be disabled in the [`Config`](config::Config). Escape sequences take no
+//! space in the layout, so paragraphs are wrapped at the configured width.
+//!
+//! ```rust
+//! use markdown_ppp::ansi_printer::{config::Config, render_ansi};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(MarkdownParserState::default(), "## Usage\n\nRun `tool`.").unwrap();
+//! assert_eq!(
+//!     "\x1b[1;36mUsage\x1b[0m\n\nRun \x1b[33mtool\x1b[39m.",
+//!     render_ansi(&doc, Config::default())
+//! );
+//! assert_eq!(
+//!     "Usage\n-----\n\nRun `tool`.",
+//!     render_ansi(&doc, Config::default().with_colors(false))
+//! );
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::ansi_printer::config::Config;
+use crate::ast::*;
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator, DocBuilder};
+use std::cell::{Cell, RefCell};
+
+/// Render the given Markdown AST for terminal display.
+pub fn render_ansi(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = AnsiRenderer {
+        arena: &arena,
+        config: &config,
+        index: Index::new(&ast),
+        width: Cell::new(config.width),
+        styles: RefCell::new(Vec::new()),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+const RESET: &str = "\x1b[0m";
+
+/// Remove SGR and OSC escape sequences.
+fn strip_escapes(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI, ends with a letter
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if c.is_ascii_alphabetic() {
+                        break;
+                    }
+                }
+            }
+            // OSC, ends with ST (`ESC \`)
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x1b' && chars.next_if_eq(&'\\').is_some() {
+                        break;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    result
+}
+
+/// Remove control characters other than line breaks and tabs, so the document
+/// cannot emit escape sequences of its own.
+fn strip_controls(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
+        .collect()
+}
+
+/// Percent-encode control characters of a link destination, which would end or
+/// extend the OSC 8 sequence.
+fn encode_controls(destination: &str) -> String {
+    let mut result = String::with_capacity(destination.len());
+    for c in destination.chars() {
+        if c.is_control() {
+            let mut bytes = [0; 4];
+            for byte in c.encode_utf8(&mut bytes).bytes() {
+                result.push_str(&format!("%{byte:02X}"));
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+struct AnsiRenderer<'a> {
+    arena: &'a Arena<'a>,
+    config: &'a Config,
+    index: Index,
+    // Width available to the block being rendered.
+    width: Cell<usize>,
+    // Styles of the enclosing elements, restored when a nested style ends.
+    styles: RefCell<Vec<&'static str>>,
+}
+
+impl<'a> AnsiRenderer<'a> {
+    /// Escape sequence taking no space in the layout, nothing without colors.
+    fn escape_code(&self, code: &str) -> Doc<'a> {
+        if !self.config.colors {
+            return self.arena.nil();
+        }
+        self.measured_text(code.to_owned(), 0)
+    }
+
+    /// Text taking `width` columns in the layout.
+    fn measured_text(&self, text: String, width: usize) -> Doc<'a> {
+        // Non-ASCII text is already measured, `RenderLen` must not be nested
+        let text = self.arena.text(text).into_doc();
+        let text = match *text {
+            pretty::Doc::RenderLen(_, inner) => inner,
+            _ => text,
+        };
+        DocBuilder(self.arena, pretty::Doc::RenderLen(width, text).into())
+    }
+
+    fn styled(&self, on: &str, off: &str, content: Doc<'a>) -> Doc<'a> {
+        let styled = self
+            .escape_code(on)
+            .append(content)
+            .append(self.escape_code(off));
+        let enclosing = self.styles.borrow().concat();
+        match enclosing.is_empty() {
+            true => styled,
+            false => styled.append(self.escape_code(&enclosing)),
+        }
+    }
+
+    /// Render content inside an element with the style `on`.
+    fn with_style<T>(&self, on: &'static str, render: impl FnOnce() -> T) -> T {
+        self.styles.borrow_mut().push(on);
+        let result = render();
+        self.styles.borrow_mut().pop();
+        result
+    }
+
+    /// Display width of text which may contain escape sequences.
+    fn display_width(&self, text: &str) -> usize {
+        let text = strip_escapes(text);
+        match *self.arena.text(text.clone()) {
+            pretty::Doc::RenderLen(width, _) => width,
+            _ => text.len(),
+        }
+    }
+
+    /// Already laid out line, measured without its escape sequences.
+    fn fixed_line(&self, line: String) -> Doc<'a> {
+        let width = self.display_width(&line);
+        self.measured_text(line, width)
+    }
+
+    fn to_string(&self, doc: Doc<'a>, width: usize) -> String {
+        let mut result = String::new();
+        doc.render_fmt(width, &mut result)
+            .expect("writing to a String never fails");
+        result
+    }
+
+    /// Render blocks with `prefix` in front of every line.
+    fn prefixed_blocks(&self, prefix: &str, blocks: &[Block]) -> Doc<'a> {
+        let prefix_width = self.display_width(prefix);
+        let width = self.width.get();
+        self.width.set(width.saturating_sub(prefix_width).max(1));
+        let content = self.render_blocks(blocks);
+        let content = self.to_string(content, self.width.get());
+        self.width.set(width);
+
+        let lines = content.split('\n').map(|line| {
+            let line = line.trim_end();
+            let prefix = if line.is_empty() {
+                prefix.trim_end()
+            } else {
+                prefix
+            };
+            self.fixed_line(format!("{prefix}{line}"))
+        });
+        self.arena.intersperse(lines, self.arena.hardline())
+    }
+
+    fn link(&self, destination: &str, content: Doc<'a>, same_text: bool) -> Doc<'a> {
+        let destination = encode_controls(destination);
+        if self.config.colors && self.config.hyperlinks {
+            return self
+                .escape_code(&format!("\x1b]8;;{destination}\x1b\\"))
+                .append(self.styled("\x1b[4m", "\x1b[24m", content))
+                .append(self.escape_code("\x1b]8;;\x1b\\"));
+        }
+        if same_text {
+            return content;
+        }
+        content
+            .append(self.arena.softline())
+            .append(self.arena.text(format!("({destination})")))
+    }
+}
+
+impl<'a> Renderer<'a> for AnsiRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        if self.config.colors {
+            let style = match level {
+                1 => "\x1b[1;4;35m",
+                2 => "\x1b[1;36m",
+                _ => "\x1b[1m",
+            };
+            let content = self.with_style(style, || self.render_inlines(&heading.content).group());
+            return self.styled(style, RESET, content);
+        }
+
+        let content = self.render_inlines(&heading.content).group();
+        let underline = match level {
+            1 => '=',
+            2 => '-',
+            _ => return content,
+        };
+        let text = self.to_string(content, self.width.get());
+        let width = text
+            .split('\n')
+            .map(|line| self.display_width(line))
+            .max()
+            .unwrap_or_default();
+        self.arena.intersperse(
+            text.split('\n')
+                .map(|line| self.fixed_line(line.to_owned()))
+                .chain([self.arena.text(underline.to_string().repeat(width))]),
+            self.arena.hardline(),
+        )
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.styled(
+            "\x1b[2m",
+            "\x1b[22m",
+            self.arena.text("\u{2500}".repeat(self.width.get())),
+        )
+    }
+
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        let prefix = match self.config.colors {
+            true => "\x1b[2m\u{2502}\x1b[22m ",
+            false => "\u{2502} ",
+        };
+        self.prefixed_blocks(prefix, blocks)
+    }
+
+    fn render_list_item(&self, _list: &List, number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let checkbox = item.task.map(|task| match task {
+            TaskState::Complete => "\u{2611}",
+            TaskState::Incomplete => "\u{2610}",
+        });
+        let marker = match (number, checkbox) {
+            (Some(n), Some(checkbox)) => format!("{n}. {checkbox} "),
+            (Some(n), None) => format!("{n}. "),
+            (None, Some(checkbox)) => format!("{checkbox} "),
+            (None, None) => "\u{2022} ".to_owned(),
+        };
+        let indent = self.display_width(&marker);
+        let width = self.width.get();
+        self.width.set(width.saturating_sub(indent).max(1));
+        let content = self.render_blocks(&item.blocks);
+        self.width.set(width);
+        self.arena
+            .text(marker)
+            .append(content.nest(indent as isize))
+    }
+
+    /// Indented and, with colors, shaded to the width of the longest line.
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let literal = strip_controls(literal.unwrap_or(&code_block.literal)).replace('\t', "    ");
+        let lines: Vec<&str> = literal.split('\n').collect();
+        if !self.config.colors {
+            let lines = lines
+                .iter()
+                .map(|line| self.fixed_line(line.trim_end().to_owned()));
+            return self
+                .arena
+                .text("    ")
+                .append(self.arena.intersperse(lines, self.arena.hardline()))
+                .nest(4);
+        }
+
+        let width = lines
+            .iter()
+            .map(|line| self.display_width(line))
+            .max()
+            .unwrap_or_default();
+        let lines = lines.iter().map(|line| {
+            let padding = " ".repeat(width - self.display_width(line));
+            self.escape_code("\x1b[48;5;236m")
+                .append(self.fixed_line(format!(" {line}{padding} ")))
+                .append(self.escape_code("\x1b[49m"))
+        });
+        self.arena
+            .text("  ")
+            .append(self.arena.intersperse(lines, self.arena.hardline()))
+            .nest(2)
+    }
+
+    /// Box-drawn table with columns aligned by the table alignments.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let rows: Vec<Vec<String>> = table
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .map(|cell| {
+                        let render = || self.to_string(self.render_inlines(cell), usize::MAX);
+                        match i {
+                            0 => self.with_style("\x1b[1m", render),
+                            _ => render(),
+                        }
+                    })
+                    .map(|cell| cell.replace('\n', " "))
+                    .collect()
+            })
+            .collect();
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        let widths: Vec<usize> = (0..columns)
+            .map(|i| {
+                rows.iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| self.display_width(cell))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let border = |left: &str, middle: &str, right: &str| {
+            let segments: Vec<String> = widths.iter().map(|w| "\u{2500}".repeat(w + 2)).collect();
+            self.fixed_line(format!("{left}{}{right}", segments.join(middle)))
+        };
+        let row_line = |row: &Vec<String>, header: bool| {
+            let cells: Vec<String> = (0..columns)
+                .map(|i| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or_default();
+                    let padding = widths[i] - self.display_width(cell);
+                    let (left, right) = match table.alignments.get(i).copied().unwrap_or_default() {
+                        Alignment::Right => (padding, 0),
+                        Alignment::Center => (padding / 2, padding - padding / 2),
+                        Alignment::Left | Alignment::None => (0, padding),
+                    };
+                    let cell = match (header, self.config.colors) {
+                        (true, true) => format!("\x1b[1m{cell}\x1b[22m"),
+                        _ => cell.to_owned(),
+                    };
+                    format!(" {}{cell}{} ", " ".repeat(left), " ".repeat(right))
+                })
+                .collect();
+            self.fixed_line(format!("\u{2502}{}\u{2502}", cells.join("\u{2502}")))
+        };
+
+        let mut lines = vec![border("\u{250c}", "\u{252c}", "\u{2510}")];
+        if let Some((header, body)) = rows.split_first() {
+            lines.push(row_line(header, true));
+            if !body.is_empty() {
+                lines.push(border("\u{251c}", "\u{253c}", "\u{2524}"));
+            }
+            lines.extend(body.iter().map(|row| row_line(row, false)));
+        }
+        lines.push(border("\u{2514}", "\u{2534}", "\u{2518}"));
+        self.arena.intersperse(lines, self.arena.hardline())
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            strip_controls(text)
+                .split([' ', '\n'])
+                .map(|word| self.arena.text(word.to_owned())),
+            self.arena.softline(),
+        )
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        let code = strip_controls(code);
+        match self.config.colors {
+            true => self.styled("\x1b[33m", "\x1b[39m", self.arena.text(code)),
+            false => self.arena.text(format!("`{code}`")),
+        }
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let same_text = matches!(
+            link.children.as_slice(),
+            [Inline::Text(text)] if *text == link.destination
+        );
+        self.link(
+            &link.destination,
+            self.render_inlines(&link.children),
+            same_text,
+        )
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        let text = self
+            .arena
+            .text(format!("[image: {}]", strip_controls(&image.alt)));
+        self.link(&image.destination, text, false)
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled("\x1b[3m", "\x1b[23m", self.render_inlines(children))
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled("\x1b[1m", "\x1b[22m", self.render_inlines(children))
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        match self.config.colors {
+            true => self.styled("\x1b[9m", "\x1b[29m", self.render_inlines(children)),
+            false => self
+                .arena
+                .text("~~")
+                .append(self.render_inlines(children))
+                .append(self.arena.text("~~")),
+        }
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        let destination = if link.starts_with("www.") {
+            format!("http://{link}")
+        } else if !link.contains(':') && link.contains('@') {
+            format!("mailto:{link}")
+        } else {
+            link.to_owned()
+        };
+        self.link(&destination, self.arena.text(strip_controls(link)), true)
+    }
+}
@@ -0,0 +1,147 @@
+#![cfg(test)]
+use crate::ansi_printer::config::Config;
+use crate::ansi_printer::render_ansi;
+use rstest::rstest;
+
+fn render(input: &str, config: Config) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_ansi(&doc, config)
+}
+
+#[rstest]
+#[case("# Title", "\x1b[1;4;35mTitle\x1b[0m")]
+#[case("### Minor", "\x1b[1mMinor\x1b[0m")]
+#[case("# a `b` c", "\x1b[1;4;35ma \x1b[33mb\x1b[39m\x1b[1;4;35m c\x1b[0m")]
+#[case("## a **b** c", "\x1b[1;36ma \x1b[1mb\x1b[22m\x1b[1;36m c\x1b[0m")]
+#[case(
+    "*em* **strong** ~~gone~~ `code`",
+    "\x1b[3mem\x1b[23m \x1b[1mstrong\x1b[22m \x1b[9mgone\x1b[29m \x1b[33mcode\x1b[39m"
+)]
+#[case(
+    "[docs](https://example.com)",
+    "\x1b]8;;https://example.com\x1b\\\x1b[4mdocs\x1b[24m\x1b]8;;\x1b\\"
+)]
+#[case(
+    "> quoted\n>\n> more",
+    "\x1b[2m│\x1b[22m quoted\n\x1b[2m│\x1b[22m\n\x1b[2m│\x1b[22m more"
+)]
+#[case(
+    "```\nfn a() {}\nb\n```",
+    "  \x1b[48;5;236m fn a() {} \x1b[49m\n  \x1b[48;5;236m b         \x1b[49m"
+)]
+fn colors(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input, Config::default()));
+}
+
+#[rstest]
+#[case("# Title", "Title\n=====")]
+#[case("### Minor", "Minor")]
+#[case("*em* **strong** ~~gone~~ `code`", "em strong ~~gone~~ `code`")]
+#[case("[docs](https://example.com)", "docs (https://example.com)")]
+#[case("<https://example.com>", "https://example.com")]
+#[case("![logo](logo.png)", "[image: logo] (logo.png)")]
+#[case("- a\n- [x] b\n- [ ] c", "• a\n☑ b\n☐ c")]
+#[case("3. a\n4. [x] b", "3. a\n4. ☑ b")]
+#[case("```\nfn a() {}\n```", "    fn a() {}")]
+fn no_colors(#[case] input: &str, #[case] expected: &str) {
+    let config = Config::default().with_colors(false);
+    assert_eq!(expected, render(input, config));
+}
+
+#[test]
+fn thematic_break() {
+    let config = Config::default().with_width(10).with_colors(false);
+    assert_eq!("a\n\n──────────\n\nb", render("a\n\n***\n\nb", config));
+}
+
+#[test]
+fn links_without_hyperlinks() {
+    let config = Config::default().with_hyperlinks(false);
+    assert_eq!(
+        "\x1b[1mdocs\x1b[22m (https://example.com)",
+        render("[**docs**](https://example.com)", config)
+    );
+}
+
+#[test]
+fn wrapping_ignores_escape_codes() {
+    let input = "**one two** *three* four\n\n- five six seven eight";
+    assert_eq!(
+        "\x1b[1mone two\x1b[22m\n\x1b[3mthree\x1b[23m four\n\n• five six\n  seven\n  eight",
+        render(input, Config::default().with_width(12))
+    );
+}
+
+#[test]
+fn block_quote_wraps_inside_prefix() {
+    let config = Config::default().with_width(10).with_colors(false);
+    assert_eq!("│ one two\n│ three", render("> one two three", config));
+}
+
+#[test]
+fn tables() {
+    let input = "| Name | Size | Kind |\n|:-----|-----:|:----:|\n| `a` | 1 | file |\n| long name | 1024 | x |";
+    assert_eq!(
+        "┌───────────┬──────┬──────┐\n\
+         │ Name      │ Size │ Kind │\n\
+         ├───────────┼──────┼──────┤\n\
+         │ `a`       │    1 │ file │\n\
+         │ long name │ 1024 │  x   │\n\
+         └───────────┴──────┴──────┘",
+        render(input, Config::default().with_colors(false))
+    );
+    assert_eq!(
+        "┌──────┬───┐\n\
+         │ \x1b[1mé\x1b[22m    │ \x1b[1mb\x1b[22m │\n\
+         ├──────┼───┤\n\
+         │ \x1b[33mcode\x1b[39m │ 1 │\n\
+         └──────┴───┘",
+        render("| é | b |\n|---|---|\n| `code` | 1 |", Config::default())
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        "Text[1]\n\n[1] Note",
+        render(
+            "Text[^n]\n\n[^n]: Note",
+            Config::default().with_colors(false)
+        )
+    );
+}
+
+/// Control characters of the document cannot emit escape sequences of their own.
+#[test]
+fn control_characters() {
+    use crate::ast::*;
+    let doc = Document {
+        blocks: vec![
+            Block::Paragraph(vec![
+                Inline::Text("a\x1b[31mred\u{9b}2J ".to_owned()),
+                Inline::Link(Link {
+                    destination: "https://example.com/\x1b\\\x07\u{85}".to_owned(),
+                    title: None,
+                    children: vec![Inline::Code("c\x1b]8;;".to_owned())],
+                }),
+            ]),
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Indented,
+                literal: "x\x1b[2J\ty\n".to_owned(),
+            }),
+        ],
+    };
+    assert_eq!(
+        concat!(
+            "a[31mred2J \x1b]8;;https://example.com/%1B\\%07%C2%85\x1b\\",
+            "\x1b[4m\x1b[33mc]8;;\x1b[39m\x1b[24m\x1b]8;;\x1b\\\n\n",
+            "  \x1b[48;5;236m x[2J    y \x1b[49m",
+        ),
+        render_ansi(&doc, Config::default())
+    );
+    assert_eq!(
+        "a[31mred2J `c]8;;` (https://example.com/%1B\\%07%C2%85)\n\n    x[2J    y",
+        render_ansi(&doc, Config::default().with_colors(false))
+    );
+}
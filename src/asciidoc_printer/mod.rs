@@ -0,0 +1,594 @@
+//! Rendering of the AST to AsciiDoc.
+//!
+//! Paragraphs are wrapped at the configured width. Headings become sections below
+//! the document title, so a level 1 heading becomes `== Title`. Code blocks become
+//! `source` listings, GitHub alerts (see [`alerts`](crate::ast::alerts)) become
+//! admonition blocks, footnotes are rendered at their first reference and
+//! directives become block and inline macros. Characters which would start markup
+//! are replaced by the built-in character attributes, such as `{asterisk}`.
+//!
+//! ```rust
+//! use markdown_ppp::asciidoc_printer::{config::Config, render_asciidoc};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "## Usage\n\nRun `make` in the [repository](https://example.com)[^1].\n\n[^1]: Or **ask**.",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     "=== Usage\n\nRun `+make+` in the https://example.com[repository]footnote:fn-1[Or *ask*.].",
+//!     render_asciidoc(&doc, Config::default().with_width(100))
+//! );
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::asciidoc_printer::config::Config;
+use crate::ast::alerts::{alert, AlertKind};
+use crate::ast::*;
+use crate::renderer::plain::plain_text;
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Render the given Markdown AST to AsciiDoc.
+pub fn render_asciidoc(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = AsciidocRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+        unconstrained: Cell::new(false),
+        footnote_references_rendered: RefCell::new(HashMap::new()),
+        open_footnotes: RefCell::new(Vec::new()),
+        bullet_depth: Cell::new(0),
+        ordered_depth: Cell::new(0),
+        delimited_depth: Cell::new(0),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+/// Escape a word for AsciiDoc. Markup characters are replaced by character
+/// attributes or passed through, and words which would start a block construct at
+/// the start of a line are prefixed with `{empty}`, since wrapping may put any word
+/// there.
+pub fn escape(word: &str) -> String {
+    escape_word(word, true)
+}
+
+/// Escape a word, which may be at the start of a line.
+fn escape_word(word: &str, line_start: bool) -> String {
+    let mut result = String::with_capacity(word.len());
+    if line_start && starts_construct(word) {
+        result.push_str("{empty}");
+    }
+    let count = word.chars().count();
+    for (i, c) in word.chars().enumerate() {
+        match c {
+            '*' => result.push_str("{asterisk}"),
+            '`' => result.push_str("{backtick}"),
+            '^' => result.push_str("{caret}"),
+            '~' => result.push_str("{tilde}"),
+            '+' => result.push_str("{plus}"),
+            '|' => result.push_str("{vbar}"),
+            '[' => result.push_str("{startsb}"),
+            ']' => result.push_str("{endsb}"),
+            '\\' => result.push_str("{backslash}"),
+            '#' | '{' => result.push_str(&format!("pass:[{c}]")),
+            // Constrained emphasis needs a word boundary
+            '_' if i == 0 || i + 1 == count => result.push_str("pass:[_]"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Whether the word starts a block construct at the start of a line.
+fn starts_construct(word: &str) -> bool {
+    if word.starts_with(['=', '.', ':', '\'']) || word.starts_with("//") {
+        return true;
+    }
+    if word.chars().all(|c| c == '-') || word.ends_with("::") || word.ends_with(";;") {
+        return !word.is_empty();
+    }
+    if matches!(
+        word,
+        "NOTE:" | "TIP:" | "IMPORTANT:" | "WARNING:" | "CAUTION:"
+    ) {
+        return true;
+    }
+    // Ordered list items `1.`, `a.`, `iv)`
+    match word.strip_suffix(['.', ')']) {
+        Some(rest) if !rest.is_empty() => {
+            rest.chars().all(|c| c.is_ascii_digit())
+                || rest.chars().count() == 1 && rest.chars().all(|c| c.is_ascii_alphabetic())
+                || rest.chars().all(|c| "ivxlcdm".contains(c))
+                || rest.chars().all(|c| "IVXLCDM".contains(c))
+        }
+        _ => false,
+    }
+}
+
+/// Attribute value, quoted if it would be split or parsed as a named attribute.
+fn attribute_value(value: &str) -> String {
+    if value.contains([',', '=', '"', ']']) || value.starts_with(' ') {
+        format!("\"{}\"", value.replace('"', "\\\"").replace(']', "\\]"))
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Whether text adjacent to constrained markup is part of a word, so unconstrained
+/// markup is needed.
+fn is_word_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_alphanumeric() || c == '_')
+}
+
+struct AsciidocRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+    // Whether the inline being rendered is adjacent to a word character.
+    unconstrained: Cell<bool>,
+    // Number of already rendered references to each footnote.
+    footnote_references_rendered: RefCell<HashMap<String, usize>>,
+    // Footnotes being rendered, to stop on footnotes referencing themselves.
+    open_footnotes: RefCell<Vec<String>>,
+    bullet_depth: Cell<usize>,
+    ordered_depth: Cell<usize>,
+    // Number of enclosing delimited blocks, nested blocks need longer delimiters.
+    delimited_depth: Cell<usize>,
+}
+
+impl<'a> AsciidocRenderer<'a> {
+    /// Content between `marker`s, doubled when adjacent to a word.
+    fn quoted_text(&self, marker: &str, content: &[Inline]) -> Doc<'a> {
+        let marker = match self.unconstrained.get() {
+            true => marker.repeat(2),
+            false => marker.to_owned(),
+        };
+        self.arena
+            .text(marker.clone())
+            .append(self.render_inlines(content))
+            .append(self.arena.text(marker))
+    }
+
+    /// Escaped words of a text joined by soft line breaks. The first word only starts
+    /// a line if `line_start` is set.
+    fn words(&self, text: &str, line_start: bool) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .enumerate()
+                .map(|(i, word)| self.arena.text(escape_word(word, i > 0 || line_start))),
+            self.arena.softline(),
+        )
+    }
+
+    /// Delimited block, with the delimiter lengthened inside other delimited blocks.
+    fn delimited(&self, delimiter: char, content: impl FnOnce() -> Doc<'a>) -> Doc<'a> {
+        let depth = self.delimited_depth.get();
+        self.delimited_depth.set(depth + 1);
+        let content = content();
+        self.delimited_depth.set(depth);
+        let delimiter = delimiter.to_string().repeat(4 + depth);
+        self.arena
+            .text(delimiter.clone())
+            .append(self.arena.hardline())
+            .append(content)
+            .append(self.arena.hardline())
+            .append(self.arena.text(delimiter))
+    }
+
+    /// Lines of a literal text, such as code, between delimiters which do not occur
+    /// in the text.
+    fn literal_block(&self, delimiter: char, text: &str) -> Doc<'a> {
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        let longest = text
+            .split('\n')
+            .filter(|line| !line.is_empty() && line.chars().all(|c| c == delimiter))
+            .map(str::len)
+            .max()
+            .unwrap_or_default();
+        let delimiter = delimiter.to_string().repeat((longest + 1).max(4));
+        self.arena
+            .text(delimiter.clone())
+            .append(self.arena.hardline())
+            .append(
+                self.arena.intersperse(
+                    text.split('\n')
+                        .map(|line| self.arena.text(line.to_owned())),
+                    self.arena.hardline(),
+                ),
+            )
+            .append(self.arena.hardline())
+            .append(self.arena.text(delimiter))
+    }
+
+    /// Link macro `url[text]`, with the `link:` prefix for URLs without a scheme.
+    fn link(&self, url: &str, text: Option<Doc<'a>>) -> Doc<'a> {
+        let url = url.replace(' ', "%20");
+        let target = match url.contains("://") || url.starts_with("mailto:") {
+            true => url,
+            false => format!("link:{url}"),
+        };
+        match text {
+            Some(text) => self
+                .arena
+                .text(format!("{target}["))
+                .append(text)
+                .append(self.arena.text("]")),
+            None if target.starts_with("link:") => self.arena.text(format!("{target}[]")),
+            None => self.arena.text(target),
+        }
+    }
+
+    /// Attribute list `name,key=value` of a macro or block.
+    fn attributes(&self, name: Option<&str>, attributes: &[(String, String)]) -> String {
+        name.into_iter()
+            .map(str::to_owned)
+            .chain(
+                attributes
+                    .iter()
+                    .map(|(key, value)| format!("{key}={}", attribute_value(value))),
+            )
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn image_macro(&self, colons: &str, image: &Image) -> String {
+        let mut attributes = vec![attribute_value(&image.alt)];
+        if let Some(title) = &image.title {
+            attributes.push(format!("title={}", attribute_value(title)));
+        }
+        format!(
+            "image{colons}{}[{}]",
+            image.destination.replace(' ', "%20"),
+            attributes.join(",")
+        )
+    }
+}
+
+impl<'a> Renderer<'a> for AsciidocRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// Footnotes are rendered at their references, so only the body is rendered.
+    fn render_document(&self, document: &Document) -> Doc<'a> {
+        self.render_blocks(&document.blocks)
+    }
+
+    /// A paragraph with only an image is rendered as a block image.
+    fn render_paragraph(&self, inlines: &[Inline]) -> Doc<'a> {
+        match inlines {
+            [Inline::Image(image)] => self.arena.text(self.image_macro("::", image)),
+            inlines => self.render_inlines(inlines).group(),
+        }
+    }
+
+    /// Sections start at level 1 (`==`), as a single `=` is the document title.
+    /// AsciiDoc has five section levels, so levels 5 and 6 are both `======`.
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level as usize,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        let mut title = String::new();
+        self.render_inlines(&heading.content)
+            .render_fmt(usize::MAX, &mut title)
+            .expect("writing to a String never fails");
+        self.arena.text(format!(
+            "{} {}",
+            "=".repeat(level.min(5) + 1),
+            title.replace('\n', " ")
+        ))
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("'''")
+    }
+
+    /// GitHub alerts are rendered as admonition blocks, other quotes as quote blocks.
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        if let Some((kind, content)) = alert(blocks) {
+            let style = match kind {
+                AlertKind::Note => "NOTE",
+                AlertKind::Tip => "TIP",
+                AlertKind::Important => "IMPORTANT",
+                AlertKind::Warning => "WARNING",
+                AlertKind::Caution => "CAUTION",
+            };
+            return self
+                .arena
+                .text(format!("[{style}]"))
+                .append(self.arena.hardline())
+                .append(self.delimited('=', || self.render_blocks(&content)));
+        }
+        self.delimited('_', || self.render_blocks(blocks))
+    }
+
+    /// Ordered lists not starting at 1 get a `start` attribute.
+    fn render_list(&self, list: &List) -> Doc<'a> {
+        let (depth, start) = match &list.kind {
+            ListKind::Ordered(options) => (&self.ordered_depth, Some(options.start)),
+            ListKind::Bullet(_) => (&self.bullet_depth, None),
+        };
+        depth.set(depth.get() + 1);
+        let items = list
+            .items
+            .iter()
+            .map(|item| self.render_list_item(list, None, item))
+            .collect::<Vec<_>>();
+        depth.set(depth.get() - 1);
+        let items = self.arena.intersperse(items, self.arena.hardline());
+        match start {
+            Some(start) if start != 1 => self
+                .arena
+                .text(format!("[start={start}]"))
+                .append(self.arena.hardline())
+                .append(items),
+            _ => items,
+        }
+    }
+
+    /// Nested lists follow the item text, other blocks are attached with `+` lines.
+    fn render_list_item(&self, list: &List, _number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let marker = match &list.kind {
+            ListKind::Ordered(_) => ".".repeat(self.ordered_depth.get()),
+            ListKind::Bullet(_) => "*".repeat(self.bullet_depth.get()),
+        };
+        let task = match item.task {
+            Some(TaskState::Complete) => "[x] ",
+            Some(TaskState::Incomplete) => "[ ] ",
+            None => "",
+        };
+        let mut doc = self.arena.text(format!("{marker} {task}"));
+        for (i, block) in item.blocks.iter().enumerate() {
+            doc = match (i, block) {
+                (0, Block::Paragraph(inlines)) => doc.append(self.render_inlines(inlines)),
+                (0, _) => doc
+                    .append(self.arena.text("{empty}"))
+                    .append(self.arena.hardline())
+                    .append(self.arena.text("+"))
+                    .append(self.arena.hardline())
+                    .append(self.render_block(block)),
+                (_, Block::List(_)) => doc
+                    .append(self.arena.hardline())
+                    .append(self.render_block(block)),
+                _ => doc
+                    .append(self.arena.hardline())
+                    .append(self.arena.text("+"))
+                    .append(self.arena.hardline())
+                    .append(self.render_block(block)),
+            };
+        }
+        doc
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let language = match &code_block.kind {
+            CodeBlockKind::Fenced { info: Some(info) } => info.split_whitespace().next(),
+            _ => None,
+        };
+        let listing = self.literal_block('-', &code_block.literal);
+        match language {
+            Some(language) => self
+                .arena
+                .text(format!("[source,{language}]"))
+                .append(self.arena.hardline())
+                .append(listing),
+            None => listing,
+        }
+    }
+
+    fn render_html_block(&self, html: &str) -> Doc<'a> {
+        self.literal_block('+', html)
+    }
+
+    /// The first row is the header, `cols` carries the column alignment.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let columns = table.rows.first().map(Vec::len).unwrap_or_default();
+        let cols = (0..columns)
+            .map(
+                |i| match table.alignments.get(i).copied().unwrap_or_default() {
+                    Alignment::Left | Alignment::None => "<",
+                    Alignment::Center => "^",
+                    Alignment::Right => ">",
+                },
+            )
+            .collect::<Vec<_>>()
+            .join(",");
+        let rows = table.rows.iter().map(|row| {
+            let cells = row.iter().map(|cell| {
+                self.arena
+                    .text("|")
+                    .append(self.render_inlines(cell))
+                    .group()
+            });
+            self.arena.intersperse(cells, self.arena.softline())
+        });
+        self.arena
+            .text(format!("[cols=\"{cols}\",options=\"header\"]"))
+            .append(self.arena.hardline())
+            .append(self.arena.text("|==="))
+            .append(self.arena.hardline())
+            .append(self.arena.intersperse(rows, self.arena.hardline()))
+            .append(self.arena.hardline())
+            .append(self.arena.text("|==="))
+    }
+
+    /// Container directives are rendered as open blocks with the directive name as
+    /// the style and the label as the title.
+    fn render_container_directive(&self, directive: &ContainerDirective) -> Doc<'a> {
+        let mut doc = self.arena.nil();
+        let label = plain_text(&directive.label, Some(&self.index));
+        if !label.is_empty() {
+            doc = doc
+                .append(self.arena.text(format!(".{label}")))
+                .append(self.arena.hardline());
+        }
+        doc.append(self.arena.text(format!(
+            "[{}]",
+            self.attributes(Some(&directive.name), &directive.attributes)
+        )))
+        .append(self.arena.hardline())
+        .append(self.delimited('-', || self.render_blocks(&directive.blocks)))
+    }
+
+    /// Leaf directives are rendered as block macros `name::label[attributes]`.
+    fn render_leaf_directive(&self, directive: &Directive) -> Doc<'a> {
+        let label = plain_text(&directive.label, Some(&self.index));
+        self.arena.text(format!(
+            "{}::{}[{}]",
+            directive.name,
+            label.replace(' ', "%20"),
+            self.attributes(None, &directive.attributes)
+        ))
+    }
+
+    /// Constrained markup is doubled where it is adjacent to a word.
+    fn render_inlines(&self, inlines: &[Inline]) -> Doc<'a> {
+        let outer = self.unconstrained.get();
+        let doc = self
+            .arena
+            .concat(inlines.iter().enumerate().map(|(i, inline)| {
+                let previous = match i.checked_sub(1).map(|i| &inlines[i]) {
+                    Some(Inline::Text(text)) => is_word_char(text.chars().next_back()),
+                    _ => false,
+                };
+                let next = match inlines.get(i + 1) {
+                    Some(Inline::Text(text)) => is_word_char(text.chars().next()),
+                    _ => false,
+                };
+                self.unconstrained.set(previous || next);
+                match (i.checked_sub(1).map(|i| &inlines[i]), inline) {
+                    // Text directly following another inline does not start a line
+                    (Some(previous), Inline::Text(text)) if *previous != Inline::LineBreak => {
+                        self.words(text, false)
+                    }
+                    _ => self.render_inline(inline),
+                }
+            }));
+        self.unconstrained.set(outer);
+        doc
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.words(text, true)
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena.text(" +").append(self.arena.hardline())
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        let marker = match self.unconstrained.get() {
+            true => "``",
+            false => "`",
+        };
+        if code.contains('+') {
+            let code = code.replace(']', "\\]");
+            self.arena.text(format!("{marker}pass:c[{code}]{marker}"))
+        } else {
+            self.arena.text(format!("{marker}+{code}+{marker}"))
+        }
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let same_text = matches!(
+            link.children.as_slice(),
+            [Inline::Text(text)] if *text == link.destination
+        );
+        let text = match same_text || link.children.is_empty() {
+            true => None,
+            false => Some(self.render_inlines(&link.children)),
+        };
+        self.link(&link.destination, text)
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        self.arena.text(self.image_macro(":", image))
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.quoted_text("_", children)
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.quoted_text("*", children)
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.arena
+            .text("[.line-through]")
+            .append(self.quoted_text("#", children))
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        if link.starts_with("www.") {
+            self.link(&format!("http://{link}"), Some(self.render_text(link)))
+        } else if !link.contains(':') && link.contains('@') {
+            self.arena.text(link.to_owned())
+        } else {
+            self.link(link, None)
+        }
+    }
+
+    /// The first reference to a footnote renders its content with `footnote:fn-n[…]`,
+    /// further references refer to it with `footnote:fn-n[]`.
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        let Some(n) = self.index.footnote_index(label) else {
+            return self.render_text(&format!("[^{label}]"));
+        };
+        let first = {
+            let mut rendered = self.footnote_references_rendered.borrow_mut();
+            let counter = rendered.entry(label.to_owned()).or_default();
+            *counter += 1;
+            *counter == 1
+        };
+        let open = self.open_footnotes.borrow().iter().any(|v| v == label);
+        let definition = self
+            .index
+            .footnotes()
+            .iter()
+            .find(|footnote| footnote.label == label);
+        match definition {
+            Some(definition) if first && !open => {
+                self.open_footnotes.borrow_mut().push(label.to_owned());
+                // Footnote content is inline, paragraphs are joined by spaces
+                let content = self.arena.intersperse(
+                    definition.blocks.iter().map(|block| match block {
+                        Block::Paragraph(inlines) => self.render_inlines(inlines),
+                        block => self.render_block(block),
+                    }),
+                    self.arena.softline(),
+                );
+                self.open_footnotes.borrow_mut().pop();
+                self.arena
+                    .text(format!("footnote:fn-{n}["))
+                    .append(content)
+                    .append(self.arena.text("]"))
+            }
+            _ => self.arena.text(format!("footnote:fn-{n}[]")),
+        }
+    }
+
+    /// Text directives are rendered as inline macros `name:label[attributes]`.
+    fn render_text_directive(&self, directive: &Directive) -> Doc<'a> {
+        let label = plain_text(&directive.label, Some(&self.index));
+        self.arena.text(format!(
+            "{}:{}[{}]",
+            directive.name,
+            label.replace(' ', "%20"),
+            self.attributes(None, &directive.attributes)
+        ))
+    }
+}
@@ -0,0 +1,103 @@
+#![cfg(test)]
+use crate::asciidoc_printer::config::Config;
+use crate::asciidoc_printer::render_asciidoc;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_asciidoc(&doc, Config::default())
+}
+
+#[rstest]
+#[case("# Title", "== Title")]
+#[case("### Three *x*", "==== Three _x_")]
+#[case("###### Six", "====== Six")]
+#[case("*em* **strong** ~~gone~~", "_em_ *strong* [.line-through]#gone#")]
+#[case("a`b`c", "a``+b+``c")]
+#[case("`a+b]`", "`pass:c[a+b\\]]`")]
+#[case(
+    "2 * 3 ^ x | y_ #z [a",
+    "2 {asterisk} 3 {caret} x {vbar} ypass:[_] pass:[#]z {startsb}a"
+)]
+#[case(
+    ". .x 1. NOTE: term:: -- foo",
+    "{empty}. {empty}.x {empty}1. {empty}NOTE: {empty}term:: {empty}-- foo"
+)]
+#[case("one  \ntwo", "one +\ntwo")]
+#[case(
+    "[docs](https://example.com) [page](other.adoc) <https://example.com>",
+    "https://example.com[docs] link:other.adoc[page] https://example.com"
+)]
+#[case("![A, B](a.png)", "image::a.png[\"A, B\"]")]
+#[case("See ![Logo](logo.png \"T\").", "See image:logo.png[Logo,title=T].")]
+#[case(
+    "> quoted\n>\n> > nested",
+    "____\nquoted\n\n_____\nnested\n_____\n____"
+)]
+#[case("> [!CAUTION]\n> Hot.", "[CAUTION]\n====\nHot.\n====")]
+#[case("```rust\nfn a() {}\n```", "[source,rust]\n----\nfn a() {}\n----")]
+#[case("```\n----\n```", "-----\n----\n-----")]
+#[case("<div>\n</div>", "++++\n<div>\n</div>\n++++")]
+#[case("a\n\n***\n\nb", "a\n\n'''\n\nb")]
+#[case("A[^n] B[^n]\n\n[^n]: Note.", "Afootnote:fn-1[Note.] Bfootnote:fn-1[]")]
+fn asciidoc(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input));
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        "* a\n** b\n. c\n+\n----\nx\n----\n* [x] done\n\n[start=3]\n. three",
+        render("- a\n    - b\n        1. c\n\n           ```\n           x\n           ```\n- [x] done\n\n3. three")
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        "[cols=\"<,>,^\",options=\"header\"]\n|===\n|Name |Size |Kind\n|`+a+` |1 |x\n|===",
+        render("| Name | Size | Kind |\n|------|-----:|:----:|\n| `a` | 1 | x |")
+    );
+}
+
+#[test]
+fn wrapping() {
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::default(),
+        "- one two three four five six",
+    )
+    .unwrap();
+    assert_eq!(
+        "* one two\nthree four\nfive six",
+        render_asciidoc(&doc, Config::default().with_width(10))
+    );
+}
+
+#[test]
+fn directives() {
+    use crate::ast::*;
+    let doc = Document {
+        blocks: vec![
+            Block::ContainerDirective(ContainerDirective {
+                name: "sidebar".to_owned(),
+                label: vec![Inline::Text("Aside".to_owned())],
+                attributes: vec![("role".to_owned(), "a,b".to_owned())],
+                blocks: vec![Block::Paragraph(vec![Inline::TextDirective(Directive {
+                    name: "kbd".to_owned(),
+                    label: vec![],
+                    attributes: vec![("keys".to_owned(), "Ctrl+T".to_owned())],
+                })])],
+            }),
+            Block::LeafDirective(Directive {
+                name: "include".to_owned(),
+                label: vec![Inline::Text("other.adoc".to_owned())],
+                attributes: vec![],
+            }),
+        ],
+    };
+    assert_eq!(
+        ".Aside\n[sidebar,role=\"a,b\"]\n----\nkbd:[keys=Ctrl+T]\n----\n\ninclude::other.adoc[]",
+        render_asciidoc(&doc, Config::default())
+    );
+}
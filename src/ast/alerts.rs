@@ -0,0 +1,114 @@
+//! GitHub alerts: block quotes starting with a `[!KIND]` marker line.
+//!
+//! Alerts are parsed as ordinary block quotes, where the marker is a link reference
+//! without definition. [`alert`] recognizes them for output formats with a matching
+//! construct, such as admonitions or info panels.
+//!
+//! ```rust
+//! use markdown_ppp::ast::alerts::{alert, AlertKind};
+//! use markdown_ppp::ast::{Block, Inline};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(MarkdownParserState::default(), "> [!TIP]\n> Use `cargo`.").unwrap();
+//! let Block::BlockQuote(blocks) = &doc.blocks[0] else { unreachable!() };
+//! let (kind, content) = alert(blocks).unwrap();
+//! assert_eq!(AlertKind::Tip, kind);
+//! assert_eq!(
+//!     vec![Block::Paragraph(vec![
+//!         Inline::Text("Use ".to_owned()),
+//!         Inline::Code("cargo".to_owned()),
+//!         Inline::Text(".".to_owned()),
+//!     ])],
+//!     content
+//! );
+//! ```
+
+use crate::ast::*;
+
+/// Kind of a GitHub alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    /// `[!NOTE]`
+    Note,
+
+    /// `[!TIP]`
+    Tip,
+
+    /// `[!IMPORTANT]`
+    Important,
+
+    /// `[!WARNING]`
+    Warning,
+
+    /// `[!CAUTION]`
+    Caution,
+}
+
+impl AlertKind {
+    /// Kind for the marker text between `[!` and `]`, ignoring case.
+    pub fn from_marker(marker: &str) -> Option<Self> {
+        match marker.to_ascii_uppercase().as_str() {
+            "NOTE" => Some(Self::Note),
+            "TIP" => Some(Self::Tip),
+            "IMPORTANT" => Some(Self::Important),
+            "WARNING" => Some(Self::Warning),
+            "CAUTION" => Some(Self::Caution),
+            _ => None,
+        }
+    }
+}
+
+/// Kind and content of a block quote which is an alert: its first paragraph starts
+/// with a `[!KIND]` marker followed by a line break or the end of the paragraph.
+/// The content is the block quote without the marker line.
+pub fn alert(blocks: &[Block]) -> Option<(AlertKind, Vec<Block>)> {
+    let (Block::Paragraph(inlines), rest) = blocks.split_first()? else {
+        return None;
+    };
+    let (kind, after) = match inlines.first()? {
+        Inline::LinkReference(link) => match link.text.as_slice() {
+            [Inline::Text(text)] => (marker_kind(&format!("[{text}]"))?, ""),
+            _ => return None,
+        },
+        Inline::Text(text) => {
+            let end = text.find(']')? + 1;
+            (marker_kind(&text[..end])?, &text[end..])
+        }
+        _ => return None,
+    };
+
+    // The marker must be alone on its line
+    let mut paragraph = Vec::with_capacity(inlines.len());
+    let after = match after.is_empty() {
+        true => match inlines.get(1) {
+            Some(Inline::Text(text)) => text.as_str(),
+            Some(Inline::LineBreak) | None => "",
+            Some(_) => return None,
+        },
+        false => after,
+    };
+    let remainder = after.trim_start_matches([' ', '\t']);
+    match remainder.strip_prefix('\n') {
+        Some(line) if !line.is_empty() => paragraph.push(Inline::Text(line.to_owned())),
+        Some(_) => (),
+        None if remainder.is_empty() => (),
+        None => return None,
+    }
+    let consumed = match (&inlines[0], inlines.get(1)) {
+        (Inline::LinkReference(_), Some(Inline::Text(_) | Inline::LineBreak)) => 2,
+        _ => 1,
+    };
+    paragraph.extend(inlines[consumed..].iter().cloned());
+
+    let mut content = Vec::with_capacity(blocks.len());
+    if !paragraph.is_empty() {
+        content.push(Block::Paragraph(paragraph));
+    }
+    content.extend(rest.iter().cloned());
+    Some((kind, content))
+}
+
+/// Kind of a complete `[!KIND]` marker.
+fn marker_kind(marker: &str) -> Option<AlertKind> {
+    AlertKind::from_marker(marker.strip_prefix("[!")?.strip_suffix(']')?)
+}
@@ -0,0 +1,242 @@
+//! Conversion of inline footnotes (`^[note]`) to regular footnotes.
+
+use crate::ast::*;
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+/// Replace every [`Inline::InlineFootnote`] with a [`Inline::FootnoteReference`] and
+/// append the matching [`FootnoteDefinition`]s to the end of the document.
+///
+/// Inline footnotes get numeric labels (`1`, `2`, …) in document order, skipping
+/// labels already used by regular footnotes.
+pub fn hoist_inline_footnotes(document: &mut Document) {
+    hoist_inline_footnotes_with_labels(document, &[]);
+}
+
+/// Hoist inline footnotes like [`hoist_inline_footnotes`], labeling a footnote with
+/// the label of the first footnote of `known` with the same content. Returns the
+/// labels given, with the original content of each footnote.
+pub(crate) fn hoist_inline_footnotes_with_labels(
+    document: &mut Document,
+    known: &[(Vec<Inline>, String)],
+) -> Vec<(Vec<Inline>, String)> {
+    let mut used = HashSet::new();
+    collect_definition_labels(&document.blocks, &mut used);
+    for block in &mut document.blocks {
+        for_each_inlines(block, &mut |inlines| collect_labels(inlines, &mut used));
+    }
+
+    let mut hoister = Hoister {
+        used,
+        next_label: 1,
+        definitions: Vec::new(),
+        known,
+        labels: Vec::new(),
+    };
+    for block in &mut document.blocks {
+        for_each_inlines(block, &mut |inlines| hoister.hoist(inlines));
+    }
+
+    document.blocks.extend(
+        hoister
+            .definitions
+            .into_iter()
+            .map(Block::FootnoteDefinition),
+    );
+    hoister.labels
+}
+
+/// The document with inline footnotes hoisted, or the document itself if it has
+/// none, for printers which number and render them like regular footnotes.
+#[cfg_attr(
+    not(any(
+        feature = "printer",
+        feature = "html-printer",
+        feature = "mdast",
+        feature = "ansi-printer",
+        feature = "asciidoc-printer",
+        feature = "confluence-printer",
+        feature = "docx-printer",
+        feature = "jira-printer",
+        feature = "latex-printer",
+        feature = "man-printer",
+        feature = "rst-printer",
+        feature = "slack-printer",
+        feature = "telegram-printer",
+        feature = "typst-printer"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn hoisted(document: &Document) -> Cow<'_, Document> {
+    if has_inline_footnotes(document) {
+        let mut document = document.clone();
+        hoist_inline_footnotes(&mut document);
+        Cow::Owned(document)
+    } else {
+        Cow::Borrowed(document)
+    }
+}
+
+/// Whether the document contains inline footnotes.
+pub fn has_inline_footnotes(document: &Document) -> bool {
+    fn inlines_have(inlines: &[Inline]) -> bool {
+        inlines.iter().any(|inline| match inline {
+            Inline::InlineFootnote(_) => true,
+            Inline::Emphasis(v) | Inline::Strong(v) | Inline::Strikethrough(v) => inlines_have(v),
+            Inline::Link(v) => inlines_have(&v.children),
+            Inline::LinkReference(v) => inlines_have(&v.text),
+            Inline::TextDirective(v) => inlines_have(&v.label),
+            _ => false,
+        })
+    }
+
+    fn block_has(block: &Block) -> bool {
+        match block {
+            Block::Paragraph(v) => inlines_have(v),
+            Block::Heading(v) => inlines_have(&v.content),
+            Block::BlockQuote(v) => v.iter().any(block_has),
+            Block::List(v) => v.items.iter().any(|item| item.blocks.iter().any(block_has)),
+            Block::Table(v) => v.rows.iter().flatten().any(|cell| inlines_have(cell)),
+            Block::FootnoteDefinition(v) => v.blocks.iter().any(block_has),
+            Block::ContainerDirective(v) => {
+                inlines_have(&v.label) || v.blocks.iter().any(block_has)
+            }
+            Block::LeafDirective(v) => inlines_have(&v.label),
+            Block::ThematicBreak
+            | Block::CodeBlock(_)
+            | Block::HtmlBlock(_)
+            | Block::Definition(_)
+            | Block::Empty => false,
+        }
+    }
+
+    document.blocks.iter().any(block_has)
+}
+
+struct Hoister<'a> {
+    used: HashSet<String>,
+    next_label: usize,
+    definitions: Vec<FootnoteDefinition>,
+    known: &'a [(Vec<Inline>, String)],
+    labels: Vec<(Vec<Inline>, String)>,
+}
+
+impl Hoister<'_> {
+    fn hoist(&mut self, inlines: &mut [Inline]) {
+        for inline in inlines {
+            match inline {
+                Inline::InlineFootnote(content) => {
+                    let label = match self.known.iter().find(|(known, _)| known == content) {
+                        Some((_, label)) => label.clone(),
+                        None => self.new_label(),
+                    };
+                    self.labels.push((content.clone(), label.clone()));
+                    let mut content = std::mem::take(content);
+                    *inline = Inline::FootnoteReference(label.clone());
+                    let index = self.definitions.len();
+                    self.definitions.push(FootnoteDefinition {
+                        label,
+                        blocks: Vec::new(),
+                    });
+                    // Nested inline footnotes are numbered after the enclosing one
+                    self.hoist(&mut content);
+                    self.definitions[index].blocks = vec![Block::Paragraph(content)];
+                }
+                Inline::Emphasis(v) | Inline::Strong(v) | Inline::Strikethrough(v) => self.hoist(v),
+                Inline::Link(v) => self.hoist(&mut v.children),
+                Inline::LinkReference(v) => self.hoist(&mut v.text),
+                Inline::TextDirective(v) => self.hoist(&mut v.label),
+                _ => (),
+            }
+        }
+    }
+
+    fn new_label(&mut self) -> String {
+        loop {
+            let label = self.next_label.to_string();
+            self.next_label += 1;
+            if self.used.insert(label.clone()) {
+                return label;
+            }
+        }
+    }
+}
+
+fn collect_labels(inlines: &mut [Inline], used: &mut HashSet<String>) {
+    for inline in inlines {
+        match inline {
+            Inline::FootnoteReference(label) => {
+                used.insert(label.clone());
+            }
+            Inline::InlineFootnote(v)
+            | Inline::Emphasis(v)
+            | Inline::Strong(v)
+            | Inline::Strikethrough(v) => collect_labels(v, used),
+            Inline::Link(v) => collect_labels(&mut v.children, used),
+            Inline::LinkReference(v) => collect_labels(&mut v.text, used),
+            Inline::TextDirective(v) => collect_labels(&mut v.label, used),
+            _ => (),
+        }
+    }
+}
+
+fn collect_definition_labels(blocks: &[Block], used: &mut HashSet<String>) {
+    for block in blocks {
+        match block {
+            Block::FootnoteDefinition(v) => {
+                used.insert(v.label.clone());
+                collect_definition_labels(&v.blocks, used);
+            }
+            Block::BlockQuote(v) => collect_definition_labels(v, used),
+            Block::List(v) => {
+                for item in &v.items {
+                    collect_definition_labels(&item.blocks, used);
+                }
+            }
+            Block::ContainerDirective(v) => collect_definition_labels(&v.blocks, used),
+            _ => (),
+        }
+    }
+}
+
+/// Call `f` for every top-level inline sequence of the block and its nested blocks.
+fn for_each_inlines(block: &mut Block, f: &mut impl FnMut(&mut Vec<Inline>)) {
+    match block {
+        Block::Paragraph(v) => f(v),
+        Block::Heading(v) => f(&mut v.content),
+        Block::BlockQuote(v) => {
+            for block in v {
+                for_each_inlines(block, f);
+            }
+        }
+        Block::List(v) => {
+            for item in &mut v.items {
+                for block in &mut item.blocks {
+                    for_each_inlines(block, f);
+                }
+            }
+        }
+        Block::Table(v) => {
+            for cell in v.rows.iter_mut().flatten() {
+                f(cell);
+            }
+        }
+        Block::FootnoteDefinition(v) => {
+            for block in &mut v.blocks {
+                for_each_inlines(block, f);
+            }
+        }
+        Block::ContainerDirective(v) => {
+            f(&mut v.label);
+            for block in &mut v.blocks {
+                for_each_inlines(block, f);
+            }
+        }
+        Block::LeafDirective(v) => f(&mut v.label),
+        Block::ThematicBreak
+        | Block::CodeBlock(_)
+        | Block::HtmlBlock(_)
+        | Block::Definition(_)
+        | Block::Empty => (),
+    }
+}
@@ -14,6 +14,10 @@
 //!                     └─ ...
 //! ```
 
+pub mod alerts;
+pub mod footnotes;
+pub mod typographer;
+
 // ——————————————————————————————————————————————————————————————————————————
 // Document root
 // ——————————————————————————————————————————————————————————————————————————
@@ -64,6 +68,12 @@ pub enum Block {
     /// Footnote definition
     FootnoteDefinition(FootnoteDefinition),
 
+    /// Container directive (`:::name[label]{attrs}` … `:::`)
+    ContainerDirective(ContainerDirective),
+
+    /// Leaf block directive (`::name[label]{attrs}`)
+    LeafDirective(Directive),
+
     /// Empty block. This is used to represent skipped blocks in the AST.
     Empty,
 }
@@ -270,6 +280,43 @@ pub struct FootnoteDefinition {
     pub blocks: Vec<Block>,
 }
 
+// ——————————————————————————————————————————————————————————————————————————
+// Directives
+// ——————————————————————————————————————————————————————————————————————————
+
+/// Leaf block directive (`::name[label]{attrs}`) or inline text directive
+/// (`:name[label]{attrs}`) from the generic directives proposal.
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Directive {
+    /// Directive name.
+    pub name: String,
+
+    /// Label (`[…]`), empty if absent.
+    pub label: Vec<Inline>,
+
+    /// Attributes (`{#id .class key="value"}`) in source order. `#id` is stored as
+    /// `id` and all `.class` shortcuts are joined into a single `class` attribute.
+    pub attributes: Vec<(String, String)>,
+}
+
+/// Container directive (`:::name[label]{attrs}` … `:::`) wrapping nested blocks.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContainerDirective {
+    /// Directive name.
+    pub name: String,
+
+    /// Label (`[…]`), empty if absent.
+    pub label: Vec<Inline>,
+
+    /// Attributes, see [`Directive::attributes`].
+    pub attributes: Vec<(String, String)>,
+
+    /// Nested blocks.
+    pub blocks: Vec<Block>,
+}
+
 // ——————————————————————————————————————————————————————————————————————————
 // Inline‑level nodes
 // ——————————————————————————————————————————————————————————————————————————
@@ -305,12 +352,25 @@ pub enum Inline {
     /// Strikethrough (`~~`)
     Strikethrough(Vec<Inline>),
 
-    /// Autolink (`<https://>` or `<mailto:…>`)
+    /// Autolink (`<https://>` or `<mailto:…>`), or a GFM extended autolink
+    /// (bare `https://…`, `www.…` or email address) stored as written.
     Autolink(String),
 
     /// Footnote reference (`[^label]`)
     FootnoteReference(String),
 
+    /// Inline footnote with its content written in place (`^[note text]`)
+    InlineFootnote(Vec<Inline>),
+
+    /// Text directive (`:name[label]{attrs}`)
+    TextDirective(Directive),
+
+    /// Wiki-link (`[[Page]]`, `[[Page#Heading|label]]`) or embed (`![[file]]`)
+    WikiLink(WikiLink),
+
+    /// User mention, issue or commit reference (`@user`, `#123`, `org/repo#45`)
+    Mention(Mention),
+
     /// Empty element. This is used to represent skipped elements in the AST.
     Empty,
 }
@@ -352,3 +412,46 @@ pub struct LinkReference {
     /// Link text
     pub text: Vec<Inline>,
 }
+
+/// Obsidian-style wiki-link or embed.
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WikiLink {
+    /// Link target (page or file name) without the heading fragment.
+    pub target: String,
+
+    /// Optional heading fragment (`[[Page#Heading]]`).
+    pub fragment: Option<String>,
+
+    /// Optional alias used as the link text (`[[Page|label]]`).
+    pub alias: Option<String>,
+
+    /// `true` for embeds (`![[file]]`).
+    pub embed: bool,
+}
+
+/// User mention or reference to an issue, pull request or commit.
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "ast-serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mention {
+    /// User or team mention (`@user`, `@org/team`), without the leading `@`.
+    User(String),
+
+    /// Issue or pull request reference (`#123`, `org/repo#45`).
+    Issue {
+        /// Repository (`org/repo`) if the reference is qualified.
+        repository: Option<String>,
+
+        /// Issue or pull request number.
+        number: u64,
+    },
+
+    /// Commit reference (`a5c3785ed8d6`, `org/repo@a5c3785`).
+    Commit {
+        /// Repository (`org/repo`) if the reference is qualified.
+        repository: Option<String>,
+
+        /// Commit SHA as written (7 to 40 hex digits).
+        sha: String,
+    },
+}
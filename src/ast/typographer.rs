@@ -0,0 +1,332 @@
+//! Smart punctuation: straight quotes to typographic quotes, `--` / `---` to
+//! en / em dashes and `...` to an ellipsis.
+//!
+//! Only [`Inline::Text`] is rewritten. Code spans, code blocks, raw HTML, URLs and
+//! link labels (which identify link definitions) are left untouched, and
+//! backslash-escaped characters are kept as written. Quotes are paired across
+//! emphasis and link boundaries within a paragraph, heading or table cell.
+//!
+//! The pass can be applied to any document, e.g. before rendering to HTML:
+//!
+//! ```rust
+//! use markdown_ppp::ast::typographer::{smart_punctuation, Locale, TypographerConfig};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let mut doc = parse_markdown(MarkdownParserState::default(), "\"Hi\" -- it's me...").unwrap();
+//! smart_punctuation(&mut doc, &TypographerConfig::default().with_locale(Locale::German));
+//! let html = markdown_ppp::html_printer::render_html(&doc, Default::default());
+//! assert_eq!(html, "<p>„Hi“ – it’s me…</p>");
+//! ```
+//!
+//! or enabled in the parser with
+//! [`MarkdownParserConfig::with_smart_punctuation`](crate::parser::config::MarkdownParserConfig::with_smart_punctuation).
+
+use crate::ast::*;
+
+/// Quotation style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// “double” and ‘single’
+    #[default]
+    English,
+
+    /// „double“ and ‚single‘
+    German,
+
+    /// « double » and ‹ single ›, separated from the quoted text by no-break spaces
+    French,
+}
+
+impl Locale {
+    /// Opening and closing double quote.
+    fn double_quotes(self) -> (char, char) {
+        match self {
+            Locale::English => ('“', '”'),
+            Locale::German => ('„', '“'),
+            Locale::French => ('«', '»'),
+        }
+    }
+
+    /// Opening and closing single quote.
+    fn single_quotes(self) -> (char, char) {
+        match self {
+            Locale::English => ('‘', '’'),
+            Locale::German => ('‚', '‘'),
+            Locale::French => ('‹', '›'),
+        }
+    }
+
+    fn is_spaced(self) -> bool {
+        self == Locale::French
+    }
+}
+
+/// Configuration of the smart punctuation pass. All replacements are enabled by
+/// default.
+#[derive(Debug, Clone)]
+pub struct TypographerConfig {
+    locale: Locale,
+    quotes: bool,
+    dashes: bool,
+    ellipsis: bool,
+}
+
+impl Default for TypographerConfig {
+    fn default() -> Self {
+        Self {
+            locale: Locale::default(),
+            quotes: true,
+            dashes: true,
+            ellipsis: true,
+        }
+    }
+}
+
+impl TypographerConfig {
+    /// Set the quotation style. Default is [`Locale::English`].
+    pub fn with_locale(self, locale: Locale) -> Self {
+        Self { locale, ..self }
+    }
+
+    /// Enable or disable replacement of straight quotes and apostrophes.
+    pub fn with_quotes(self, quotes: bool) -> Self {
+        Self { quotes, ..self }
+    }
+
+    /// Enable or disable replacement of `--` with an en dash and `---` with an em dash.
+    pub fn with_dashes(self, dashes: bool) -> Self {
+        Self { dashes, ..self }
+    }
+
+    /// Enable or disable replacement of `...` with an ellipsis.
+    pub fn with_ellipsis(self, ellipsis: bool) -> Self {
+        Self { ellipsis, ..self }
+    }
+}
+
+/// Apply smart punctuation to all text of the document.
+pub fn smart_punctuation(document: &mut Document, config: &TypographerConfig) {
+    for block in &mut document.blocks {
+        block_punctuation(block, config);
+    }
+}
+
+fn block_punctuation(block: &mut Block, config: &TypographerConfig) {
+    match block {
+        Block::Paragraph(inlines) => inlines_punctuation(inlines, config),
+        Block::Heading(v) => inlines_punctuation(&mut v.content, config),
+        Block::BlockQuote(blocks) => {
+            for block in blocks {
+                block_punctuation(block, config);
+            }
+        }
+        Block::List(v) => {
+            for item in &mut v.items {
+                for block in &mut item.blocks {
+                    block_punctuation(block, config);
+                }
+            }
+        }
+        Block::Table(v) => {
+            for cell in v.rows.iter_mut().flatten() {
+                inlines_punctuation(cell, config);
+            }
+        }
+        Block::FootnoteDefinition(v) => {
+            for block in &mut v.blocks {
+                block_punctuation(block, config);
+            }
+        }
+        Block::ContainerDirective(v) => {
+            inlines_punctuation(&mut v.label, config);
+            for block in &mut v.blocks {
+                block_punctuation(block, config);
+            }
+        }
+        Block::LeafDirective(v) => inlines_punctuation(&mut v.label, config),
+        Block::ThematicBreak
+        | Block::CodeBlock(_)
+        | Block::HtmlBlock(_)
+        | Block::Definition(_)
+        | Block::Empty => (),
+    }
+}
+
+/// Part of a run of inline content, in reading order.
+enum Segment<'a> {
+    Text(&'a mut String),
+    /// Content which is not rewritten but reads as a word (code, links, …)
+    Word,
+    LineBreak,
+}
+
+impl Segment<'_> {
+    fn first_char(&self) -> Option<char> {
+        match self {
+            Segment::Text(text) => text.chars().next(),
+            Segment::Word => Some('a'),
+            Segment::LineBreak => Some('\n'),
+        }
+    }
+}
+
+fn inlines_punctuation(inlines: &mut [Inline], config: &TypographerConfig) {
+    let mut segments = Vec::new();
+    let mut nested = Vec::new();
+    collect_segments(inlines, &mut segments, &mut nested);
+
+    let mut state = State::default();
+    for i in 0..segments.len() {
+        let next = segments[i + 1..].iter().find_map(Segment::first_char);
+        match &mut segments[i] {
+            Segment::Text(text) => **text = state.convert(text, next, config),
+            Segment::Word => state.prev = Some('a'),
+            Segment::LineBreak => state.prev = Some('\n'),
+        }
+    }
+
+    // Directive labels and inline footnotes are separate runs
+    for label in nested {
+        inlines_punctuation(label, config);
+    }
+}
+
+fn collect_segments<'a>(
+    inlines: &'a mut [Inline],
+    segments: &mut Vec<Segment<'a>>,
+    nested: &mut Vec<&'a mut Vec<Inline>>,
+) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => segments.push(Segment::Text(text)),
+            Inline::LineBreak => segments.push(Segment::LineBreak),
+            Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children) => collect_segments(children, segments, nested),
+            Inline::Link(v) => collect_segments(&mut v.children, segments, nested),
+            Inline::LinkReference(v) => collect_segments(&mut v.text, segments, nested),
+            Inline::TextDirective(Directive { label: v, .. }) | Inline::InlineFootnote(v) => {
+                nested.push(v);
+                segments.push(Segment::Word);
+            }
+            Inline::Code(_)
+            | Inline::Html(_)
+            | Inline::Image(_)
+            | Inline::Autolink(_)
+            | Inline::FootnoteReference(_)
+            | Inline::WikiLink(_)
+            | Inline::Mention(_) => segments.push(Segment::Word),
+            Inline::Empty => (),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Last character of the already converted text, `None` at the start of the run
+    prev: Option<char>,
+    single_open: bool,
+    double_open: bool,
+}
+
+impl State {
+    fn convert(&mut self, text: &str, next: Option<char>, config: &TypographerConfig) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let mut result = String::with_capacity(text.len());
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let rest = &chars[i..];
+            let next_char = chars.get(i + 1).copied().or(next);
+
+            if c == '\\' && i + 1 < chars.len() {
+                result.push(c);
+                result.push(chars[i + 1]);
+                i += 2;
+            } else if config.dashes && rest.starts_with(&['-', '-', '-']) {
+                result.push('—');
+                i += 3;
+            } else if config.dashes && rest.starts_with(&['-', '-']) {
+                result.push('–');
+                i += 2;
+            } else if config.ellipsis && rest.starts_with(&['.', '.', '.']) {
+                result.push('…');
+                i += 3;
+            } else if config.quotes && c == '"' {
+                self.double_quote(&mut result, next_char, config.locale);
+                i += 1;
+            } else if config.quotes && c == '\'' {
+                self.single_quote(&mut result, next_char, config.locale);
+                i += 1;
+            } else {
+                result.push(c);
+                i += 1;
+            }
+            self.prev = result.chars().next_back().or(self.prev);
+        }
+        result
+    }
+
+    fn double_quote(&mut self, result: &mut String, next: Option<char>, locale: Locale) {
+        let (open, close) = locale.double_quotes();
+        if self.opens(next, locale) {
+            self.double_open = true;
+            push_open(result, open, locale);
+        } else if self.double_open || self.prev.is_some_and(|c| !c.is_whitespace()) {
+            self.double_open = false;
+            push_close(result, close, locale);
+        } else {
+            result.push('"');
+        }
+    }
+
+    fn single_quote(&mut self, result: &mut String, next: Option<char>, locale: Locale) {
+        let (open, close) = locale.single_quotes();
+        let inside_word =
+            self.prev.is_some_and(char::is_alphanumeric) && next.is_some_and(char::is_alphanumeric);
+        if self.opens(next, locale) && !next.is_some_and(|c| c.is_ascii_digit()) {
+            self.single_open = true;
+            push_open(result, open, locale);
+        } else if self.single_open && !inside_word {
+            self.single_open = false;
+            push_close(result, close, locale);
+        } else {
+            // Apostrophe, including elisions like '90s
+            result.push('’');
+        }
+    }
+
+    /// A quote opens when it follows whitespace or opening punctuation and is
+    /// followed by text.
+    fn opens(&self, next: Option<char>, locale: Locale) -> bool {
+        let after_opening = match self.prev {
+            None => true,
+            Some(c) => {
+                c.is_whitespace()
+                    || "([{<-–—/".contains(c)
+                    || c == locale.double_quotes().0
+                    || c == locale.single_quotes().0
+            }
+        };
+        after_opening && next.is_some_and(|c| !c.is_whitespace())
+    }
+}
+
+fn push_open(result: &mut String, quote: char, locale: Locale) {
+    result.push(quote);
+    if locale.is_spaced() {
+        result.push('\u{a0}');
+    }
+}
+
+fn push_close(result: &mut String, quote: char, locale: Locale) {
+    if locale.is_spaced() {
+        if result.ends_with(' ') {
+            result.pop();
+        }
+        if !result.ends_with('\u{a0}') {
+            result.push('\u{a0}');
+        }
+    }
+    result.push(quote);
+}
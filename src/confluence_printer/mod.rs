@@ -0,0 +1,365 @@
+//! Rendering of the AST to the Confluence storage format.
+//!
+//! The storage format is XHTML with Confluence macros: code blocks become `code`
+//! macros, GitHub alerts (see [`alerts`](crate::ast::alerts)) become `info`, `tip`,
+//! `note` and `warning` macros, task lists become Confluence task lists and wiki-links
+//! become page links. Images without a URL scheme refer to page attachments.
+//!
+//! ```rust
+//! use markdown_ppp::confluence_printer::{config::Config, render_confluence};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(MarkdownParserState::default(), "# Setup\n\n```sh\nmake\n```").unwrap();
+//! assert_eq!(
+//!     "<h1>Setup</h1>\n\
+//!      <ac:structured-macro ac:name=\"code\">\n\
+//!      <ac:parameter ac:name=\"language\">sh</ac:parameter>\n\
+//!      <ac:plain-text-body><![CDATA[make]]></ac:plain-text-body>\n\
+//!      </ac:structured-macro>",
+//!     render_confluence(&doc, Config::default())
+//! );
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::ast::alerts::{alert, AlertKind};
+use crate::ast::*;
+use crate::confluence_printer::config::Config;
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+
+/// Render the given Markdown AST to Confluence storage format.
+pub fn render_confluence(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = ConfluenceRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+/// Escape text for XHTML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// CDATA section, split where the text contains its end marker.
+fn cdata(text: &str) -> String {
+    format!("<![CDATA[{}]]>", text.replace("]]>", "]]]]><![CDATA[>"))
+}
+
+struct ConfluenceRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+}
+
+impl<'a> ConfluenceRenderer<'a> {
+    /// Inline element `<tag>content</tag>`.
+    fn element(&self, tag: &str, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text(format!("<{tag}>"))
+            .append(content)
+            .append(self.arena.text(format!("</{}>", tag_name(tag))))
+    }
+
+    /// Block element with its opening and closing tags on separate lines. Content is
+    /// not indented, as it may contain code.
+    fn block_element(&self, tag: &str, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text(format!("<{tag}>"))
+            .append(self.arena.hardline())
+            .append(content)
+            .append(self.arena.hardline())
+            .append(self.arena.text(format!("</{}>", tag_name(tag))))
+    }
+
+    /// Macro with a rich text body.
+    fn rich_text_macro(&self, name: &str, blocks: &[Block]) -> Doc<'a> {
+        self.block_element(
+            &format!("ac:structured-macro ac:name=\"{name}\""),
+            self.block_element("ac:rich-text-body", self.render_blocks(blocks)),
+        )
+    }
+
+    /// Content of a list item, without paragraph tags for a single paragraph.
+    fn item_content(&self, blocks: &[Block]) -> Doc<'a> {
+        match blocks {
+            [Block::Paragraph(inlines)] => self.render_inlines(inlines),
+            blocks => self.render_blocks(blocks),
+        }
+    }
+
+    fn render_task_list(&self, list: &List) -> Doc<'a> {
+        let tasks = list.items.iter().map(|item| {
+            let status = match item.task {
+                Some(TaskState::Complete) => "complete",
+                _ => "incomplete",
+            };
+            self.block_element(
+                "ac:task",
+                self.arena
+                    .text(format!("<ac:task-status>{status}</ac:task-status>"))
+                    .append(self.arena.hardline())
+                    .append(self.element("ac:task-body", self.item_content(&item.blocks))),
+            )
+        });
+        self.block_element(
+            "ac:task-list",
+            self.arena.intersperse(tasks, self.arena.hardline()),
+        )
+    }
+}
+
+/// Tag name of an opening tag with attributes.
+fn tag_name(tag: &str) -> &str {
+    tag.split(' ').next().unwrap_or(tag)
+}
+
+impl<'a> Renderer<'a> for ConfluenceRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    fn block_separator(&self) -> Doc<'a> {
+        self.arena.hardline()
+    }
+
+    fn render_paragraph(&self, inlines: &[Inline]) -> Doc<'a> {
+        self.element("p", self.render_inlines(inlines)).group()
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        self.element(&format!("h{level}"), self.render_inlines(&heading.content))
+            .group()
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("<hr />")
+    }
+
+    /// GitHub alerts are rendered as panel macros.
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        match alert(blocks) {
+            Some((kind, content)) => {
+                let name = match kind {
+                    AlertKind::Note | AlertKind::Important => "info",
+                    AlertKind::Tip => "tip",
+                    AlertKind::Warning => "note",
+                    AlertKind::Caution => "warning",
+                };
+                self.rich_text_macro(name, &content)
+            }
+            None => self.block_element("blockquote", self.render_blocks(blocks)),
+        }
+    }
+
+    /// Lists where every item is a task are rendered as task lists.
+    fn render_list(&self, list: &List) -> Doc<'a> {
+        if !list.items.is_empty() && list.items.iter().all(|item| item.task.is_some()) {
+            return self.render_task_list(list);
+        }
+        let tag = match &list.kind {
+            ListKind::Ordered(options) if options.start != 1 => {
+                format!("ol start=\"{}\"", options.start)
+            }
+            ListKind::Ordered(_) => "ol".to_owned(),
+            ListKind::Bullet(_) => "ul".to_owned(),
+        };
+        let items = list
+            .items
+            .iter()
+            .map(|item| self.render_list_item(list, None, item));
+        self.block_element(&tag, self.arena.intersperse(items, self.arena.hardline()))
+    }
+
+    fn render_list_item(&self, _list: &List, _number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let task = match item.task {
+            Some(TaskState::Complete) => "\u{2611} ",
+            Some(TaskState::Incomplete) => "\u{2610} ",
+            None => "",
+        };
+        self.element(
+            "li",
+            self.arena
+                .text(task)
+                .append(self.item_content(&item.blocks)),
+        )
+    }
+
+    /// Code blocks are rendered as `code` macros with the language of the info string.
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let literal = literal.unwrap_or(&code_block.literal);
+        let mut content = self.arena.nil();
+        if let CodeBlockKind::Fenced { info: Some(info) } = &code_block.kind {
+            if let Some(language) = info.split_whitespace().next() {
+                content = content
+                    .append(self.arena.text(format!(
+                        "<ac:parameter ac:name=\"language\">{}</ac:parameter>",
+                        escape(language)
+                    )))
+                    .append(self.arena.hardline());
+            }
+        }
+        let body = self.arena.intersperse(
+            cdata(literal)
+                .split('\n')
+                .map(|line| self.arena.text(line.to_owned())),
+            self.arena.hardline(),
+        );
+        content = content.append(self.element("ac:plain-text-body", body));
+        self.block_element("ac:structured-macro ac:name=\"code\"", content)
+    }
+
+    /// The first row is rendered with header cells.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let rows = table.rows.iter().enumerate().map(|(row, cells)| {
+            let tag = if row == 0 { "th" } else { "td" };
+            let cells = cells.iter().enumerate().map(|(i, cell)| {
+                let tag = match table.alignments.get(i).copied().unwrap_or_default() {
+                    Alignment::Left => format!("{tag} style=\"text-align: left;\""),
+                    Alignment::Center => format!("{tag} style=\"text-align: center;\""),
+                    Alignment::Right => format!("{tag} style=\"text-align: right;\""),
+                    Alignment::None => tag.to_owned(),
+                };
+                self.element(&tag, self.render_inlines(cell)).group()
+            });
+            self.block_element("tr", self.arena.intersperse(cells, self.arena.hardline()))
+        });
+        self.block_element(
+            "table",
+            self.block_element("tbody", self.arena.intersperse(rows, self.arena.hardline())),
+        )
+    }
+
+    /// Footnotes are rendered as a numbered list.
+    fn render_footnotes(&self, footnotes: &[FootnoteDefinition]) -> Doc<'a> {
+        let items = footnotes
+            .iter()
+            .map(|footnote| self.element("li", self.item_content(&footnote.blocks)));
+        self.arena
+            .text("<hr />")
+            .append(self.arena.hardline())
+            .append(self.block_element("ol", self.arena.intersperse(items, self.arena.hardline())))
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            self.arena.softline(),
+        )
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena.text("<br />").append(self.arena.hardline())
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        self.element("code", self.arena.text(escape(code)))
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let mut tag = format!("a href=\"{}\"", escape(&link.destination));
+        if let Some(title) = &link.title {
+            tag.push_str(&format!(" title=\"{}\"", escape(title)));
+        }
+        self.element(&tag, self.render_inlines(&link.children))
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        let mut tag = "ac:image".to_owned();
+        if !image.alt.is_empty() {
+            tag.push_str(&format!(" ac:alt=\"{}\"", escape(&image.alt)));
+        }
+        if let Some(title) = &image.title {
+            tag.push_str(&format!(" ac:title=\"{}\"", escape(title)));
+        }
+        let resource = match image.destination.contains("://") {
+            true => format!("<ri:url ri:value=\"{}\" />", escape(&image.destination)),
+            false => format!(
+                "<ri:attachment ri:filename=\"{}\" />",
+                escape(&image.destination)
+            ),
+        };
+        self.element(&tag, self.arena.text(resource))
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.element("em", self.render_inlines(children))
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.element("strong", self.render_inlines(children))
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.element(
+            "span style=\"text-decoration: line-through;\"",
+            self.render_inlines(children),
+        )
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        let destination = if link.starts_with("www.") {
+            format!("http://{link}")
+        } else if !link.contains(':') && link.contains('@') {
+            format!("mailto:{link}")
+        } else {
+            link.to_owned()
+        };
+        self.element(
+            &format!("a href=\"{}\"", escape(&destination)),
+            self.arena.text(escape(link)),
+        )
+    }
+
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        match self.index.footnote_index(label) {
+            Some(n) => self.element("sup", self.arena.text(n.to_string())),
+            None => self.render_text(&format!("[^{label}]")),
+        }
+    }
+
+    /// Wiki-links are rendered as links to the Confluence page with the target title.
+    fn render_wiki_link(&self, link: &WikiLink) -> Doc<'a> {
+        let mut tag = "ac:link".to_owned();
+        if let Some(fragment) = &link.fragment {
+            tag.push_str(&format!(" ac:anchor=\"{}\"", escape(fragment)));
+        }
+        let mut content = self.arena.nil();
+        if !link.target.is_empty() {
+            content = content.append(self.arena.text(format!(
+                "<ri:page ri:content-title=\"{}\" />",
+                escape(&link.target)
+            )));
+        }
+        if let Some(alias) = &link.alias {
+            content = content
+                .append(self.element("ac:plain-text-link-body", self.arena.text(cdata(alias))));
+        }
+        self.element(&tag, content)
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(test)]
+use crate::confluence_printer::config::Config;
+use crate::confluence_printer::render_confluence;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_confluence(&doc, Config::default())
+}
+
+#[rstest]
+#[case("## Title *em*", "<h2>Title <em>em</em></h2>")]
+#[case(
+    "**a** ~~b~~ `<c>` & \"d\"",
+    "<p><strong>a</strong> <span style=\"text-decoration: line-through;\">b</span>\n\
+     <code>&lt;c&gt;</code> &amp; &quot;d&quot;</p>"
+)]
+#[case("one  \ntwo", "<p>one<br />\ntwo</p>")]
+#[case(
+    "[docs](https://example.com?a=1&b=2 \"Docs\")",
+    "<p><a href=\"https://example.com?a=1&amp;b=2\" title=\"Docs\">docs</a></p>"
+)]
+#[case(
+    "![Logo](https://example.com/logo.png) ![Chart](chart.png)",
+    "<p><ac:image ac:alt=\"Logo\"><ri:url ri:value=\"https://example.com/logo.png\" /></ac:image>\n\
+     <ac:image ac:alt=\"Chart\"><ri:attachment ri:filename=\"chart.png\" /></ac:image></p>"
+)]
+#[case("***", "<hr />")]
+#[case("> quoted", "<blockquote>\n<p>quoted</p>\n</blockquote>")]
+#[case(
+    "```\nlet a = b[c[0]]>d;\n\n```",
+    "<ac:structured-macro ac:name=\"code\">\n\
+     <ac:plain-text-body><![CDATA[let a = b[c[0]]]]><![CDATA[>d;]]></ac:plain-text-body>\n\
+     </ac:structured-macro>"
+)]
+fn confluence(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input));
+}
+
+#[rstest]
+#[case("NOTE", "info")]
+#[case("TIP", "tip")]
+#[case("IMPORTANT", "info")]
+#[case("WARNING", "note")]
+#[case("caution", "warning")]
+fn alerts(#[case] marker: &str, #[case] name: &str) {
+    assert_eq!(
+        format!(
+            "<ac:structured-macro ac:name=\"{name}\">\n\
+             <ac:rich-text-body>\n\
+             <p>Read <em>this</em>.</p>\n\
+             <p>More.</p>\n\
+             </ac:rich-text-body>\n\
+             </ac:structured-macro>"
+        ),
+        render(&format!("> [!{marker}]\n> Read *this*.\n>\n> More."))
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        "<ul>\n<li>a</li>\n<li><p>b</p>\n<ol start=\"3\">\n<li>c</li>\n</ol></li>\n</ul>",
+        render("- a\n- b\n    3. c")
+    );
+    assert_eq!(
+        "<ac:task-list>\n\
+         <ac:task>\n<ac:task-status>complete</ac:task-status>\n<ac:task-body>done</ac:task-body>\n</ac:task>\n\
+         <ac:task>\n<ac:task-status>incomplete</ac:task-status>\n<ac:task-body>todo</ac:task-body>\n</ac:task>\n\
+         </ac:task-list>",
+        render("- [x] done\n- [ ] todo")
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        "<table>\n<tbody>\n\
+         <tr>\n<th>Name</th>\n<th style=\"text-align: right;\">Size</th>\n</tr>\n\
+         <tr>\n<td><code>a</code></td>\n<td style=\"text-align: right;\">1</td>\n</tr>\n\
+         </tbody>\n</table>",
+        render("| Name | Size |\n|------|-----:|\n| `a` | 1 |")
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        "<p>Text<sup>1</sup></p>\n<hr />\n<ol>\n<li>Note.</li>\n</ol>",
+        render("Text[^n]\n\n[^n]: Note.")
+    );
+}
+
+#[test]
+fn wiki_links() {
+    use crate::ast::*;
+    let doc = Document {
+        blocks: vec![Block::Paragraph(vec![Inline::WikiLink(WikiLink {
+            target: "Release notes".to_owned(),
+            fragment: Some("Fixes".to_owned()),
+            alias: Some("fixes".to_owned()),
+            embed: false,
+        })])],
+    };
+    assert_eq!(
+        "<p><ac:link ac:anchor=\"Fixes\"><ri:page ri:content-title=\"Release notes\" />\
+         <ac:plain-text-link-body><![CDATA[fixes]]></ac:plain-text-link-body></ac:link></p>",
+        render_confluence(&doc, Config::default())
+    );
+}
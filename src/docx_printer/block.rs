@@ -0,0 +1,153 @@
+use crate::ast::alerts::{alert, AlertKind};
+use crate::ast::*;
+use crate::docx_printer::{run, text, Context, Numbering, Part, RunStyle, Writer};
+
+impl Writer<'_> {
+    pub(crate) fn blocks(&mut self, part: &mut Part, context: Context, blocks: &[Block]) {
+        for block in blocks {
+            self.block(part, context, block);
+        }
+    }
+
+    fn block(&mut self, part: &mut Part, context: Context, block: &Block) {
+        match block {
+            Block::Paragraph(inlines) => {
+                let runs = self.inlines(part, RunStyle::default(), inlines);
+                part.paragraph(context, None, &runs);
+            }
+            Block::Heading(heading) => {
+                let level = match heading.kind {
+                    HeadingKind::Atx(level) => level.clamp(1, 6),
+                    HeadingKind::Setext(SetextHeading::Level1) => 1,
+                    HeadingKind::Setext(SetextHeading::Level2) => 2,
+                };
+                let runs = self.inlines(part, RunStyle::default(), &heading.content);
+                part.paragraph(context, Some(&format!("Heading{level}")), &runs);
+            }
+            Block::ThematicBreak => part.paragraph(context, Some("ThematicBreak"), ""),
+            Block::BlockQuote(blocks) => self.block_quote(part, context, blocks),
+            Block::List(list) => self.list(part, context, list),
+            Block::CodeBlock(code_block) => {
+                let literal = code_block.literal.strip_suffix('\n');
+                let content = text(literal.unwrap_or(&code_block.literal));
+                part.paragraph(context, Some("Code"), &run(RunStyle::default(), &content));
+            }
+            Block::Table(table) => self.table(part, context, table),
+            Block::ContainerDirective(directive) => self.blocks(part, context, &directive.blocks),
+            Block::HtmlBlock(_)
+            | Block::Definition(_)
+            | Block::FootnoteDefinition(_)
+            | Block::LeafDirective(_)
+            | Block::Empty => (),
+        }
+    }
+
+    /// GitHub alerts start with a paragraph naming their kind.
+    fn block_quote(&mut self, part: &mut Part, context: Context, blocks: &[Block]) {
+        let context = Context {
+            quote_depth: context.quote_depth + 1,
+            ..context
+        };
+        match alert(blocks) {
+            Some((kind, content)) => {
+                let title = match kind {
+                    AlertKind::Note => "Note",
+                    AlertKind::Tip => "Tip",
+                    AlertKind::Important => "Important",
+                    AlertKind::Warning => "Warning",
+                    AlertKind::Caution => "Caution",
+                };
+                let bold = RunStyle {
+                    bold: true,
+                    ..RunStyle::default()
+                };
+                part.paragraph(context, None, &run(bold, &text(title)));
+                self.blocks(part, context, &content);
+            }
+            None => self.blocks(part, context, blocks),
+        }
+    }
+
+    /// Every list gets its own numbering instance, the first paragraph of an item
+    /// is numbered and following blocks are indented to the item content.
+    fn list(&mut self, part: &mut Part, context: Context, list: &List) {
+        let level = context.list_depth;
+        self.numbering.push(Numbering {
+            start: match &list.kind {
+                ListKind::Ordered(options) => Some(options.start),
+                ListKind::Bullet(_) => None,
+            },
+            level,
+        });
+        let id = self.numbering.len();
+        let context = Context {
+            list_depth: level + 1,
+            ..context
+        };
+        for item in &list.items {
+            part.numbering = Some((id, level));
+            part.prefix = match item.task {
+                Some(TaskState::Complete) => run(RunStyle::default(), &text("\u{2611} ")),
+                Some(TaskState::Incomplete) => run(RunStyle::default(), &text("\u{2610} ")),
+                None => String::new(),
+            };
+            self.blocks(part, context, &item.blocks);
+            part.flush(context);
+        }
+    }
+
+    /// The first row is the header row, repeated on every page.
+    fn table(&mut self, part: &mut Part, context: Context, table: &Table) {
+        let columns = table.rows.iter().map(Vec::len).max().unwrap_or_default();
+        if columns == 0 {
+            return;
+        }
+        part.flush(context);
+
+        let indent = 720 * (context.quote_depth + context.list_depth);
+        part.xml.push_str(
+            "<w:tbl><w:tblPr><w:tblStyle w:val=\"Table\"/><w:tblW w:w=\"0\" w:type=\"auto\"/>",
+        );
+        if indent > 0 {
+            part.xml
+                .push_str(&format!("<w:tblInd w:w=\"{indent}\" w:type=\"dxa\"/>"));
+        }
+        part.xml.push_str("</w:tblPr><w:tblGrid>");
+        part.xml.push_str(&"<w:gridCol/>".repeat(columns));
+        part.xml.push_str("</w:tblGrid>");
+
+        for (i, row) in table.rows.iter().enumerate() {
+            part.xml.push_str("<w:tr>");
+            if i == 0 {
+                part.xml.push_str("<w:trPr><w:tblHeader/></w:trPr>");
+            }
+            let style = RunStyle {
+                bold: i == 0,
+                ..RunStyle::default()
+            };
+            for column in 0..columns {
+                let alignment = match table.alignments.get(column) {
+                    Some(Alignment::Left) => Some("left"),
+                    Some(Alignment::Center) => Some("center"),
+                    Some(Alignment::Right) => Some("right"),
+                    Some(Alignment::None) | None => None,
+                };
+                let cell_context = Context {
+                    style: context.style,
+                    alignment,
+                    ..Context::default()
+                };
+                let runs = match row.get(column) {
+                    Some(cell) => self.inlines(part, style, cell),
+                    None => String::new(),
+                };
+                part.xml
+                    .push_str("<w:tc><w:tcPr><w:tcW w:w=\"0\" w:type=\"auto\"/></w:tcPr>");
+                part.paragraph(cell_context, None, &runs);
+                part.xml.push_str("</w:tc>");
+            }
+            part.xml.push_str("</w:tr>");
+        }
+        part.xml.push_str("</w:tbl>");
+    }
+}
@@ -0,0 +1,20 @@
+pub struct Config {
+    pub(crate) code_font: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            code_font: "Consolas".to_owned(),
+        }
+    }
+}
+
+impl Config {
+    /// Font of code blocks and inline code.
+    pub fn with_code_font(self, code_font: impl Into<String>) -> Self {
+        Self {
+            code_font: code_font.into(),
+        }
+    }
+}
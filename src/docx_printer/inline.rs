@@ -0,0 +1,139 @@
+use crate::ast::*;
+use crate::docx_printer::{run, text, Part, RunStyle, Writer};
+use crate::renderer::plain::plain_text;
+
+impl Writer<'_> {
+    pub(crate) fn inlines(
+        &mut self,
+        part: &mut Part,
+        style: RunStyle,
+        inlines: &[Inline],
+    ) -> String {
+        inlines
+            .iter()
+            .map(|inline| self.inline(part, style, inline))
+            .collect()
+    }
+
+    fn inline(&mut self, part: &mut Part, style: RunStyle, inline: &Inline) -> String {
+        match inline {
+            Inline::Text(content) => run(style, &text(&content.replace('\n', " "))),
+            Inline::LineBreak => "<w:r><w:br/></w:r>".to_owned(),
+            Inline::Code(code) => run(
+                RunStyle {
+                    code: true,
+                    ..style
+                },
+                &text(code),
+            ),
+            Inline::Link(link) => self.hyperlink(part, style, &link.destination, &link.children),
+            Inline::LinkReference(link) => {
+                let index = self.index;
+                match index.link_definition(&link.label) {
+                    Some(definition) => {
+                        self.hyperlink(part, style, &definition.destination, &link.text)
+                    }
+                    None => self.inlines(part, style, &link.text),
+                }
+            }
+            Inline::Image(image) => {
+                let alt = match image.alt.is_empty() {
+                    true => &image.destination,
+                    false => &image.alt,
+                };
+                self.hyperlink(
+                    part,
+                    style,
+                    &image.destination,
+                    &[Inline::Text(alt.clone())],
+                )
+            }
+            Inline::Emphasis(children) => self.inlines(
+                part,
+                RunStyle {
+                    italic: true,
+                    ..style
+                },
+                children,
+            ),
+            Inline::Strong(children) => self.inlines(
+                part,
+                RunStyle {
+                    bold: true,
+                    ..style
+                },
+                children,
+            ),
+            Inline::Strikethrough(children) => self.inlines(
+                part,
+                RunStyle {
+                    strike: true,
+                    ..style
+                },
+                children,
+            ),
+            Inline::Autolink(link) => {
+                let destination = if link.starts_with("www.") {
+                    format!("http://{link}")
+                } else if !link.contains(':') && link.contains('@') {
+                    format!("mailto:{link}")
+                } else {
+                    link.clone()
+                };
+                self.hyperlink(part, style, &destination, &[Inline::Text(link.clone())])
+            }
+            Inline::FootnoteReference(label) => self.footnote_reference(style, label),
+            Inline::Html(_) | Inline::Empty => String::new(),
+            inline => run(
+                style,
+                &text(&plain_text(std::slice::from_ref(inline), Some(self.index))),
+            ),
+        }
+    }
+
+    /// External hyperlink, hyperlinks cannot be nested.
+    fn hyperlink(
+        &mut self,
+        part: &mut Part,
+        style: RunStyle,
+        destination: &str,
+        children: &[Inline],
+    ) -> String {
+        if style.hyperlink {
+            return self.inlines(part, style, children);
+        }
+        let id = part.hyperlink(destination);
+        let style = RunStyle {
+            hyperlink: true,
+            ..style
+        };
+        format!(
+            "<w:hyperlink r:id=\"{id}\" w:history=\"1\">{}</w:hyperlink>",
+            self.inlines(part, style, children)
+        )
+    }
+
+    /// Every reference in the body gets its own Word footnote. References inside
+    /// footnotes show the number of the footnote's first Word footnote.
+    fn footnote_reference(&mut self, style: RunStyle, label: &str) -> String {
+        let first = self.footnotes.iter().position(|footnote| footnote == label);
+        match (self.index.footnote_index(label), first) {
+            (Some(_), _) if !self.in_footnote => {
+                self.footnotes.push(label.to_owned());
+                format!(
+                    "<w:r><w:rPr><w:rStyle w:val=\"FootnoteReference\"/></w:rPr>\
+                     <w:footnoteReference w:id=\"{}\"/></w:r>",
+                    self.footnotes.len()
+                )
+            }
+            (Some(_), Some(first)) => {
+                let style = RunStyle {
+                    superscript: true,
+                    ..style
+                };
+                run(style, &text(&(first + 1).to_string()))
+            }
+            _ => run(style, &text(&format!("[^{label}]"))),
+        }
+    }
+}
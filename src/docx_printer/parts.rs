@@ -0,0 +1,180 @@
+//! Package parts other than the document body and footnotes content.
+
+use crate::docx_printer::config::Config;
+use crate::docx_printer::{escape, Numbering};
+
+const HEADER: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+const NAMESPACES: &str =
+    "xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+     xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"";
+
+const RELATIONSHIP_TYPES: &str =
+    "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Relationship types and targets of the main document part, in order of their ids.
+pub(crate) const DOCUMENT_RELATIONSHIPS: [(&str, &str); 3] = [
+    ("styles", "styles.xml"),
+    ("numbering", "numbering.xml"),
+    ("footnotes", "footnotes.xml"),
+];
+
+pub(crate) fn content_types() -> String {
+    let overrides = [
+        ("document", "document.main"),
+        ("styles", "styles"),
+        ("numbering", "numbering"),
+        ("footnotes", "footnotes"),
+    ]
+    .iter()
+    .map(|(part, content_type)| {
+        format!(
+            "<Override PartName=\"/word/{part}.xml\" ContentType=\"application/vnd.openxmlformats-officedocument.wordprocessingml.{content_type}+xml\"/>"
+        )
+    })
+    .collect::<String>();
+    format!(
+        "{HEADER}<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+         <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+         <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+         {overrides}</Types>"
+    )
+}
+
+pub(crate) fn package_relationships() -> String {
+    relationships(&[("officeDocument", "word/document.xml")], &[])
+}
+
+/// Relationships of a part: `parts` first, then external `hyperlinks`.
+pub(crate) fn relationships(parts: &[(&str, &str)], hyperlinks: &[String]) -> String {
+    let parts = parts.iter().map(|(kind, target)| (*kind, *target, ""));
+    let hyperlinks = hyperlinks
+        .iter()
+        .map(|target| ("hyperlink", target.as_str(), " TargetMode=\"External\""));
+    let relationships = parts
+        .chain(hyperlinks)
+        .enumerate()
+        .map(|(i, (kind, target, mode))| {
+            format!(
+                "<Relationship Id=\"rId{}\" Type=\"{RELATIONSHIP_TYPES}/{kind}\" Target=\"{}\"{mode}/>",
+                i + 1,
+                escape(target)
+            )
+        })
+        .collect::<String>();
+    format!(
+        "{HEADER}<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+         {relationships}</Relationships>"
+    )
+}
+
+pub(crate) fn document(body: &str) -> String {
+    format!("{HEADER}<w:document {NAMESPACES}><w:body>{body}<w:sectPr/></w:body></w:document>")
+}
+
+/// Footnotes part, starting with the separators Word expects.
+pub(crate) fn footnotes(footnotes: &str) -> String {
+    format!(
+        "{HEADER}<w:footnotes {NAMESPACES}>\
+         <w:footnote w:type=\"separator\" w:id=\"-1\"><w:p><w:r><w:separator/></w:r></w:p></w:footnote>\
+         <w:footnote w:type=\"continuationSeparator\" w:id=\"0\"><w:p><w:r><w:continuationSeparator/></w:r></w:p></w:footnote>\
+         {footnotes}</w:footnotes>"
+    )
+}
+
+/// Bullet and decimal list definitions, and a numbering instance for every list so
+/// ordered lists restart at their start number.
+pub(crate) fn numbering(numbering: &[Numbering]) -> String {
+    let bullets = ["\u{2022}", "\u{25e6}", "\u{25aa}"];
+    let levels = |format: &str, text: &dyn Fn(usize) -> String| {
+        (0..9)
+            .map(|level| {
+                format!(
+                    "<w:lvl w:ilvl=\"{level}\"><w:start w:val=\"1\"/><w:numFmt w:val=\"{format}\"/>\
+                     <w:lvlText w:val=\"{}\"/><w:lvlJc w:val=\"left\"/>\
+                     <w:pPr><w:ind w:left=\"{}\" w:hanging=\"360\"/></w:pPr></w:lvl>",
+                    text(level),
+                    720 * (level + 1)
+                )
+            })
+            .collect::<String>()
+    };
+    let instances = numbering
+        .iter()
+        .enumerate()
+        .map(|(i, numbering)| match numbering.start {
+            Some(start) => format!(
+                "<w:num w:numId=\"{}\"><w:abstractNumId w:val=\"1\"/>\
+                 <w:lvlOverride w:ilvl=\"{}\"><w:startOverride w:val=\"{start}\"/></w:lvlOverride></w:num>",
+                i + 1,
+                numbering.level
+            ),
+            None => format!(
+                "<w:num w:numId=\"{}\"><w:abstractNumId w:val=\"0\"/></w:num>",
+                i + 1
+            ),
+        })
+        .collect::<String>();
+    format!(
+        "{HEADER}<w:numbering {NAMESPACES}>\
+         <w:abstractNum w:abstractNumId=\"0\"><w:multiLevelType w:val=\"hybridMultilevel\"/>{}</w:abstractNum>\
+         <w:abstractNum w:abstractNumId=\"1\"><w:multiLevelType w:val=\"hybridMultilevel\"/>{}</w:abstractNum>\
+         {instances}</w:numbering>",
+        levels("bullet", &|level| bullets[level % bullets.len()].to_owned()),
+        levels("decimal", &|level| format!("%{}.", level + 1)),
+    )
+}
+
+pub(crate) fn styles(config: &Config) -> String {
+    let font = escape(&config.code_font);
+    let code_font = format!("<w:rFonts w:ascii=\"{font}\" w:hAnsi=\"{font}\" w:cs=\"{font}\"/>");
+    let headings = [36, 32, 28, 26, 24, 22]
+        .iter()
+        .enumerate()
+        .map(|(i, size)| {
+            format!(
+                "<w:style w:type=\"paragraph\" w:styleId=\"Heading{level}\">\
+                 <w:name w:val=\"heading {level}\"/><w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/>\
+                 <w:uiPriority w:val=\"9\"/><w:qFormat/>\
+                 <w:pPr><w:keepNext/><w:spacing w:before=\"240\" w:after=\"120\"/><w:outlineLvl w:val=\"{i}\"/></w:pPr>\
+                 <w:rPr><w:b/><w:sz w:val=\"{size}\"/></w:rPr></w:style>",
+                level = i + 1
+            )
+        })
+        .collect::<String>();
+    let border = |side: &str, size: usize| {
+        format!("<w:{side} w:val=\"single\" w:sz=\"{size}\" w:space=\"0\" w:color=\"auto\"/>")
+    };
+    let table_borders = ["top", "left", "bottom", "right", "insideH", "insideV"]
+        .iter()
+        .map(|side| border(side, 4))
+        .collect::<String>();
+    format!(
+        "{HEADER}<w:styles {NAMESPACES}>\
+         <w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val=\"22\"/></w:rPr></w:rPrDefault>\
+         <w:pPrDefault><w:pPr><w:spacing w:after=\"120\"/></w:pPr></w:pPrDefault></w:docDefaults>\
+         <w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/><w:qFormat/></w:style>\
+         {headings}\
+         <w:style w:type=\"paragraph\" w:styleId=\"Quote\"><w:name w:val=\"Quote\"/><w:basedOn w:val=\"Normal\"/><w:qFormat/>\
+         <w:pPr><w:pBdr><w:left w:val=\"single\" w:sz=\"12\" w:space=\"8\" w:color=\"A0A0A0\"/></w:pBdr><w:ind w:left=\"720\"/></w:pPr>\
+         <w:rPr><w:color w:val=\"595959\"/></w:rPr></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"Code\"><w:name w:val=\"Code\"/><w:basedOn w:val=\"Normal\"/>\
+         <w:pPr><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"F2F2F2\"/></w:pPr><w:rPr>{code_font}<w:sz w:val=\"20\"/></w:rPr></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"ThematicBreak\"><w:name w:val=\"Thematic Break\"/><w:basedOn w:val=\"Normal\"/>\
+         <w:pPr><w:pBdr>{}</w:pBdr></w:pPr></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"FootnoteText\"><w:name w:val=\"footnote text\"/><w:basedOn w:val=\"Normal\"/>\
+         <w:pPr><w:spacing w:after=\"0\"/></w:pPr><w:rPr><w:sz w:val=\"20\"/></w:rPr></w:style>\
+         <w:style w:type=\"character\" w:default=\"1\" w:styleId=\"DefaultParagraphFont\"><w:name w:val=\"Default Paragraph Font\"/></w:style>\
+         <w:style w:type=\"character\" w:styleId=\"CodeChar\"><w:name w:val=\"Code Char\"/><w:basedOn w:val=\"DefaultParagraphFont\"/>\
+         <w:rPr>{code_font}</w:rPr></w:style>\
+         <w:style w:type=\"character\" w:styleId=\"Hyperlink\"><w:name w:val=\"Hyperlink\"/><w:basedOn w:val=\"DefaultParagraphFont\"/>\
+         <w:rPr><w:color w:val=\"0563C1\"/><w:u w:val=\"single\"/></w:rPr></w:style>\
+         <w:style w:type=\"character\" w:styleId=\"FootnoteReference\"><w:name w:val=\"footnote reference\"/><w:basedOn w:val=\"DefaultParagraphFont\"/>\
+         <w:rPr><w:vertAlign w:val=\"superscript\"/></w:rPr></w:style>\
+         <w:style w:type=\"table\" w:styleId=\"Table\"><w:name w:val=\"Table\"/>\
+         <w:tblPr><w:tblBorders>{table_borders}</w:tblBorders>\
+         <w:tblCellMar><w:left w:w=\"108\" w:type=\"dxa\"/><w:right w:w=\"108\" w:type=\"dxa\"/></w:tblCellMar></w:tblPr></w:style>\
+         </w:styles>",
+        border("bottom", 6),
+    )
+}
@@ -0,0 +1,239 @@
+#![cfg(test)]
+use crate::docx_printer::config::Config;
+use crate::docx_printer::render_docx;
+use crate::docx_printer::zip::crc32;
+use rstest::rstest;
+
+/// Names and contents of the stored entries of an archive, read from their local
+/// headers.
+fn entries(archive: &[u8]) -> Vec<(String, String)> {
+    let u16_at =
+        |offset: usize| u16::from_le_bytes([archive[offset], archive[offset + 1]]) as usize;
+    let u32_at = |offset: usize| {
+        u32::from_le_bytes(archive[offset..offset + 4].try_into().unwrap()) as usize
+    };
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    while u32_at(offset) == 0x04034b50 {
+        assert_eq!(0, u16_at(offset + 8), "entries are stored");
+        let size = u32_at(offset + 18);
+        let name_length = u16_at(offset + 26);
+        let start = offset + 30 + name_length + u16_at(offset + 28);
+        let content = &archive[start..start + size];
+        assert_eq!(crc32(content) as usize, u32_at(offset + 14));
+        entries.push((
+            String::from_utf8(archive[offset + 30..offset + 30 + name_length].to_vec()).unwrap(),
+            String::from_utf8(content.to_vec()).unwrap(),
+        ));
+        offset = start + size;
+    }
+
+    // End of central directory record
+    let end = archive.len() - 22;
+    assert_eq!(0x06054b50, u32_at(end));
+    assert_eq!(entries.len(), u16_at(end + 10));
+    assert_eq!(offset, u32_at(end + 16));
+    entries
+}
+
+fn part(input: &str, name: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    entries(&render_docx(&doc, Config::default()))
+        .into_iter()
+        .find(|(entry, _)| entry == name)
+        .map(|(_, content)| content)
+        .unwrap()
+}
+
+/// Content of the document body.
+fn body(input: &str) -> String {
+    let document = part(input, "word/document.xml");
+    let start = document.find("<w:body>").unwrap() + "<w:body>".len();
+    let end = document.find("<w:sectPr/>").unwrap();
+    document[start..end].to_owned()
+}
+
+#[test]
+fn crc() {
+    assert_eq!(0xcbf43926, crc32(b"123456789"));
+    assert_eq!(0, crc32(b""));
+}
+
+#[test]
+fn package() {
+    let doc =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), "Hi").unwrap();
+    let names = entries(&render_docx(&doc, Config::default()))
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "word/document.xml",
+            "word/_rels/document.xml.rels",
+            "word/styles.xml",
+            "word/numbering.xml",
+            "word/footnotes.xml",
+            "word/_rels/footnotes.xml.rels",
+        ],
+        names
+    );
+}
+
+#[rstest]
+#[case(
+    "Hello",
+    "<w:p><w:r><w:t xml:space=\"preserve\">Hello</w:t></w:r></w:p>"
+)]
+#[case(
+    "## Setup",
+    "<w:p><w:pPr><w:pStyle w:val=\"Heading2\"/></w:pPr><w:r><w:t xml:space=\"preserve\">Setup</w:t></w:r></w:p>"
+)]
+#[case(
+    "a _b **c** d_",
+    "<w:p><w:r><w:t xml:space=\"preserve\">a </w:t></w:r>\
+     <w:r><w:rPr><w:i/></w:rPr><w:t xml:space=\"preserve\">b </w:t></w:r>\
+     <w:r><w:rPr><w:b/><w:i/></w:rPr><w:t xml:space=\"preserve\">c</w:t></w:r>\
+     <w:r><w:rPr><w:i/></w:rPr><w:t xml:space=\"preserve\"> d</w:t></w:r></w:p>"
+)]
+#[case(
+    "~~x~~ `a<b`",
+    "<w:p><w:r><w:rPr><w:strike/></w:rPr><w:t xml:space=\"preserve\">x</w:t></w:r>\
+     <w:r><w:t xml:space=\"preserve\"> </w:t></w:r>\
+     <w:r><w:rPr><w:rStyle w:val=\"CodeChar\"/></w:rPr><w:t xml:space=\"preserve\">a&lt;b</w:t></w:r></w:p>"
+)]
+#[case(
+    "one\ntwo  \nthree",
+    "<w:p><w:r><w:t xml:space=\"preserve\">one two</w:t></w:r><w:r><w:br/></w:r>\
+     <w:r><w:t xml:space=\"preserve\">three</w:t></w:r></w:p>"
+)]
+#[case(
+    "```rust\nfn main() {\n\tx\n}\n```",
+    "<w:p><w:pPr><w:pStyle w:val=\"Code\"/></w:pPr><w:r>\
+     <w:t xml:space=\"preserve\">fn main() {</w:t><w:br/><w:tab/><w:t xml:space=\"preserve\">x</w:t>\
+     <w:br/><w:t xml:space=\"preserve\">}</w:t></w:r></w:p>"
+)]
+#[case("***", "<w:p><w:pPr><w:pStyle w:val=\"ThematicBreak\"/></w:pPr></w:p>")]
+#[case(
+    "> a\n>\n> > b",
+    "<w:p><w:pPr><w:pStyle w:val=\"Quote\"/><w:ind w:left=\"720\"/></w:pPr><w:r><w:t xml:space=\"preserve\">a</w:t></w:r></w:p>\
+     <w:p><w:pPr><w:pStyle w:val=\"Quote\"/><w:ind w:left=\"1440\"/></w:pPr><w:r><w:t xml:space=\"preserve\">b</w:t></w:r></w:p>"
+)]
+#[case(
+    "> [!WARNING]\n> Hot.",
+    "<w:p><w:pPr><w:pStyle w:val=\"Quote\"/><w:ind w:left=\"720\"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Warning</w:t></w:r></w:p>\
+     <w:p><w:pPr><w:pStyle w:val=\"Quote\"/><w:ind w:left=\"720\"/></w:pPr><w:r><w:t xml:space=\"preserve\">Hot.</w:t></w:r></w:p>"
+)]
+#[case("<div>\n</div>\n\n[a]: /url", "")]
+fn document(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, body(input));
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        "<w:p><w:pPr><w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"1\"/></w:numPr></w:pPr>\
+         <w:r><w:t xml:space=\"preserve\">a</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:ind w:left=\"720\"/></w:pPr><w:r><w:t xml:space=\"preserve\">more</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:numPr><w:ilvl w:val=\"1\"/><w:numId w:val=\"2\"/></w:numPr></w:pPr>\
+         <w:r><w:t xml:space=\"preserve\">b</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"1\"/></w:numPr></w:pPr>\
+         <w:r><w:t xml:space=\"preserve\">☑ </w:t></w:r><w:r><w:t xml:space=\"preserve\">done</w:t></w:r></w:p>",
+        body("- a\n\n  more\n\n    3. b\n- [x] done")
+    );
+    let numbering = part("- a\n\n    3. b", "word/numbering.xml");
+    assert!(numbering.contains("<w:num w:numId=\"1\"><w:abstractNumId w:val=\"0\"/></w:num>"));
+    assert!(numbering.contains(
+        "<w:num w:numId=\"2\"><w:abstractNumId w:val=\"1\"/>\
+         <w:lvlOverride w:ilvl=\"1\"><w:startOverride w:val=\"3\"/></w:lvlOverride></w:num>"
+    ));
+    assert!(numbering.contains("<w:lvlText w:val=\"%2.\"/>"));
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        "<w:tbl><w:tblPr><w:tblStyle w:val=\"Table\"/><w:tblW w:w=\"0\" w:type=\"auto\"/></w:tblPr>\
+         <w:tblGrid><w:gridCol/><w:gridCol/></w:tblGrid>\
+         <w:tr><w:trPr><w:tblHeader/></w:trPr>\
+         <w:tc><w:tcPr><w:tcW w:w=\"0\" w:type=\"auto\"/></w:tcPr><w:p><w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Name</w:t></w:r></w:p></w:tc>\
+         <w:tc><w:tcPr><w:tcW w:w=\"0\" w:type=\"auto\"/></w:tcPr><w:p><w:pPr><w:jc w:val=\"right\"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">Size</w:t></w:r></w:p></w:tc></w:tr>\
+         <w:tr><w:tc><w:tcPr><w:tcW w:w=\"0\" w:type=\"auto\"/></w:tcPr><w:p><w:r><w:t xml:space=\"preserve\">a</w:t></w:r></w:p></w:tc>\
+         <w:tc><w:tcPr><w:tcW w:w=\"0\" w:type=\"auto\"/></w:tcPr><w:p><w:pPr><w:jc w:val=\"right\"/></w:pPr><w:r><w:t xml:space=\"preserve\">1</w:t></w:r></w:p></w:tc></w:tr></w:tbl>",
+        body("| Name | Size |\n|------|-----:|\n| a | 1 |")
+    );
+}
+
+#[test]
+fn hyperlinks() {
+    let input = "[a](<https://example.com/a b>) <https://example.com/a%20b> [c][c]\n\n[c]: https://example.org";
+    assert_eq!(
+        "<w:p><w:hyperlink r:id=\"rId4\" w:history=\"1\"><w:r><w:rPr><w:rStyle w:val=\"Hyperlink\"/></w:rPr><w:t xml:space=\"preserve\">a</w:t></w:r></w:hyperlink>\
+         <w:r><w:t xml:space=\"preserve\"> </w:t></w:r>\
+         <w:hyperlink r:id=\"rId4\" w:history=\"1\"><w:r><w:rPr><w:rStyle w:val=\"Hyperlink\"/></w:rPr><w:t xml:space=\"preserve\">https://example.com/a%20b</w:t></w:r></w:hyperlink>\
+         <w:r><w:t xml:space=\"preserve\"> </w:t></w:r>\
+         <w:hyperlink r:id=\"rId5\" w:history=\"1\"><w:r><w:rPr><w:rStyle w:val=\"Hyperlink\"/></w:rPr><w:t xml:space=\"preserve\">c</w:t></w:r></w:hyperlink></w:p>",
+        body(input)
+    );
+    let relationships = part(input, "word/_rels/document.xml.rels");
+    assert!(relationships.contains(
+        "<Relationship Id=\"rId3\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes\" Target=\"footnotes.xml\"/>"
+    ));
+    assert!(relationships.contains(
+        "<Relationship Id=\"rId5\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" Target=\"https://example.org\" TargetMode=\"External\"/>"
+    ));
+}
+
+#[test]
+fn footnotes() {
+    let input = "A[^a] B[^b] C[^a]\n\n[^a]: First [x](https://x.org).\n\n[^b]: See[^a].";
+    assert_eq!(
+        "<w:p><w:r><w:t xml:space=\"preserve\">A</w:t></w:r>\
+         <w:r><w:rPr><w:rStyle w:val=\"FootnoteReference\"/></w:rPr><w:footnoteReference w:id=\"1\"/></w:r>\
+         <w:r><w:t xml:space=\"preserve\"> B</w:t></w:r>\
+         <w:r><w:rPr><w:rStyle w:val=\"FootnoteReference\"/></w:rPr><w:footnoteReference w:id=\"2\"/></w:r>\
+         <w:r><w:t xml:space=\"preserve\"> C</w:t></w:r>\
+         <w:r><w:rPr><w:rStyle w:val=\"FootnoteReference\"/></w:rPr><w:footnoteReference w:id=\"3\"/></w:r></w:p>",
+        body(input)
+    );
+    let footnotes = part(input, "word/footnotes.xml");
+    let reference =
+        "<w:r><w:rPr><w:rStyle w:val=\"FootnoteReference\"/></w:rPr><w:footnoteRef/></w:r>\
+         <w:r><w:t xml:space=\"preserve\"> </w:t></w:r>";
+    let first = format!(
+        "<w:p><w:pPr><w:pStyle w:val=\"FootnoteText\"/></w:pPr>{reference}\
+         <w:r><w:t xml:space=\"preserve\">First </w:t></w:r>\
+         <w:hyperlink r:id=\"rId1\" w:history=\"1\"><w:r><w:rPr><w:rStyle w:val=\"Hyperlink\"/></w:rPr><w:t xml:space=\"preserve\">x</w:t></w:r></w:hyperlink>\
+         <w:r><w:t xml:space=\"preserve\">.</w:t></w:r></w:p>"
+    );
+    assert!(footnotes.contains(&format!("<w:footnote w:id=\"1\">{first}</w:footnote>")));
+    assert!(footnotes.contains(&format!(
+        "<w:footnote w:id=\"2\"><w:p><w:pPr><w:pStyle w:val=\"FootnoteText\"/></w:pPr>{reference}\
+         <w:r><w:t xml:space=\"preserve\">See</w:t></w:r>\
+         <w:r><w:rPr><w:vertAlign w:val=\"superscript\"/></w:rPr><w:t xml:space=\"preserve\">1</w:t></w:r>\
+         <w:r><w:t xml:space=\"preserve\">.</w:t></w:r></w:p></w:footnote>"
+    )));
+    assert!(footnotes.contains(&format!("<w:footnote w:id=\"3\">{first}</w:footnote>")));
+    assert!(part(input, "word/_rels/footnotes.xml.rels").contains(
+        "<Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" Target=\"https://x.org\" TargetMode=\"External\"/>"
+    ));
+}
+
+#[test]
+fn code_font() {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), "`x`")
+        .unwrap();
+    let styles = entries(&render_docx(
+        &doc,
+        Config::default().with_code_font("Fira \"Code\""),
+    ))
+    .into_iter()
+    .find(|(entry, _)| entry == "word/styles.xml")
+    .map(|(_, content)| content)
+    .unwrap();
+    assert!(styles.contains("<w:rFonts w:ascii=\"Fira &quot;Code&quot;\""));
+    assert!(styles.contains("<w:style w:type=\"paragraph\" w:styleId=\"Heading6\">"));
+}
@@ -0,0 +1,111 @@
+//! ZIP archive writer for the package. Entries are stored without compression,
+//! which every reader of Office Open XML packages supports.
+
+/// Archive built in memory, entries are added in order.
+pub(crate) struct ZipWriter {
+    data: Vec<u8>,
+    central_directory: Vec<u8>,
+    entries: u16,
+}
+
+// Modification time of all entries, 1980-01-01 00:00 in MS-DOS format.
+const DOS_TIME: u16 = 0;
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+impl ZipWriter {
+    pub(crate) fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            central_directory: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    /// Add a stored entry.
+    pub(crate) fn add(&mut self, name: &str, content: &[u8]) {
+        let offset = self.data.len() as u32;
+        let crc = crc32(content);
+        let size = content.len() as u32;
+
+        let data = &mut self.data;
+        data.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        data.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+        data.extend_from_slice(&0u16.to_le_bytes()); // flags
+        data.extend_from_slice(&0u16.to_le_bytes()); // stored
+        data.extend_from_slice(&DOS_TIME.to_le_bytes());
+        data.extend_from_slice(&DOS_DATE.to_le_bytes());
+        data.extend_from_slice(&crc.to_le_bytes());
+        data.extend_from_slice(&size.to_le_bytes()); // compressed size
+        data.extend_from_slice(&size.to_le_bytes()); // uncompressed size
+        data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(content);
+
+        let directory = &mut self.central_directory;
+        directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        directory.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        directory.extend_from_slice(&20u16.to_le_bytes()); // version needed to extract
+        directory.extend_from_slice(&0u16.to_le_bytes()); // flags
+        directory.extend_from_slice(&0u16.to_le_bytes()); // stored
+        directory.extend_from_slice(&DOS_TIME.to_le_bytes());
+        directory.extend_from_slice(&DOS_DATE.to_le_bytes());
+        directory.extend_from_slice(&crc.to_le_bytes());
+        directory.extend_from_slice(&size.to_le_bytes());
+        directory.extend_from_slice(&size.to_le_bytes());
+        directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        directory.extend_from_slice(&0u16.to_le_bytes()); // extra field length
+        directory.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        directory.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        directory.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        directory.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        directory.extend_from_slice(&offset.to_le_bytes());
+        directory.extend_from_slice(name.as_bytes());
+
+        self.entries += 1;
+    }
+
+    /// The archive with its central directory.
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        let offset = self.data.len() as u32;
+        let size = self.central_directory.len() as u32;
+        self.data.append(&mut self.central_directory);
+        self.data.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // disk with the central directory
+        self.data.extend_from_slice(&self.entries.to_le_bytes()); // entries on this disk
+        self.data.extend_from_slice(&self.entries.to_le_bytes());
+        self.data.extend_from_slice(&size.to_le_bytes());
+        self.data.extend_from_slice(&offset.to_le_bytes());
+        self.data.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.data
+    }
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = match crc & 1 {
+                1 => 0xedb88320 ^ (crc >> 1),
+                _ => crc >> 1,
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 checksum of the data, as used by ZIP archives.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0, |crc, &byte| {
+        CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
@@ -0,0 +1,543 @@
+use crate::ast::*;
+use crate::html_parser::tree::{Element, Node};
+
+/// Elements converted to blocks, which end the paragraph of the inline content
+/// before them.
+const BLOCK_ELEMENTS: [&str; 44] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "head",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "menu",
+    "nav",
+    "noscript",
+    "ol",
+    "p",
+    "pre",
+    "script",
+    "section",
+    "style",
+    "table",
+    "template",
+    "title",
+    "ul",
+    "meta",
+    "link",
+];
+
+/// Elements of the document structure, whose content is converted as if they were
+/// not there.
+const DOCUMENT_ELEMENTS: [&str; 2] = ["html", "body"];
+
+/// Elements which only group their content, which is converted as if they were not
+/// there unless they have attributes, such as a class, that Markdown cannot keep.
+const CONTAINER_ELEMENTS: [&str; 6] = ["main", "article", "section", "div", "header", "footer"];
+
+pub(crate) struct Converter<'a> {
+    /// Source of the element ranges.
+    pub(crate) source: &'a str,
+}
+
+impl Converter<'_> {
+    pub(crate) fn blocks(&self, nodes: &[Node]) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        let mut inline_start = 0;
+        for (i, node) in nodes.iter().enumerate() {
+            let block = match node {
+                Node::Element(element) => BLOCK_ELEMENTS.contains(&element.name.as_str()),
+                // Comments between blocks are blocks of their own
+                Node::Comment(_) => nodes[inline_start..i].iter().all(is_whitespace),
+                Node::Text(_) => false,
+            };
+            if !block {
+                continue;
+            }
+            self.paragraph(&mut blocks, &nodes[inline_start..i]);
+            inline_start = i + 1;
+            match node {
+                Node::Element(element) => blocks.extend(self.block(element)),
+                Node::Comment(range) => {
+                    blocks.push(Block::HtmlBlock(self.source[range.clone()].to_owned()))
+                }
+                Node::Text(_) => (),
+            }
+        }
+        self.paragraph(&mut blocks, &nodes[inline_start..]);
+        blocks
+    }
+
+    /// Append a paragraph of the inline nodes, unless they are only whitespace.
+    fn paragraph(&self, blocks: &mut Vec<Block>, nodes: &[Node]) {
+        let inlines = self.trimmed_inlines(nodes);
+        if !inlines.is_empty() {
+            blocks.push(Block::Paragraph(inlines));
+        }
+    }
+
+    fn block(&self, element: &Element) -> Vec<Block> {
+        let name = element.name.as_str();
+        if DOCUMENT_ELEMENTS.contains(&name)
+            || CONTAINER_ELEMENTS.contains(&name) && element.attributes.is_empty()
+        {
+            return self.blocks(&element.children);
+        }
+        let block = match name {
+            "head" => return Vec::new(),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => Block::Heading(Heading {
+                kind: HeadingKind::Atx(name[1..].parse().unwrap_or(1)),
+                content: self.trimmed_inlines(&element.children),
+            }),
+            "p" => {
+                let inlines = self.trimmed_inlines(&element.children);
+                match inlines.is_empty() {
+                    true => return Vec::new(),
+                    false => Block::Paragraph(inlines),
+                }
+            }
+            "ul" | "ol" => self.list(element),
+            "blockquote" => Block::BlockQuote(self.blocks(&element.children)),
+            "pre" => self.code_block(element),
+            "hr" => Block::ThematicBreak,
+            "table" => match self.table(element) {
+                Some(table) => Block::Table(table),
+                None => self.html_block(element),
+            },
+            _ => self.html_block(element),
+        };
+        vec![block]
+    }
+
+    fn html_block(&self, element: &Element) -> Block {
+        Block::HtmlBlock(self.raw(element).to_owned())
+    }
+
+    /// Source of the element.
+    fn raw(&self, element: &Element) -> &str {
+        self.source[element.source.clone()].trim_end()
+    }
+
+    /// Items are the `li` children, other content is added to the previous item. An
+    /// item starting with a checkbox is a task list item.
+    fn list(&self, element: &Element) -> Block {
+        let kind = match element.name.as_str() {
+            "ol" => ListKind::Ordered(ListOrderedKindOptions {
+                start: element
+                    .attribute("start")
+                    .and_then(|start| start.trim().parse().ok())
+                    .unwrap_or(1),
+            }),
+            _ => ListKind::Bullet(ListBulletKind::Dash),
+        };
+        let mut items: Vec<ListItem> = Vec::new();
+        for node in &element.children {
+            match node {
+                Node::Element(item) if item.name == "li" => {
+                    let (task, blocks) = self.list_item(item);
+                    items.push(ListItem { task, blocks });
+                }
+                node if is_whitespace(node) => (),
+                node => match items.last_mut() {
+                    Some(item) => item.blocks.extend(self.blocks(std::slice::from_ref(node))),
+                    None => items.push(ListItem {
+                        task: None,
+                        blocks: self.blocks(std::slice::from_ref(node)),
+                    }),
+                },
+            }
+        }
+        Block::List(List { kind, items })
+    }
+
+    fn list_item(&self, item: &Element) -> (Option<TaskState>, Vec<Block>) {
+        let Some(first) = item.children.iter().position(|node| !is_whitespace(node)) else {
+            return (None, Vec::new());
+        };
+        if let Some(task) = checkbox(&item.children[first]) {
+            return (Some(task), self.blocks(&item.children[first + 1..]));
+        }
+
+        // The checkbox may start the first paragraph
+        if let Node::Element(paragraph) = &item.children[first] {
+            let checkbox_position = paragraph
+                .children
+                .iter()
+                .position(|node| !is_whitespace(node));
+            let task = checkbox_position
+                .filter(|_| paragraph.name == "p")
+                .and_then(|position| Some((position, checkbox(&paragraph.children[position])?)));
+            if let Some((position, task)) = task {
+                let mut blocks = Vec::new();
+                self.paragraph(&mut blocks, &paragraph.children[position + 1..]);
+                blocks.extend(self.blocks(&item.children[first + 1..]));
+                return (Some(task), blocks);
+            }
+        }
+        (None, self.blocks(&item.children))
+    }
+
+    /// The language is taken from a `language-*` or `lang-*` class of the `pre` or
+    /// `code` element.
+    fn code_block(&self, element: &Element) -> Block {
+        let code = element.children.iter().find_map(|node| match node {
+            Node::Element(code) if code.name == "code" => Some(code),
+            _ => None,
+        });
+        let language = [Some(element), code]
+            .into_iter()
+            .flatten()
+            .flat_map(|element| {
+                element
+                    .attribute("class")
+                    .unwrap_or_default()
+                    .split_whitespace()
+            })
+            .find_map(|class| {
+                class
+                    .strip_prefix("language-")
+                    .or_else(|| class.strip_prefix("lang-"))
+            })
+            .filter(|language| !language.is_empty());
+
+        let mut literal = String::new();
+        text_content(&element.children, &mut literal);
+        // A newline directly after the start tag is ignored
+        let literal = literal.strip_prefix('\n').unwrap_or(&literal);
+        Block::CodeBlock(CodeBlock {
+            kind: CodeBlockKind::Fenced {
+                info: language.map(str::to_owned),
+            },
+            literal: literal.trim_end_matches('\n').to_owned(),
+        })
+    }
+
+    /// Tables with merged cells, captions or block content in cells cannot be
+    /// represented.
+    fn table(&self, element: &Element) -> Option<Table> {
+        let mut rows = Vec::new();
+        for node in &element.children {
+            match node {
+                Node::Element(row) if row.name == "tr" => rows.push(row),
+                Node::Element(group)
+                    if ["thead", "tbody", "tfoot"].contains(&group.name.as_str()) =>
+                {
+                    for node in &group.children {
+                        match node {
+                            Node::Element(row) if row.name == "tr" => rows.push(row),
+                            node if is_whitespace(node) => (),
+                            _ => return None,
+                        }
+                    }
+                }
+                Node::Element(column) if ["colgroup", "col"].contains(&column.name.as_str()) => (),
+                node if is_whitespace(node) => (),
+                _ => return None,
+            }
+        }
+
+        let mut cells = Vec::new();
+        for row in &rows {
+            let mut row_cells = Vec::new();
+            for node in &row.children {
+                match node {
+                    Node::Element(cell) if cell.name == "td" || cell.name == "th" => {
+                        let merged = ["colspan", "rowspan"].iter().any(|attribute| {
+                            cell.attribute(attribute)
+                                .is_some_and(|span| span.trim() != "1")
+                        });
+                        if merged {
+                            return None;
+                        }
+                        row_cells.push(cell);
+                    }
+                    node if is_whitespace(node) => (),
+                    _ => return None,
+                }
+            }
+            cells.push(row_cells);
+        }
+
+        let columns = cells.iter().map(Vec::len).max().unwrap_or_default();
+        if columns == 0 {
+            return None;
+        }
+        let alignments = (0..columns)
+            .map(|column| {
+                let cell = cells[0].get(column)?;
+                let style = cell.attribute("style").unwrap_or_default();
+                let alignment = cell.attribute("align").or_else(|| {
+                    style.split(';').find_map(|declaration| {
+                        let (property, value) = declaration.split_once(':')?;
+                        (property.trim().eq_ignore_ascii_case("text-align")).then_some(value)
+                    })
+                })?;
+                match alignment.trim().to_ascii_lowercase().as_str() {
+                    "left" => Some(Alignment::Left),
+                    "center" => Some(Alignment::Center),
+                    "right" => Some(Alignment::Right),
+                    _ => None,
+                }
+            })
+            .map(|alignment| alignment.unwrap_or(Alignment::None))
+            .collect();
+        let rows = cells
+            .iter()
+            .map(|row| {
+                let mut row = row
+                    .iter()
+                    .map(|cell| self.cell(cell))
+                    .collect::<Option<Vec<_>>>()?;
+                row.resize(columns, Vec::new());
+                Some(row)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Table { rows, alignments })
+    }
+
+    /// Inline content of a cell, which may be wrapped in a single paragraph.
+    fn cell(&self, cell: &Element) -> Option<TableCell> {
+        let content = cell
+            .children
+            .iter()
+            .filter(|node| !is_whitespace(node))
+            .collect::<Vec<_>>();
+        match content.as_slice() {
+            [Node::Element(paragraph)] if paragraph.name == "p" => {
+                Some(self.trimmed_inlines(&paragraph.children))
+            }
+            content => {
+                let block = content.iter().any(|node| {
+                    matches!(node, Node::Element(element) if BLOCK_ELEMENTS.contains(&element.name.as_str()))
+                });
+                match block {
+                    true => None,
+                    false => Some(self.trimmed_inlines(&cell.children)),
+                }
+            }
+        }
+    }
+
+    /// Inlines with whitespace at the start, at the end and around line breaks
+    /// removed.
+    fn trimmed_inlines(&self, nodes: &[Node]) -> Vec<Inline> {
+        let mut inlines = normalize(self.inlines(nodes));
+        for i in 0..inlines.len() {
+            let after_break = i == 0 || inlines[i - 1] == Inline::LineBreak;
+            let before_break = i + 1 == inlines.len() || inlines[i + 1] == Inline::LineBreak;
+            if let Inline::Text(text) = &mut inlines[i] {
+                if after_break {
+                    *text = escape_line_start(text.trim_start_matches(' '));
+                }
+                if before_break {
+                    *text = text.trim_end_matches(' ').to_owned();
+                }
+            }
+        }
+        inlines.retain(|inline| !matches!(inline, Inline::Text(text) if text.is_empty()));
+        inlines
+    }
+
+    fn inlines(&self, nodes: &[Node]) -> Vec<Inline> {
+        nodes.iter().map(|node| self.inline(node)).collect()
+    }
+
+    fn inline(&self, node: &Node) -> Inline {
+        let element = match node {
+            Node::Text(text) => return Inline::Text(escape(&collapse_whitespace(text))),
+            Node::Comment(range) => return Inline::Html(self.source[range.clone()].to_owned()),
+            Node::Element(element) => element,
+        };
+        match element.name.as_str() {
+            "a" => match element.attribute("href") {
+                Some(href) => Inline::Link(Link {
+                    destination: href.trim().to_owned(),
+                    title: element.attribute("title").map(str::to_owned),
+                    children: self.inlines(&element.children),
+                }),
+                None => Inline::Html(self.raw(element).to_owned()),
+            },
+            "img" => match element.attribute("src") {
+                Some(src) => Inline::Image(Image {
+                    destination: src.trim().to_owned(),
+                    title: element.attribute("title").map(str::to_owned),
+                    alt: element.attribute("alt").unwrap_or_default().to_owned(),
+                }),
+                None => Inline::Html(self.raw(element).to_owned()),
+            },
+            "em" | "i" => Inline::Emphasis(self.inlines(&element.children)),
+            "strong" | "b" => Inline::Strong(self.inlines(&element.children)),
+            "del" | "s" | "strike" => Inline::Strikethrough(self.inlines(&element.children)),
+            "code" => {
+                let mut code = String::new();
+                text_content(&element.children, &mut code);
+                Inline::Code(code.replace(['\n', '\r'], " "))
+            }
+            "br" => Inline::LineBreak,
+            _ => Inline::Html(self.raw(element).to_owned()),
+        }
+    }
+}
+
+fn is_whitespace(node: &Node) -> bool {
+    matches!(node, Node::Text(text) if text.chars().all(|c| c.is_ascii_whitespace()))
+}
+
+/// State of a task list item for a checkbox.
+fn checkbox(node: &Node) -> Option<TaskState> {
+    match node {
+        Node::Element(input)
+            if input.name == "input"
+                && input
+                    .attribute("type")
+                    .is_some_and(|kind| kind.eq_ignore_ascii_case("checkbox")) =>
+        {
+            match input.attribute("checked") {
+                Some(_) => Some(TaskState::Complete),
+                None => Some(TaskState::Incomplete),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Text of the nodes and their descendants, with `br` elements as line breaks.
+fn text_content(nodes: &[Node], result: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => result.push_str(text),
+            Node::Element(element) if element.name == "br" => result.push('\n'),
+            Node::Element(element) => text_content(&element.children, result),
+            Node::Comment(_) => (),
+        }
+    }
+}
+
+/// Text with a backslash before characters which would start inline markup, entities
+/// or escapes, as escapes are kept in [`Inline::Text`].
+fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '~' | '[' | ']' | '<' | '&') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Escaped text with a backslash before a heading, block quote or list marker at
+/// its start, which would start a block at the start of a line.
+fn escape_line_start(text: &str) -> String {
+    if text.starts_with(['#', '>', '-', '+']) {
+        return format!("\\{text}");
+    }
+    let digits = text.len() - text.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if (1..=9).contains(&digits) && text[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &text[..digits], &text[digits..]);
+    }
+    text.to_owned()
+}
+
+/// Text with every run of whitespace replaced by a single space.
+fn collapse_whitespace(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut whitespace = false;
+    for c in text.chars() {
+        if c.is_ascii_whitespace() {
+            whitespace = true;
+            continue;
+        }
+        if whitespace {
+            result.push(' ');
+            whitespace = false;
+        }
+        result.push(c);
+    }
+    if whitespace {
+        result.push(' ');
+    }
+    result
+}
+
+/// Merge adjacent text, collapse spaces between inlines and move spaces at the edges
+/// of emphasis and links outside, where Markdown requires them.
+fn normalize(inlines: Vec<Inline>) -> Vec<Inline> {
+    fn push(result: &mut Vec<Inline>, inline: Inline) {
+        match (result.last_mut(), inline) {
+            (Some(Inline::Text(previous)), Inline::Text(text)) => match previous.ends_with(' ') {
+                true => previous.push_str(text.strip_prefix(' ').unwrap_or(&text)),
+                false => previous.push_str(&text),
+            },
+            (_, Inline::Text(text)) if text.is_empty() => (),
+            (_, inline) => result.push(inline),
+        }
+    }
+
+    let mut result = Vec::with_capacity(inlines.len());
+    for mut inline in inlines {
+        let children = match &mut inline {
+            Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children)
+            | Inline::Link(Link { children, .. }) => children,
+            _ => {
+                push(&mut result, inline);
+                continue;
+            }
+        };
+        *children = normalize(std::mem::take(children));
+        let (before, after) = edge_spaces(children);
+        // Links are kept without text, as their destination is still shown
+        let empty = children.is_empty() && !matches!(inline, Inline::Link(_));
+        push(&mut result, Inline::Text(before));
+        if !empty {
+            push(&mut result, inline);
+        }
+        push(&mut result, Inline::Text(after));
+    }
+    result
+}
+
+/// Remove the spaces at the start and end of the inlines, returning them.
+fn edge_spaces(inlines: &mut Vec<Inline>) -> (String, String) {
+    let mut before = String::new();
+    let mut after = String::new();
+    if let Some(Inline::Text(text)) = inlines.first_mut() {
+        if let Some(rest) = text.strip_prefix(' ') {
+            before.push(' ');
+            *text = rest.to_owned();
+        }
+    }
+    if let Some(Inline::Text(text)) = inlines.last_mut() {
+        if let Some(rest) = text.strip_suffix(' ') {
+            after.push(' ');
+            *text = rest.to_owned();
+        }
+    }
+    inlines.retain(|inline| !matches!(inline, Inline::Text(text) if text.is_empty()));
+    (before, after)
+}
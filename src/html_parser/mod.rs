@@ -0,0 +1,44 @@
+//! Conversion of HTML to the AST, for importing HTML content as Markdown.
+//!
+//! Headings, paragraphs, lists (including task lists with checkboxes), block quotes,
+//! `pre` code blocks with a `language-*` class, tables, horizontal rules, links,
+//! images, emphasis, strong emphasis, strikethrough, inline code and line breaks
+//! become their AST nodes. Elements which only group content, such as `div` and
+//! `section`, are left out and their content is converted unless they have
+//! attributes, and `head` and doctypes are skipped.
+//! Everything else, such as `span`, `dl`, `<div class="note">` or tables with merged
+//! cells, is kept as [`Block::HtmlBlock`](crate::ast::Block::HtmlBlock) or
+//! [`Inline::Html`](crate::ast::Inline::Html) with its source. Whitespace is
+//! collapsed as a browser would, except in code blocks.
+//!
+//! Like browsers, the parser accepts any input: omitted end tags are implied and
+//! unmatched end tags are ignored.
+//!
+//! ```rust
+//! use markdown_ppp::html_parser::parse_html;
+//! use markdown_ppp::printer::{config::Config, render_markdown};
+//!
+//! let doc = parse_html(
+//!     "<h2>Install</h2>\n<p>Run <code>make</code>, then see the\n<a href=\"/docs\">docs</a>.<p>Or <span class=\"x\">ask</span>.",
+//! );
+//! assert_eq!(
+//!     "## Install\n\nRun `make`, then see the [docs](/docs).\n\nOr <span class=\"x\">ask</span>.",
+//!     render_markdown(&doc, Config::default())
+//! );
+//! ```
+
+mod convert;
+mod tests;
+mod tokenizer;
+mod tree;
+
+use crate::ast::Document;
+
+/// Parse an HTML fragment or document into an AST.
+pub fn parse_html(input: &str) -> Document {
+    let root = tree::parse(input);
+    let converter = convert::Converter { source: input };
+    Document {
+        blocks: converter.blocks(&root.children),
+    }
+}
@@ -0,0 +1,223 @@
+#![cfg(test)]
+use crate::ast::*;
+use crate::html_parser::parse_html;
+use crate::printer::{config::Config, render_markdown};
+use rstest::rstest;
+
+fn markdown(input: &str) -> String {
+    render_markdown(&parse_html(input), Config::default())
+}
+
+#[rstest]
+#[case("<h1>One</h1><h6> Six </h6>", "# One\n\n###### Six")]
+#[case("Loose <b>text</b>", "Loose **text**")]
+#[case(
+    "<p>a\n   b</p>\n\n<p>c&amp;d &lt;e&gt; &#x41;&#66; &bogus; & g</p>",
+    "a b\n\nc\\&d \\<e> AB \\&bogus; \\& g"
+)]
+#[case("<p>one<br>two<br/>\n three</p>", "one  \ntwo  \nthree")]
+#[case(
+    "<p><em>a</em> <i>b</i> <strong>c</strong> <del>d</del> <s>e</s></p>",
+    "*a* *b* **c** ~~d~~ ~~e~~"
+)]
+#[case("<p>x<em> spaced </em>y</p>", "x *spaced* y")]
+#[case("<p>a <em> </em> b</p>", "a b")]
+#[case("<p><code>a  &lt;b&gt;\nc</code></p>", "`a  <b> c`")]
+#[case(
+    "<p><a href=\"/x\" title=\"T\">link</a> <a name=\"top\">anchor</a></p>",
+    "[link](/x \"T\") <a name=\"top\">anchor</a>"
+)]
+#[case(
+    "<p><img src=\"a.png\" alt=\"A\"> <img alt=\"no source\"></p>",
+    "![A](a.png) <img alt=\"no source\">"
+)]
+#[case(
+    "<blockquote><p>quoted</p><blockquote>nested</blockquote></blockquote>",
+    "> quoted\n> \n> > nested"
+)]
+#[case("<hr><p>after</p>", "---\n\nafter")]
+#[case("<div><section><p>inside</p></section></div>", "inside")]
+#[case(
+    "<div class=\"note\">\n<p>Careful</p>\n</div>\n<p>after</p>",
+    "<div class=\"note\">\n<p>Careful</p>\n</div>\n\nafter"
+)]
+#[case(
+    "<html lang=\"en\"><body class=\"page\"><p>Body</p></body></html>",
+    "Body"
+)]
+#[case(
+    "<!DOCTYPE html><html><head><title>T</title></head><body><p>Body</p></body></html>",
+    "Body"
+)]
+#[case(
+    "<p>a</p><!-- note --><p>b <!-- inline --></p>",
+    "a\n\n<!-- note -->\n\nb <!-- inline -->"
+)]
+#[case(
+    "<dl><dt>term<dd>definition</dl>\n<p>after</p>",
+    "<dl><dt>term<dd>definition</dl>\n\nafter"
+)]
+#[case("<p>a<p>b<div>c</div>", "a\n\nb\n\nc")]
+#[case("<p>a</b></p>", "a")]
+#[case("a < b <3", "a \\< b \\<3")]
+fn convert(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, markdown(input));
+}
+
+/// Text which would be Markdown is escaped, so the Markdown keeps the structure of
+/// the HTML when it is parsed again.
+#[rstest]
+#[case("<p># not heading</p>", "\\# not heading")]
+#[case("<p>> not quote</p>", "\\> not quote")]
+#[case("<p>- not item</p>", "\\- not item")]
+#[case("<p>+ not item</p>", "\\+ not item")]
+#[case("<p>* not item</p>", "\\* not item")]
+#[case("<p>1. not a list</p>", "1\\. not a list")]
+#[case("<p>12) not a list</p>", "12\\) not a list")]
+#[case("<p>a<br>2. b</p>", "a  \n2\\. b")]
+#[case("<strong>*star*</strong>", "**\\*star\\***")]
+#[case(
+    "<p>_a_ `b` [c] &lt;d> \\e &amp;amp; ~~f~~</p>",
+    r"\_a\_ \`b\` \[c\] \<d> \\e \&amp; \~\~f\~\~"
+)]
+fn escaping(#[case] input: &str, #[case] expected: &str) {
+    let doc = parse_html(input);
+    let result = render_markdown(&doc, Config::default());
+    assert_eq!(expected, result);
+    let reparsed =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), &result)
+            .unwrap();
+    // The parser decodes some escapes, compare without them
+    let structure = |doc: &Document| format!("{doc:?}").replace('\\', "");
+    assert_eq!(structure(&doc), structure(&reparsed));
+}
+
+#[test]
+fn entities() {
+    assert_eq!(
+        vec![Block::Paragraph(vec![Inline::Link(Link {
+            destination: "/a?b=1&c=2".to_owned(),
+            title: None,
+            children: vec![Inline::Text("\u{a0}\u{2014}\u{fffd}\\&#;".to_owned())],
+        })])],
+        parse_html("<a href='/a?b=1&amp;c=2'>&nbsp;&mdash;&#0;&#;</a>").blocks
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        " - one\n - two\n   \n   more\n   \n    1. three",
+        markdown("<ul>\n  <li>one\n  <li><p>two</p><p>more</p>\n    <ol><li>three</ol>\n</ul>")
+    );
+    assert_eq!(
+        Block::List(List {
+            kind: ListKind::Ordered(ListOrderedKindOptions { start: 3 }),
+            items: vec![
+                ListItem {
+                    task: Some(TaskState::Complete),
+                    blocks: vec![Block::Paragraph(vec![Inline::Text("done".to_owned())])],
+                },
+                ListItem {
+                    task: Some(TaskState::Incomplete),
+                    blocks: vec![Block::Paragraph(vec![Inline::Text("todo".to_owned())])],
+                },
+                ListItem {
+                    task: None,
+                    blocks: vec![],
+                },
+            ],
+        }),
+        parse_html(
+            "<ol start=\"3\"><li><input type=\"checkbox\" checked disabled> done</li>\
+             <li><p><input type=checkbox>todo</p></li><li></li></ol>"
+        )
+        .blocks[0]
+    );
+}
+
+#[test]
+fn code_blocks() {
+    assert_eq!(
+        vec![
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced {
+                    info: Some("rust".to_owned())
+                },
+                literal: "fn main() {\n    <span>x</span>\n}".to_owned(),
+            }),
+            Block::CodeBlock(CodeBlock {
+                kind: CodeBlockKind::Fenced { info: None },
+                literal: "a &\n  b".to_owned(),
+            }),
+        ],
+        parse_html(
+            "<pre><code class=\"hljs language-rust\">\nfn main() {\n    &lt;span&gt;<b>x</b>&lt;/span&gt;\n}\n</code></pre>\
+             <pre>a &amp;<br>  b</pre>"
+        )
+        .blocks
+    );
+    assert_eq!(
+        "```python\nprint(1)\n```",
+        markdown("<pre class=\"lang-python\">print(1)</pre>")
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        Block::Table(Table {
+            rows: vec![
+                vec![
+                    vec![Inline::Text("Name".to_owned())],
+                    vec![Inline::Text("Size".to_owned())],
+                    vec![Inline::Text("Kind".to_owned())],
+                ],
+                vec![
+                    vec![Inline::Code("a".to_owned())],
+                    vec![Inline::Text("1".to_owned())],
+                    vec![],
+                ],
+            ],
+            alignments: vec![Alignment::None, Alignment::Right, Alignment::Center],
+        }),
+        parse_html(
+            "<table>\n<thead><tr><th>Name</th><th align=\"right\">Size<th style=\"color: red; text-align: center\">Kind</thead>\n\
+             <tbody><tr><td><p><code>a</code></p><td>1</tbody>\n</table>"
+        )
+        .blocks[0]
+    );
+    let merged = "<table><tr><td colspan=\"2\">a</td></tr></table>";
+    assert_eq!(
+        vec![Block::HtmlBlock(merged.to_owned())],
+        parse_html(merged).blocks
+    );
+    let lists = "<table><tr><td><ul><li>a</li></ul></td></tr></table>";
+    assert_eq!(
+        vec![Block::HtmlBlock(lists.to_owned())],
+        parse_html(lists).blocks
+    );
+}
+
+#[test]
+fn raw_text_elements() {
+    assert_eq!(
+        vec![
+            Block::HtmlBlock("<script>if (a < b && c) { x = \"</p>\"; }</script>".to_owned()),
+            Block::Paragraph(vec![Inline::Text("after".to_owned())]),
+        ],
+        parse_html("<script>if (a < b && c) { x = \"</p>\"; }</script><p>after</p>").blocks
+    );
+}
+
+#[test]
+fn unclosed_elements() {
+    assert_eq!(
+        vec![Block::Paragraph(vec![
+            Inline::Text("a ".to_owned()),
+            Inline::Html("<span>b <em>c".to_owned()),
+        ])],
+        parse_html("<p>a <span>b <em>c").blocks
+    );
+    assert_eq!(Vec::<Block>::new(), parse_html("").blocks);
+}
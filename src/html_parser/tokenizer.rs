@@ -0,0 +1,195 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till, take_until, take_while, take_while1},
+    character::complete::{alpha1, anychar, char, digit1, hex_digit1, multispace0, one_of},
+    combinator::{map, map_opt, opt, recognize, rest, value},
+    multi::many0,
+    sequence::{delimited, pair, preceded, terminated},
+    IResult, Parser,
+};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone)]
+pub(crate) enum Token {
+    StartTag {
+        name: String,
+        attributes: Vec<(String, String)>,
+        self_closing: bool,
+    },
+    EndTag(String),
+    /// Text with character references decoded.
+    Text(String),
+    Comment,
+    /// Doctype or processing instruction.
+    Declaration,
+}
+
+/// The next token. Never fails on non-empty input, as a `<` which does not start a
+/// tag is text.
+pub(crate) fn token(input: &str) -> IResult<&str, Token> {
+    alt((comment, declaration, end_tag, start_tag, text)).parse(input)
+}
+
+/// Content of a raw text element such as `<script>`, up to its end tag, and the
+/// input after the end tag.
+pub(crate) fn raw_text<'a>(input: &'a str, name: &str) -> (&'a str, &'a str) {
+    // ASCII lowercasing keeps byte offsets
+    let Some(end) = input.to_ascii_lowercase().find(&format!("</{name}")) else {
+        return ("", input);
+    };
+    let after = &input[end..];
+    let rest = match after.find('>') {
+        Some(close) => &after[close + 1..],
+        None => "",
+    };
+    (rest, &input[..end])
+}
+
+fn comment(input: &str) -> IResult<&str, Token> {
+    value(
+        Token::Comment,
+        preceded(
+            tag("<!--"),
+            alt((terminated(take_until("-->"), tag("-->")), rest)),
+        ),
+    )
+    .parse(input)
+}
+
+fn declaration(input: &str) -> IResult<&str, Token> {
+    value(
+        Token::Declaration,
+        (
+            alt((tag("<!"), tag("<?"))),
+            take_till(|c| c == '>'),
+            char('>'),
+        ),
+    )
+    .parse(input)
+}
+
+fn end_tag(input: &str) -> IResult<&str, Token> {
+    map(
+        delimited(tag("</"), tag_name, (take_till(|c| c == '>'), char('>'))),
+        |name| Token::EndTag(name.to_ascii_lowercase()),
+    )
+    .parse(input)
+}
+
+fn start_tag(input: &str) -> IResult<&str, Token> {
+    map(
+        (
+            char('<'),
+            tag_name,
+            many0(preceded(multispace0, attribute)),
+            multispace0,
+            opt(char('/')),
+            char('>'),
+        ),
+        |(_, name, attributes, _, slash, _)| Token::StartTag {
+            name: name.to_ascii_lowercase(),
+            attributes,
+            self_closing: slash.is_some(),
+        },
+    )
+    .parse(input)
+}
+
+fn tag_name(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        alpha1,
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == ':'),
+    ))
+    .parse(input)
+}
+
+fn attribute(input: &str) -> IResult<&str, (String, String)> {
+    let name = take_while1(|c: char| !c.is_whitespace() && !"/>=\"'".contains(c));
+    let quoted = alt((
+        delimited(char('"'), take_till(|c| c == '"'), char('"')),
+        delimited(char('\''), take_till(|c| c == '\''), char('\'')),
+    ));
+    let unquoted = take_while1(|c: char| !c.is_whitespace() && !"\"'=<>`".contains(c));
+    map(
+        pair(
+            name,
+            opt(preceded(
+                (multispace0, char('='), multispace0),
+                alt((quoted, unquoted)),
+            )),
+        ),
+        |(name, value): (&str, Option<&str>)| {
+            (name.to_ascii_lowercase(), decode(value.unwrap_or_default()))
+        },
+    )
+    .parse(input)
+}
+
+fn text(input: &str) -> IResult<&str, Token> {
+    map(recognize(pair(anychar, take_till(|c| c == '<'))), |text| {
+        Token::Text(decode(text))
+    })
+    .parse(input)
+}
+
+/// Text with character references such as `&amp;` and `&#x41;` decoded.
+pub(crate) fn decode(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match character_reference(rest) {
+            Ok((after, decoded)) => {
+                result.push_str(&decoded);
+                rest = after;
+            }
+            Err(_) => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn character_reference(input: &str) -> IResult<&str, String> {
+    alt((named_reference, numeric_reference)).parse(input)
+}
+
+fn named_reference(input: &str) -> IResult<&str, String> {
+    static ENTITIES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    let entities = ENTITIES.get_or_init(|| {
+        entities::ENTITIES
+            .iter()
+            .map(|entity| (entity.entity, entity.characters))
+            .collect()
+    });
+    map_opt(
+        recognize((
+            char('&'),
+            take_while1(|c: char| c.is_ascii_alphanumeric()),
+            char(';'),
+        )),
+        |reference: &str| entities.get(reference).map(|c| (*c).to_owned()),
+    )
+    .parse(input)
+}
+
+fn numeric_reference(input: &str) -> IResult<&str, String> {
+    let base16 = map_opt(preceded(one_of("xX"), hex_digit1), |s: &str| {
+        u32::from_str_radix(s, 16).ok()
+    });
+    let base10 = map_opt(digit1, |s: &str| s.parse::<u32>().ok());
+
+    map(
+        delimited(tag("&#"), alt((base10, base16)), char(';')),
+        |code| match char::from_u32(code) {
+            Some(c) if c != '\0' => c.to_string(),
+            _ => '\u{fffd}'.to_string(),
+        },
+    )
+    .parse(input)
+}
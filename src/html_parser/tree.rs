@@ -0,0 +1,202 @@
+use crate::html_parser::tokenizer::{raw_text, token, Token};
+use std::ops::Range;
+
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+    /// Comment with its range in the source.
+    Comment(Range<usize>),
+}
+
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Node>,
+    /// Range in the source, from the start tag to the end tag or the end of the
+    /// last child when the end tag is omitted.
+    pub(crate) source: Range<usize>,
+}
+
+impl Element {
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Elements without content or end tag.
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements whose content is text up to their end tag, without markup.
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["script", "style", "textarea", "title"];
+
+/// Elements which end an open paragraph.
+const PARAGRAPH_CLOSERS: [&str; 32] = [
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "main",
+    "menu",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+    "li",
+];
+
+/// Phrasing elements, which an implicitly closed element may contain.
+const PHRASING_ELEMENTS: [&str; 30] = [
+    "a", "abbr", "b", "bdi", "bdo", "big", "cite", "code", "data", "del", "dfn", "em", "font", "i",
+    "ins", "kbd", "mark", "q", "s", "samp", "small", "span", "strike", "strong", "sub", "sup",
+    "time", "tt", "u", "var",
+];
+
+/// Parse HTML into a tree below a root element, following the rules for omitted end
+/// tags of paragraphs, list items and tables. Unmatched end tags, doctypes and
+/// processing instructions are ignored and open elements are closed at the end of the
+/// input.
+pub(crate) fn parse(input: &str) -> Element {
+    let mut stack = vec![Element {
+        name: String::new(),
+        attributes: Vec::new(),
+        children: Vec::new(),
+        source: 0..input.len(),
+    }];
+    let offset = |rest: &str| input.len() - rest.len();
+
+    let mut rest = input;
+    while let Ok((next, token)) = token(rest) {
+        let start = offset(rest);
+        let end = offset(next);
+        rest = next;
+        match token {
+            Token::Text(text) => push(&mut stack, Node::Text(text)),
+            Token::Comment => push(&mut stack, Node::Comment(start..end)),
+            Token::Declaration => (),
+            Token::EndTag(name) => {
+                if let Some(position) = stack.iter().rposition(|element| element.name == name) {
+                    if position > 0 {
+                        close(&mut stack, position, start);
+                        if let Some(Node::Element(element)) = stack
+                            .last_mut()
+                            .and_then(|parent| parent.children.last_mut())
+                        {
+                            element.source.end = end;
+                        }
+                    }
+                }
+            }
+            Token::StartTag {
+                name,
+                attributes,
+                self_closing,
+            } => {
+                close_implied(&mut stack, &name, start);
+                let mut element = Element {
+                    name,
+                    attributes,
+                    children: Vec::new(),
+                    source: start..end,
+                };
+                if RAW_TEXT_ELEMENTS.contains(&element.name.as_str()) {
+                    let (next, text) = raw_text(rest, &element.name);
+                    let text = match element.name.as_str() {
+                        "script" | "style" => text.to_owned(),
+                        _ => crate::html_parser::tokenizer::decode(text),
+                    };
+                    element.children.push(Node::Text(text));
+                    element.source.end = offset(next);
+                    rest = next;
+                    push(&mut stack, Node::Element(element));
+                } else if self_closing || VOID_ELEMENTS.contains(&element.name.as_str()) {
+                    push(&mut stack, Node::Element(element));
+                } else {
+                    stack.push(element);
+                }
+            }
+        }
+    }
+
+    close(&mut stack, 1, input.len());
+    stack.pop().expect("the root element is never closed")
+}
+
+fn push(stack: &mut [Element], node: Node) {
+    stack
+        .last_mut()
+        .expect("the root element is never closed")
+        .children
+        .push(node);
+}
+
+/// Close the elements from `position` to the top of the stack, ending at `end`.
+fn close(stack: &mut Vec<Element>, position: usize, end: usize) {
+    while stack.len() > position {
+        let mut element = stack.pop().expect("stack is longer than position");
+        element.source.end = end;
+        push(stack, Node::Element(element));
+    }
+}
+
+/// Close the elements whose end tag is implied by the start tag `name`.
+fn close_implied(stack: &mut Vec<Element>, name: &str, end: usize) {
+    let (closed, boundaries): (&[&str], &[&str]) = match name {
+        "li" => (&["li"], &["ul", "ol", "menu", "table"]),
+        "dt" | "dd" => (&["dt", "dd"], &["dl", "table"]),
+        "tr" => (&["tr"], &["table"]),
+        "td" | "th" => (&["td", "th"], &["tr", "table"]),
+        "thead" | "tbody" | "tfoot" => (&["thead", "tbody", "tfoot"], &["table"]),
+        _ => (&[], &[]),
+    };
+    let position = stack
+        .iter()
+        .rposition(|element| closed.contains(&element.name.as_str()))
+        .filter(|&position| {
+            stack[position + 1..]
+                .iter()
+                .all(|element| !boundaries.contains(&element.name.as_str()))
+        });
+    if let Some(position) = position {
+        close(stack, position, end);
+    }
+
+    // A paragraph only contains phrasing content
+    if PARAGRAPH_CLOSERS.contains(&name) {
+        let position = stack.iter().rposition(|element| element.name == "p");
+        if let Some(position) = position {
+            let phrasing = stack[position + 1..]
+                .iter()
+                .all(|element| PHRASING_ELEMENTS.contains(&element.name.as_str()));
+            if phrasing {
+                close(stack, position, end);
+            }
+        }
+    }
+}
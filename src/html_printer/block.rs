@@ -1,5 +1,6 @@
 use crate::ast::*;
-use crate::html_printer::util::tag;
+use crate::html_printer::config::{CssClass, TableAlignmentStyle};
+use crate::html_printer::util::{class_attribute, tag, void_tag, with_render_hook};
 use crate::html_printer::ToDoc;
 use pretty::{Arena, DocAllocator, DocBuilder};
 
@@ -22,13 +23,17 @@ impl<'a> ToDoc<'a> for Vec<&Block> {
 impl<'a> ToDoc<'a> for Block {
     fn to_doc(&self, state: &'a crate::html_printer::State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
         match self {
+            Block::Paragraph(inlines) if state.config.figures => match inlines.as_slice() {
+                [Inline::Image(image)] => figure_to_doc(state, image),
+                _ => tag(state, "p", Vec::new(), inlines.to_doc(state)),
+            },
             Block::Paragraph(inlines) => {
                 let inner = state
                     .arena
                     .concat(inlines.iter().map(|inline| inline.to_doc(state)));
                 tag(state, "p", Vec::new(), inner)
             }
-            Block::Heading(v) => {
+            Block::Heading(v) => with_render_hook(state, &state.config.heading_renderer, v, || {
                 let htag = match v.kind {
                     HeadingKind::Atx(1) => "h1",
                     HeadingKind::Atx(2) => "h2",
@@ -43,7 +48,7 @@ impl<'a> ToDoc<'a> for Block {
                     .arena
                     .concat(v.content.iter().map(|inline| inline.to_doc(state)));
                 tag(state, htag, Vec::new(), inner)
-            }
+            }),
             Block::ThematicBreak => tag(state, "hr", Vec::new(), state.arena.nil()),
             Block::BlockQuote(inner) => {
                 let inner = state
@@ -52,72 +57,142 @@ impl<'a> ToDoc<'a> for Block {
                 tag(state, "blockquote", Vec::new(), inner)
             }
             Block::List(v) => v.to_doc(state),
-            Block::CodeBlock(v) => v.to_doc(state),
+            Block::CodeBlock(v) => {
+                with_render_hook(state, &state.config.code_block_renderer, v, || {
+                    v.to_doc(state)
+                })
+            }
             Block::HtmlBlock(html) => state.arena.text(html.clone()),
             Block::Definition(_) => state.arena.nil(),
             Block::Empty => state.arena.nil(),
-            Block::Table(v) => v.to_doc(state),
-            Block::FootnoteDefinition(def) => def.to_doc(state),
+            Block::Table(v) => {
+                with_render_hook(state, &state.config.table_renderer, v, || v.to_doc(state))
+            }
+            // Rendered in the footnotes section at the end of the document
+            Block::FootnoteDefinition(_) => state.arena.nil(),
+            Block::ContainerDirective(v) => v.to_doc(state),
+            Block::LeafDirective(v) => crate::html_printer::directive::directive_to_doc(
+                state,
+                crate::html_printer::config::DirectiveKind::Leaf,
+                v,
+            ),
         }
     }
 }
 
+/// `<figure>` with the image and a caption from its title or alt text.
+fn figure_to_doc<'a>(
+    state: &'a crate::html_printer::State<'a>,
+    image: &Image,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let caption = image.title.as_deref().unwrap_or(&image.alt);
+    let (attributes, number) = match &state.config.figure_label {
+        Some(label) => {
+            let n = state.next_figure();
+            (
+                vec![(
+                    "id".to_owned(),
+                    format!("{}figure-{n}", state.config.anchor_prefix),
+                )],
+                Some(format!("{label} {n}")),
+            )
+        }
+        None => (Vec::new(), None),
+    };
+    let caption = match (number, caption.is_empty()) {
+        (Some(number), true) => number,
+        (Some(number), false) => format!("{number}: {caption}"),
+        (None, _) => caption.to_owned(),
+    };
+
+    let mut content = Inline::Image(image.clone()).to_doc(state);
+    if !caption.is_empty() {
+        content = content.append(tag(
+            state,
+            "figcaption",
+            Vec::new(),
+            state
+                .arena
+                .text(crate::html_printer::util::escape(&caption)),
+        ));
+    }
+    tag(state, "figure", attributes, content)
+}
+
 impl<'a> ToDoc<'a> for List {
     fn to_doc(&self, state: &'a crate::html_printer::State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
         let items = state
             .arena
             .concat(self.items.iter().map(|item| item.to_doc(state)));
+        let mut classes = Vec::new();
+        if let ListKind::Bullet(kind) = self.kind {
+            classes.push(match kind {
+                ListBulletKind::Dash => CssClass::ListKindDash,
+                ListBulletKind::Star => CssClass::ListKindStar,
+                ListBulletKind::Plus => CssClass::ListKindPlus,
+            });
+        }
+        if self.items.iter().any(|item| item.task.is_some()) {
+            classes.push(CssClass::ContainsTaskList);
+        }
+        let class = class_attribute(state, &classes);
         match self.kind {
-            ListKind::Ordered(ListOrderedKindOptions { start }) => tag(
-                state,
-                "ol",
-                vec![("start".to_owned(), format!("{}", start))],
-                items,
-            ),
-            ListKind::Bullet(kind) => {
-                let style = match kind {
-                    ListBulletKind::Dash => "markdown-list-kind-dash",
-                    ListBulletKind::Star => "markdown-list-kind-star",
-                    ListBulletKind::Plus => "markdown-list-kind-plus",
-                };
-                tag(
-                    state,
-                    "ul",
-                    vec![("class".to_owned(), style.to_owned())],
-                    items,
-                )
+            ListKind::Ordered(ListOrderedKindOptions { start }) => {
+                let mut attributes = vec![("start".to_owned(), format!("{}", start))];
+                attributes.extend(class);
+                tag(state, "ol", attributes, items)
             }
+            ListKind::Bullet(_) => tag(state, "ul", class.into_iter().collect(), items),
         }
     }
 }
 
 impl<'a> ToDoc<'a> for ListItem {
     fn to_doc(&self, state: &'a crate::html_printer::State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
-        let task = match self.task {
-            Some(TaskState::Complete) => tag(
-                state,
-                "span",
-                vec![("class".to_owned(), "markdown-list-task-complete".to_owned())],
-                state.arena.text("[X] "),
-            ),
-            Some(TaskState::Incomplete) => tag(
+        let Some(task) = self.task else {
+            return tag(state, "li", vec![], self.blocks.to_doc(state));
+        };
+
+        let index = state.next_task();
+        let mut attributes = vec![("type".to_owned(), "checkbox".to_owned())];
+        if state.config.interactive_task_lists {
+            attributes.push(("data-task-index".to_owned(), format!("{index}")));
+        } else {
+            attributes.push(("disabled".to_owned(), String::new()));
+        }
+        if task == TaskState::Complete {
+            attributes.push(("checked".to_owned(), String::new()));
+        }
+        let checkbox = void_tag(state, "input", attributes).append(state.arena.text(" "));
+
+        // The checkbox goes into the first paragraph, as on GitHub
+        let content = match self.blocks.split_first() {
+            Some((Block::Paragraph(inlines), rest)) => tag(
                 state,
-                "span",
-                vec![(
-                    "class".to_owned(),
-                    "markdown-list-task-incomplete".to_owned(),
-                )],
-                state.arena.text("[ ] "),
+                "p",
+                Vec::new(),
+                checkbox.append(
+                    state
+                        .arena
+                        .concat(inlines.iter().map(|inline| inline.to_doc(state))),
+                ),
+            )
+            .append(
+                state
+                    .arena
+                    .concat(rest.iter().map(|block| block.to_doc(state))),
             ),
-            None => state.arena.nil(),
+            _ => checkbox.append(self.blocks.to_doc(state)),
         };
-        let content = task.append(
-            state
-                .arena
-                .concat(self.blocks.iter().map(|block| block.to_doc(state))),
-        );
 
-        tag(state, "li", vec![], content)
+        tag(
+            state,
+            "li",
+            class_attribute(state, &[CssClass::TaskListItem])
+                .into_iter()
+                .collect(),
+            content,
+        )
     }
 }
 
@@ -162,51 +237,95 @@ fn table_row_to_doc<'a>(
 ) -> DocBuilder<'a, Arena<'a>, ()> {
     let mut acc = state.arena.nil();
     for (i, cell) in row.iter().enumerate() {
-        let alignment = match alignments.get(i) {
-            Some(Alignment::Left) | Some(Alignment::None) => "left",
-            Some(Alignment::Right) => "right",
-            Some(Alignment::Center) => "center",
-            None => "left",
+        let alignment = alignments.get(i).copied().unwrap_or(Alignment::None);
+        let attributes = match (state.config.table_alignment_style, alignment) {
+            (TableAlignmentStyle::Class, alignment) => {
+                let class = match alignment {
+                    Alignment::Left | Alignment::None => CssClass::TableAlignLeft,
+                    Alignment::Right => CssClass::TableAlignRight,
+                    Alignment::Center => CssClass::TableAlignCenter,
+                };
+                class_attribute(state, &[class]).into_iter().collect()
+            }
+            (_, Alignment::None) => Vec::new(),
+            (style, alignment) => {
+                let alignment = match alignment {
+                    Alignment::Right => "right",
+                    Alignment::Center => "center",
+                    _ => "left",
+                };
+                match style {
+                    TableAlignmentStyle::Style => {
+                        vec![("style".to_owned(), format!("text-align: {alignment}"))]
+                    }
+                    _ => vec![("align".to_owned(), alignment.to_owned())],
+                }
+            }
         };
-        let alignment_class = format!("markdown-table-align-{}", alignment);
-        let attributes = vec![("class".to_owned(), alignment_class.to_owned())];
         acc = acc.append(tag(state, row_tag, attributes, cell.to_doc(state)));
     }
 
     tag(state, "tr", Vec::new(), acc)
 }
 
+/// Item of the footnotes section, with back-references to every reference.
 impl<'a> ToDoc<'a> for FootnoteDefinition {
     fn to_doc(&self, state: &'a crate::html_printer::State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
         let index = match state.get_footnote_index(&self.label) {
             Some(v) => v,
             None => return state.arena.nil(),
         };
+        let prefix = &state.config.anchor_prefix;
+        let backrefs = state.arena.intersperse(
+            (1..=state.get_footnote_reference_count(&self.label)).map(|n| {
+                let (id, suffix) = match n {
+                    1 => (format!("{index}"), state.arena.nil()),
+                    n => (
+                        format!("{index}-{n}"),
+                        tag(state, "sup", Vec::new(), state.arena.text(format!("{n}"))),
+                    ),
+                };
+                let mut attributes = vec![("href".to_owned(), format!("#{prefix}fnref-{id}"))];
+                attributes.extend(class_attribute(state, &[CssClass::FootnoteBackReference]));
+                attributes.push(("aria-label".to_owned(), format!("Back to reference {id}")));
+                tag(
+                    state,
+                    "a",
+                    attributes,
+                    state.arena.text("\u{21a9}").append(suffix),
+                )
+            }),
+            state.arena.text(" "),
+        );
+
+        // Back-references go to the end of the last paragraph, as on GitHub
+        let content = match self.blocks.split_last() {
+            Some((Block::Paragraph(inlines), rest)) => rest
+                .iter()
+                .fold(state.arena.nil(), |acc, block| {
+                    acc.append(block.to_doc(state))
+                })
+                .append(tag(
+                    state,
+                    "p",
+                    Vec::new(),
+                    state
+                        .arena
+                        .concat(inlines.iter().map(|inline| inline.to_doc(state)))
+                        .append(state.arena.text(" "))
+                        .append(backrefs),
+                )),
+            _ => self
+                .blocks
+                .to_doc(state)
+                .append(tag(state, "p", Vec::new(), backrefs)),
+        };
+
         tag(
             state,
-            "div",
-            vec![(
-                "class".to_owned(),
-                "markdown-footnote-definition".to_owned(),
-            )],
-            tag(
-                state,
-                "span",
-                vec![(
-                    "class".to_owned(),
-                    "markdown-footnote-definition-index".to_owned(),
-                )],
-                state.arena.text(format!("{index}. ")),
-            )
-            .append(tag(
-                state,
-                "span",
-                vec![(
-                    "class".to_owned(),
-                    "markdown-footnote-definition-content".to_owned(),
-                )],
-                self.blocks.to_doc(state),
-            )),
+            "li",
+            vec![("id".to_owned(), format!("{prefix}fn-{index}"))],
+            content,
         )
     }
 }
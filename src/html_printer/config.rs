@@ -1,6 +1,177 @@
+use crate::ast::{Block, CodeBlock, Heading, Image, Inline, Link, Mention, Table, WikiLink};
+use crate::html_printer::ToDoc;
+use pretty::DocAllocator;
+use std::collections::HashMap;
+
+/// Function type for resolving wiki-link targets to URLs.
+pub type WikiLinkResolverFn = crate::Xrc<dyn Fn(&WikiLink) -> String>;
+
+/// Function type for resolving mentions and issue or commit references to URLs.
+/// Returning `None` renders the mention as plain text.
+pub type MentionResolverFn = crate::Xrc<dyn Fn(&Mention) -> Option<String>>;
+
+/// Function type for resolving image dimensions as `(width, height)`.
+pub type ImageSizeFn = crate::Xrc<dyn Fn(&Image) -> Option<(u32, u32)>>;
+
+/// Function type for overriding the rendering of a node. Returning `None` falls back
+/// to the default rendering.
+pub type RenderHookFn<T> = crate::Xrc<dyn Fn(&T, &RenderContext) -> Option<String>>;
+
+/// Handle passed to a [`RenderHookFn`] to render parts of the node with the default
+/// renderer.
+pub struct RenderContext<'c, 'a> {
+    pub(crate) state: &'a crate::html_printer::State<'a>,
+    pub(crate) default: &'c dyn Fn() -> String,
+}
+
+impl RenderContext<'_, '_> {
+    /// Render the node itself as it would be rendered without the hook.
+    pub fn render_default(&self) -> String {
+        (self.default)()
+    }
+
+    /// Render inline nodes, e.g. link children or heading content.
+    pub fn render_inlines(&self, inlines: &[Inline]) -> String {
+        let state = self.state;
+        let doc = state
+            .arena
+            .concat(inlines.iter().map(|inline| inline.to_doc(state)));
+        crate::html_printer::util::render_to_string(state, doc)
+    }
+
+    /// Render block nodes.
+    pub fn render_blocks(&self, blocks: &[Block]) -> String {
+        let state = self.state;
+        let doc = state
+            .arena
+            .concat(blocks.iter().map(|block| block.to_doc(state)));
+        crate::html_printer::util::render_to_string(state, doc)
+    }
+
+    /// Escape text for use in HTML content or attribute values.
+    pub fn escape(&self, text: &str) -> String {
+        crate::html_printer::util::escape(text)
+    }
+}
+
+/// CSS classes emitted by the HTML printer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CssClass {
+    /// Bullet list with `-` markers, `markdown-list-kind-dash` by default
+    ListKindDash,
+    /// Bullet list with `*` markers, `markdown-list-kind-star` by default
+    ListKindStar,
+    /// Bullet list with `+` markers, `markdown-list-kind-plus` by default
+    ListKindPlus,
+    /// Left aligned table cell, `markdown-table-align-left` by default
+    TableAlignLeft,
+    /// Centered table cell, `markdown-table-align-center` by default
+    TableAlignCenter,
+    /// Right aligned table cell, `markdown-table-align-right` by default
+    TableAlignRight,
+    /// Label paragraph of a container directive, `markdown-directive-label` by default
+    DirectiveLabel,
+    /// List containing task items, `contains-task-list` by default
+    ContainsTaskList,
+    /// Task list item, `task-list-item` by default
+    TaskListItem,
+    /// Footnotes section, `footnotes` by default
+    Footnotes,
+    /// Footnote reference, `footnote-ref` by default
+    FootnoteReference,
+    /// Back-reference from a footnote, `footnote-backref` by default
+    FootnoteBackReference,
+}
+
+impl CssClass {
+    /// Default class name. Classes specific to this crate get the configured prefix,
+    /// GitHub-compatible ones are used as is.
+    fn default_name(self, prefix: &str) -> String {
+        let (prefixed, name) = match self {
+            CssClass::ListKindDash => (true, "list-kind-dash"),
+            CssClass::ListKindStar => (true, "list-kind-star"),
+            CssClass::ListKindPlus => (true, "list-kind-plus"),
+            CssClass::TableAlignLeft => (true, "table-align-left"),
+            CssClass::TableAlignCenter => (true, "table-align-center"),
+            CssClass::TableAlignRight => (true, "table-align-right"),
+            CssClass::DirectiveLabel => (true, "directive-label"),
+            CssClass::ContainsTaskList => (false, "contains-task-list"),
+            CssClass::TaskListItem => (false, "task-list-item"),
+            CssClass::Footnotes => (false, "footnotes"),
+            CssClass::FootnoteReference => (false, "footnote-ref"),
+            CssClass::FootnoteBackReference => (false, "footnote-backref"),
+        };
+        if prefixed {
+            format!("{prefix}{name}")
+        } else {
+            name.to_owned()
+        }
+    }
+}
+
+/// How table cell alignment is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TableAlignmentStyle {
+    /// `class="markdown-table-align-…"`, see [`CssClass::TableAlignLeft`] and others.
+    /// Cells without alignment are rendered as left aligned.
+    #[default]
+    Class,
+    /// `style="text-align: …"`, as on github.com
+    Style,
+    /// `align="…"`, as rendered by cmark-gfm
+    Attribute,
+}
+
+/// Kind of a generic directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `:::name` block containing other blocks
+    Container,
+    /// `::name` block
+    Leaf,
+    /// `:name` inline
+    Text,
+}
+
+/// A directive passed to a [`DirectiveRenderFn`], with label and content already
+/// rendered to HTML.
+#[derive(Debug, Clone)]
+pub struct RenderedDirective<'d> {
+    pub kind: DirectiveKind,
+    pub name: &'d str,
+    pub attributes: &'d [(String, String)],
+    /// Rendered label, empty if the directive has no label
+    pub label: String,
+    /// Rendered content blocks, always empty for leaf and text directives
+    pub content: String,
+}
+
+/// Function type for rendering a directive to HTML.
+pub type DirectiveRenderFn = crate::Xrc<dyn Fn(&RenderedDirective) -> String>;
+
+#[derive(Clone)]
 pub struct Config {
     pub(crate) width: usize,
     pub(crate) anchor_prefix: String,
+    pub(crate) wiki_link_resolver: Option<WikiLinkResolverFn>,
+    pub(crate) mention_resolver: Option<MentionResolverFn>,
+    pub(crate) directive_renderers: HashMap<String, DirectiveRenderFn>,
+    pub(crate) interactive_task_lists: bool,
+    pub(crate) css_classes: bool,
+    pub(crate) class_prefix: String,
+    pub(crate) class_names: HashMap<CssClass, Option<String>>,
+    pub(crate) table_alignment_style: TableAlignmentStyle,
+    pub(crate) link_renderer: Option<RenderHookFn<Link>>,
+    pub(crate) image_renderer: Option<RenderHookFn<Image>>,
+    pub(crate) heading_renderer: Option<RenderHookFn<Heading>>,
+    pub(crate) code_block_renderer: Option<RenderHookFn<CodeBlock>>,
+    pub(crate) table_renderer: Option<RenderHookFn<Table>>,
+    pub(crate) inline_html_renderer: Option<RenderHookFn<str>>,
+    pub(crate) url_rewriter: Option<crate::html_printer::url::UrlRewriter>,
+    pub(crate) figures: bool,
+    pub(crate) figure_label: Option<String>,
+    pub(crate) image_size_resolver: Option<ImageSizeFn>,
+    pub(crate) lazy_images: bool,
 }
 
 impl Default for Config {
@@ -8,6 +179,25 @@ impl Default for Config {
         Self {
             width: 80,
             anchor_prefix: String::new(),
+            wiki_link_resolver: None,
+            mention_resolver: None,
+            directive_renderers: HashMap::new(),
+            interactive_task_lists: false,
+            css_classes: true,
+            class_prefix: "markdown-".to_owned(),
+            class_names: HashMap::new(),
+            table_alignment_style: TableAlignmentStyle::default(),
+            link_renderer: None,
+            image_renderer: None,
+            heading_renderer: None,
+            code_block_renderer: None,
+            table_renderer: None,
+            inline_html_renderer: None,
+            url_rewriter: None,
+            figures: false,
+            figure_label: None,
+            image_size_resolver: None,
+            lazy_images: false,
         }
     }
 }
@@ -23,4 +213,174 @@ impl Config {
             ..self
         }
     }
+
+    /// Set a function that maps wiki-links and embeds to URLs. Without a resolver the
+    /// target is used as is, followed by `#fragment` if present.
+    pub fn with_wiki_link_resolver(self, resolver: WikiLinkResolverFn) -> Self {
+        Self {
+            wiki_link_resolver: Some(resolver),
+            ..self
+        }
+    }
+
+    /// Set a function that maps mentions and issue or commit references to URLs.
+    /// Without a resolver, or when it returns `None`, mentions are rendered as text.
+    pub fn with_mention_resolver(self, resolver: MentionResolverFn) -> Self {
+        Self {
+            mention_resolver: Some(resolver),
+            ..self
+        }
+    }
+
+    /// Render task list checkboxes enabled instead of `disabled`, with a
+    /// `data-task-index` attribute numbering them in rendering order. The number
+    /// indexes [`task_states_mut`](crate::html_printer::task_states_mut).
+    pub fn with_interactive_task_lists(self, interactive_task_lists: bool) -> Self {
+        Self {
+            interactive_task_lists,
+            ..self
+        }
+    }
+
+    /// Enable or disable all CSS classes. Enabled by default.
+    pub fn with_css_classes(self, css_classes: bool) -> Self {
+        Self {
+            css_classes,
+            ..self
+        }
+    }
+
+    /// Set the prefix of crate-specific CSS classes. Default is `markdown-`.
+    pub fn with_class_prefix(self, class_prefix: impl Into<String>) -> Self {
+        Self {
+            class_prefix: class_prefix.into(),
+            ..self
+        }
+    }
+
+    /// Override the name of a CSS class, or suppress it with `None`.
+    pub fn with_class_name(mut self, class: CssClass, name: Option<String>) -> Self {
+        self.class_names.insert(class, name);
+        self
+    }
+
+    /// Set how table cell alignment is rendered. Default is [`TableAlignmentStyle::Class`].
+    pub fn with_table_alignment_style(self, table_alignment_style: TableAlignmentStyle) -> Self {
+        Self {
+            table_alignment_style,
+            ..self
+        }
+    }
+
+    /// Rewrite destinations of links, images and autolinks. Render hooks get nodes
+    /// with already rewritten destinations.
+    pub fn with_url_rewriter(self, url_rewriter: crate::html_printer::url::UrlRewriter) -> Self {
+        Self {
+            url_rewriter: Some(url_rewriter),
+            ..self
+        }
+    }
+
+    /// Render paragraphs consisting of a single image as `<figure>` with a
+    /// `<figcaption>` taken from the image title, or the alt text if there is no
+    /// title. Disabled by default.
+    pub fn with_figures(self, figures: bool) -> Self {
+        Self { figures, ..self }
+    }
+
+    /// Number figures: captions start with `<label> <n>: ` and figures get an
+    /// `id="figure-<n>"` (with the anchor prefix) for cross-references.
+    pub fn with_figure_label(self, label: impl Into<String>) -> Self {
+        Self {
+            figure_label: Some(label.into()),
+            ..self
+        }
+    }
+
+    /// Set a function providing `width` and `height` attributes of images.
+    pub fn with_image_size_resolver(self, resolver: ImageSizeFn) -> Self {
+        Self {
+            image_size_resolver: Some(resolver),
+            ..self
+        }
+    }
+
+    /// Add `loading="lazy"` to images. Disabled by default.
+    pub fn with_lazy_images(self, lazy_images: bool) -> Self {
+        Self {
+            lazy_images,
+            ..self
+        }
+    }
+
+    /// Override rendering of links, including resolved reference links.
+    pub fn with_link_renderer(self, renderer: RenderHookFn<Link>) -> Self {
+        Self {
+            link_renderer: Some(renderer),
+            ..self
+        }
+    }
+
+    /// Override rendering of images.
+    pub fn with_image_renderer(self, renderer: RenderHookFn<Image>) -> Self {
+        Self {
+            image_renderer: Some(renderer),
+            ..self
+        }
+    }
+
+    /// Override rendering of headings.
+    pub fn with_heading_renderer(self, renderer: RenderHookFn<Heading>) -> Self {
+        Self {
+            heading_renderer: Some(renderer),
+            ..self
+        }
+    }
+
+    /// Override rendering of code blocks.
+    pub fn with_code_block_renderer(self, renderer: RenderHookFn<CodeBlock>) -> Self {
+        Self {
+            code_block_renderer: Some(renderer),
+            ..self
+        }
+    }
+
+    /// Override rendering of tables.
+    pub fn with_table_renderer(self, renderer: RenderHookFn<Table>) -> Self {
+        Self {
+            table_renderer: Some(renderer),
+            ..self
+        }
+    }
+
+    /// Override rendering of inline raw HTML.
+    pub fn with_inline_html_renderer(self, renderer: RenderHookFn<str>) -> Self {
+        Self {
+            inline_html_renderer: Some(renderer),
+            ..self
+        }
+    }
+
+    /// Name of the CSS class, `None` if it is suppressed.
+    pub(crate) fn class_name(&self, class: CssClass) -> Option<String> {
+        if !self.css_classes {
+            return None;
+        }
+        match self.class_names.get(&class) {
+            Some(name) => name.clone(),
+            None => Some(class.default_name(&self.class_prefix)),
+        }
+    }
+
+    /// Set a function rendering directives with the given name. Directives without
+    /// a renderer are rendered as `<div>` (container and leaf) or `<span>` (text)
+    /// with a `data-directive` attribute and the directive attributes.
+    pub fn with_directive_renderer(
+        mut self,
+        name: impl Into<String>,
+        renderer: DirectiveRenderFn,
+    ) -> Self {
+        self.directive_renderers.insert(name.into(), renderer);
+        self
+    }
 }
@@ -0,0 +1,86 @@
+use crate::ast::*;
+use crate::html_printer::config::{CssClass, DirectiveKind, RenderedDirective};
+use crate::html_printer::util::{class_attribute, tag};
+use crate::html_printer::{State, ToDoc};
+use pretty::{Arena, DocAllocator, DocBuilder};
+
+impl<'a> ToDoc<'a> for ContainerDirective {
+    fn to_doc(&self, state: &'a State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
+        render(
+            state,
+            DirectiveKind::Container,
+            &self.name,
+            &self.label,
+            &self.attributes,
+            Some(&self.blocks),
+        )
+    }
+}
+
+pub(crate) fn directive_to_doc<'a>(
+    state: &'a State<'a>,
+    kind: DirectiveKind,
+    directive: &Directive,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    render(
+        state,
+        kind,
+        &directive.name,
+        &directive.label,
+        &directive.attributes,
+        None,
+    )
+}
+
+fn render<'a>(
+    state: &'a State<'a>,
+    kind: DirectiveKind,
+    name: &str,
+    label: &[Inline],
+    attributes: &[(String, String)],
+    blocks: Option<&Vec<Block>>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let label_doc = state
+        .arena
+        .concat(label.iter().map(|inline| inline.to_doc(state)));
+    let content_doc = match blocks {
+        Some(blocks) => blocks.to_doc(state),
+        None => state.arena.nil(),
+    };
+
+    if let Some(renderer) = state.config.directive_renderers.get(name) {
+        let directive = RenderedDirective {
+            kind,
+            name,
+            attributes,
+            label: crate::html_printer::util::render_to_string(state, label_doc),
+            content: crate::html_printer::util::render_to_string(state, content_doc),
+        };
+        return state.arena.text(renderer(&directive));
+    }
+
+    let mut html_attributes = vec![("data-directive".to_owned(), name.to_owned())];
+    html_attributes.extend(attributes.iter().cloned());
+    match kind {
+        DirectiveKind::Container => tag(
+            state,
+            "div",
+            html_attributes,
+            if label.is_empty() {
+                content_doc
+            } else {
+                tag(
+                    state,
+                    "p",
+                    class_attribute(state, &[CssClass::DirectiveLabel])
+                        .into_iter()
+                        .collect(),
+                    label_doc,
+                )
+                .append(content_doc)
+            },
+        ),
+        DirectiveKind::Leaf => tag(state, "div", html_attributes, label_doc),
+        DirectiveKind::Text => tag(state, "span", html_attributes, label_doc),
+    }
+}
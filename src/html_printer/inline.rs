@@ -1,5 +1,6 @@
 use crate::ast::*;
-use crate::html_printer::util::{escape, tag};
+use crate::html_printer::config::CssClass;
+use crate::html_printer::util::{class_attribute, escape, tag, with_render_hook};
 use crate::html_printer::ToDoc;
 use pretty::{Arena, DocAllocator, DocBuilder};
 
@@ -19,71 +20,204 @@ impl<'a> ToDoc<'a> for Inline {
             Inline::Text(t) => state.arena.text(escape(t)),
             Inline::LineBreak => tag(state, "br", Vec::new(), state.arena.nil()),
             Inline::Code(code) => tag(state, "code", Vec::new(), state.arena.text(escape(code))),
-            Inline::Html(html) => state.arena.text(html.clone()),
+            Inline::Html(html) => {
+                with_render_hook(state, &state.config.inline_html_renderer, html, || {
+                    state.arena.text(html.clone())
+                })
+            }
             Inline::Emphasis(children) => tag(state, "em", Vec::new(), children.to_doc(state)),
             Inline::Strong(children) => tag(state, "b", Vec::new(), children.to_doc(state)),
             Inline::Strikethrough(children) => tag(state, "s", Vec::new(), children.to_doc(state)),
-            Inline::Link(Link {
-                destination,
-                title,
-                children,
-            }) => {
-                let mut attributes = vec![("href".to_owned(), escape(destination))];
-                if let Some(title) = title {
-                    attributes.push(("title".to_owned(), escape(title)))
-                }
-                tag(state, "a", attributes, children.to_doc(state))
+            Inline::Link(v) => link_to_doc(state, v),
+            Inline::Image(v) => {
+                let image = match &state.config.url_rewriter {
+                    Some(rewriter) => &Image {
+                        destination: rewriter.rewrite_image(&v.destination),
+                        ..v.clone()
+                    },
+                    None => v,
+                };
+                with_render_hook(state, &state.config.image_renderer, image, || {
+                    let mut attributes = vec![
+                        ("src".to_owned(), escape(&image.destination)),
+                        ("alt".to_owned(), escape(&image.alt)),
+                    ];
+                    if let Some(title) = &image.title {
+                        attributes.push(("title".to_owned(), escape(title)))
+                    }
+                    if let Some((width, height)) = state
+                        .config
+                        .image_size_resolver
+                        .as_ref()
+                        .and_then(|resolver| resolver(image))
+                    {
+                        attributes.push(("width".to_owned(), format!("{width}")));
+                        attributes.push(("height".to_owned(), format!("{height}")));
+                    }
+                    if state.config.lazy_images {
+                        attributes.push(("loading".to_owned(), "lazy".to_owned()));
+                    }
+                    tag(state, "img", attributes, state.arena.nil())
+                })
             }
-            Inline::Image(Image {
-                destination,
-                title,
-                alt,
-            }) => {
-                let mut attributes = vec![
-                    ("src".to_owned(), escape(destination)),
-                    ("alt".to_owned(), escape(alt)),
-                ];
-                if let Some(title) = title {
-                    attributes.push(("title".to_owned(), escape(title)))
+            Inline::Autolink(link) => {
+                let mut destination = autolink_destination(link);
+                let mut attributes = Vec::new();
+                if let Some(rewriter) = &state.config.url_rewriter {
+                    destination = rewriter.rewrite_link(&destination);
+                    attributes = rewriter.link_attributes(&destination);
                 }
-                tag(state, "img", attributes, state.arena.nil())
+                attributes.insert(0, ("href".to_owned(), escape(&destination)));
+                tag(state, "a", attributes, state.arena.text(escape(link)))
             }
-            Inline::Autolink(link) => tag(
-                state,
-                "a",
-                vec![("href".to_owned(), escape(link))],
-                state.arena.text(escape(link)),
-            ),
             Inline::FootnoteReference(label) => {
                 let index = match state.get_footnote_index(label) {
                     Some(v) => v,
-                    None => return state.arena.nil(),
+                    None => return state.arena.text(escape(&format!("[^{label}]"))),
+                };
+                let prefix = &state.config.anchor_prefix;
+                let id = match state.next_footnote_reference(label) {
+                    1 => format!("{prefix}fnref-{index}"),
+                    n => format!("{prefix}fnref-{index}-{n}"),
                 };
                 tag(
                     state,
-                    "a",
-                    vec![
-                        ("class".to_owned(), "markdown-footnote-reference".to_owned()),
-                        (
-                            "href".to_owned(),
-                            escape(&format!("#{}{}", state.config.anchor_prefix, index)),
-                        ),
-                    ],
-                    state.arena.text(format!("[{}]", index)),
+                    "sup",
+                    class_attribute(state, &[CssClass::FootnoteReference])
+                        .into_iter()
+                        .collect(),
+                    tag(
+                        state,
+                        "a",
+                        vec![
+                            ("href".to_owned(), format!("#{prefix}fn-{index}")),
+                            ("id".to_owned(), id),
+                        ],
+                        state.arena.text(format!("{index}")),
+                    ),
                 )
             }
+            Inline::WikiLink(v) => {
+                let destination = match &state.config.wiki_link_resolver {
+                    Some(resolver) => resolver(v),
+                    None => wiki_link_destination(v),
+                };
+                let text = match (&v.alias, &v.fragment) {
+                    (Some(alias), _) => alias.clone(),
+                    (None, Some(fragment)) if v.target.is_empty() => fragment.clone(),
+                    (None, Some(fragment)) => format!("{} > {}", v.target, fragment),
+                    (None, None) => v.target.clone(),
+                };
+                if v.embed {
+                    let attributes =
+                        vec![("src".to_owned(), destination), ("alt".to_owned(), text)];
+                    tag(state, "img", attributes, state.arena.nil())
+                } else {
+                    tag(
+                        state,
+                        "a",
+                        vec![("href".to_owned(), destination)],
+                        state.arena.text(escape(&text)),
+                    )
+                }
+            }
+            Inline::Mention(v) => {
+                let text = state.arena.text(escape(&mention_text(v)));
+                match state
+                    .config
+                    .mention_resolver
+                    .as_ref()
+                    .and_then(|resolver| resolver(v))
+                {
+                    Some(destination) => {
+                        tag(state, "a", vec![("href".to_owned(), destination)], text)
+                    }
+                    None => text,
+                }
+            }
+            // Replaced with footnote references before rendering
+            Inline::InlineFootnote(_) => state.arena.nil(),
+            Inline::TextDirective(v) => crate::html_printer::directive::directive_to_doc(
+                state,
+                crate::html_printer::config::DirectiveKind::Text,
+                v,
+            ),
             Inline::Empty => state.arena.nil(),
             Inline::LinkReference(v) => {
                 let definition = match state.get_link_definition(&v.label) {
                     Some(v) => v,
                     None => return state.arena.nil(),
                 };
-                let mut attributes =
-                    vec![("href".to_owned(), escape(definition.destination.as_str()))];
-                if let Some(title) = &definition.title {
-                    attributes.push(("title".to_owned(), escape(title)))
-                }
-                tag(state, "a", attributes, v.text.to_doc(state))
+                let link = Link {
+                    destination: definition.destination.clone(),
+                    title: definition.title.clone(),
+                    children: v.text.clone(),
+                };
+                link_to_doc(state, &link)
+            }
+        }
+    }
+}
+
+fn link_to_doc<'a>(
+    state: &'a crate::html_printer::State<'a>,
+    link: &Link,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let rewritten;
+    let link = match &state.config.url_rewriter {
+        Some(rewriter) => {
+            rewritten = Link {
+                destination: rewriter.rewrite_link(&link.destination),
+                ..link.clone()
+            };
+            &rewritten
+        }
+        None => link,
+    };
+    with_render_hook(state, &state.config.link_renderer, link, || {
+        let mut attributes = vec![("href".to_owned(), escape(&link.destination))];
+        if let Some(title) = &link.title {
+            attributes.push(("title".to_owned(), escape(title)))
+        }
+        if let Some(rewriter) = &state.config.url_rewriter {
+            attributes.extend(rewriter.link_attributes(&link.destination));
+        }
+        tag(state, "a", attributes, link.children.to_doc(state))
+    })
+}
+
+/// Destination of an autolink: `www.` links get an `http://` scheme and bare
+/// email addresses get `mailto:`.
+fn autolink_destination(link: &str) -> String {
+    if link.starts_with("www.") {
+        format!("http://{link}")
+    } else if !link.contains(':') && link.contains('@') {
+        format!("mailto:{link}")
+    } else {
+        link.to_owned()
+    }
+}
+
+/// Default wiki-link destination: the target followed by the `#fragment`.
+fn wiki_link_destination(link: &WikiLink) -> String {
+    match &link.fragment {
+        Some(fragment) => format!("{}#{}", link.target, fragment),
+        None => link.target.clone(),
+    }
+}
+
+/// Link text of a mention, commit SHAs are shortened to 7 digits.
+fn mention_text(mention: &Mention) -> String {
+    match mention {
+        Mention::User(name) => format!("@{name}"),
+        Mention::Issue { repository, number } => {
+            format!("{}#{number}", repository.as_deref().unwrap_or_default())
+        }
+        Mention::Commit { repository, sha } => {
+            let short = sha.get(..7).unwrap_or(sha);
+            match repository {
+                Some(repository) => format!("{repository}@{short}"),
+                None => short.to_owned(),
             }
         }
     }
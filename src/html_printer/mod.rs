@@ -1,52 +1,288 @@
 mod block;
 pub mod config;
-mod index;
+mod directive;
 mod inline;
 mod tests;
+pub mod url;
 mod util;
 
 use crate::ast::*;
-use pretty::{Arena, DocBuilder};
+use pretty::{Arena, DocAllocator, DocBuilder};
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 
 pub(crate) struct State<'a> {
     arena: Arena<'a>,
     config: crate::html_printer::config::Config,
-    // Mapping of footnote labels to their indices in the footnote list.
-    footnote_index: HashMap<String, usize>,
-    // Mapping of link labels to their definitions.
-    link_definitions: HashMap<Vec<Inline>, LinkDefinition>,
+    index: crate::Xrc<crate::renderer::index::Index>,
+    // Number of already rendered references to each footnote.
+    footnote_references_rendered: RefCell<HashMap<String, usize>>,
+    // Number of already rendered figures.
+    figures_rendered: Cell<usize>,
+    // Number of already rendered task list items.
+    tasks_rendered: Cell<usize>,
 }
 
 impl State<'_> {
     pub fn new(config: crate::html_printer::config::Config, ast: &Document) -> Self {
-        let (footnote_index, link_definitions) = crate::html_printer::index::get_indicies(ast);
+        let index = crate::Xrc::new(crate::renderer::index::Index::new(ast));
+        Self::with_index(config, index)
+    }
+
+    pub fn with_index(
+        config: crate::html_printer::config::Config,
+        index: crate::Xrc<crate::renderer::index::Index>,
+    ) -> Self {
         let arena = Arena::new();
         Self {
             arena,
             config,
-            footnote_index,
-            link_definitions,
+            index,
+            footnote_references_rendered: RefCell::new(HashMap::new()),
+            figures_rendered: Cell::new(0),
+            tasks_rendered: Cell::new(0),
         }
     }
 
-    pub fn get_footnote_index(&self, label: &str) -> Option<&usize> {
-        self.footnote_index.get(label)
+    pub fn get_footnote_index(&self, label: &str) -> Option<usize> {
+        self.index.footnote_index(label)
+    }
+
+    /// Register a rendered task list item, returning its number starting from 0.
+    pub fn next_task(&self) -> usize {
+        self.tasks_rendered.set(self.tasks_rendered.get() + 1);
+        self.tasks_rendered.get() - 1
+    }
+
+    /// Register a rendered figure, returning its number starting from 1.
+    pub fn next_figure(&self) -> usize {
+        self.figures_rendered.set(self.figures_rendered.get() + 1);
+        self.figures_rendered.get()
     }
 
-    pub fn get_link_definition(&self, label: &Vec<Inline>) -> Option<&LinkDefinition> {
-        self.link_definitions.get(label)
+    /// Number of references to the footnote in the whole document.
+    pub fn get_footnote_reference_count(&self, label: &str) -> usize {
+        self.index.footnote_reference_count(label)
+    }
+
+    /// Register a rendered reference to the footnote, returning its ordinal number
+    /// starting from 1.
+    pub fn next_footnote_reference(&self, label: &str) -> usize {
+        let mut rendered = self.footnote_references_rendered.borrow_mut();
+        let counter = rendered.entry(label.to_owned()).or_default();
+        *counter += 1;
+        *counter
+    }
+
+    pub fn get_link_definition(&self, label: &[Inline]) -> Option<&LinkDefinition> {
+        self.index.link_definition(label)
     }
 }
 
 /// Render the given Markdown AST to HTML.
 pub fn render_html(ast: &Document, config: crate::html_printer::config::Config) -> String {
-    let state = crate::Xrc::new(State::new(config, ast));
-    let doc = ast.to_doc(&state);
+    let mut result = String::new();
+    render_html_to_fmt(&mut result, ast, config).expect("writing to a String never fails");
+    result
+}
 
-    let mut buf = Vec::new();
-    doc.render(state.config.width, &mut buf).unwrap();
-    String::from_utf8(buf).unwrap()
+/// Render the given Markdown AST to HTML, writing the output to `writer` as it is
+/// produced.
+pub fn render_html_to<W: std::io::Write>(
+    writer: &mut W,
+    ast: &Document,
+    config: crate::html_printer::config::Config,
+) -> std::io::Result<()> {
+    let ast = crate::ast::footnotes::hoisted(ast);
+    let state = crate::Xrc::new(State::new(config, &ast));
+    ast.to_doc(&state).render(state.config.width, writer)
+}
+
+/// Render the given Markdown AST to HTML, writing the output to a [`std::fmt::Write`]
+/// such as a `String` or a `Formatter`.
+pub fn render_html_to_fmt<W: std::fmt::Write>(
+    writer: &mut W,
+    ast: &Document,
+    config: crate::html_printer::config::Config,
+) -> std::fmt::Result {
+    let ast = crate::ast::footnotes::hoisted(ast);
+    let state = crate::Xrc::new(State::new(config, &ast));
+    ast.to_doc(&state).render_fmt(state.config.width, writer)
+}
+
+/// Link definitions and footnote numbers of a whole document, for rendering
+/// fragments of it with [`render_html_block`], [`render_html_blocks`] and
+/// [`render_html_inlines`]. Footnotes are numbered as in [`render_html`].
+#[derive(Clone)]
+pub struct DocumentIndex {
+    index: crate::Xrc<crate::renderer::index::Index>,
+    // Labels given to the inline footnotes of the document, with their content.
+    inline_footnotes: crate::Xrc<Vec<(Vec<Inline>, String)>>,
+}
+
+impl DocumentIndex {
+    pub fn new(ast: &Document) -> Self {
+        let mut document = ast.clone();
+        let inline_footnotes =
+            crate::ast::footnotes::hoist_inline_footnotes_with_labels(&mut document, &[]);
+        Self {
+            index: crate::Xrc::new(crate::renderer::index::Index::new(&document)),
+            inline_footnotes: crate::Xrc::new(inline_footnotes),
+        }
+    }
+}
+
+/// Replace the inline footnotes of a fragment with footnote references, labeled as in
+/// the document of `index`, and return the index to render the fragment with.
+fn hoisted_fragment(
+    blocks: Vec<Block>,
+    index: Option<&DocumentIndex>,
+) -> (Document, crate::Xrc<crate::renderer::index::Index>) {
+    let mut fragment = Document { blocks };
+    if crate::ast::footnotes::has_inline_footnotes(&fragment) {
+        let known = index.map_or(&[][..], |index| &index.inline_footnotes[..]);
+        crate::ast::footnotes::hoist_inline_footnotes_with_labels(&mut fragment, known);
+    }
+    let index = match index {
+        Some(index) => index.index.clone(),
+        None => crate::Xrc::new(crate::renderer::index::Index::new(&fragment)),
+    };
+    (fragment, index)
+}
+
+/// Render a single block to HTML, see [`render_html_blocks`].
+pub fn render_html_block(
+    block: &Block,
+    config: crate::html_printer::config::Config,
+    index: Option<&DocumentIndex>,
+) -> String {
+    render_html_blocks(std::slice::from_ref(block), config, index)
+}
+
+/// Render a sequence of blocks to HTML.
+///
+/// Link references and footnote references are resolved with `index`, or with the
+/// definitions inside the fragment if no index is given. Inline footnotes are
+/// numbered as in the document of `index`, or within the fragment. Footnote
+/// definitions are not rendered and no footnotes section is appended.
+///
+/// ```rust
+/// use markdown_ppp::html_printer::config::Config;
+/// use markdown_ppp::html_printer::{render_html_block, DocumentIndex};
+/// use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+///
+/// let doc = parse_markdown(
+///     MarkdownParserState::default(),
+///     "Intro\n\nSee [docs]\n\n[docs]: https://example.com",
+/// )
+/// .unwrap();
+/// let index = DocumentIndex::new(&doc);
+/// assert_eq!(
+///     "<p>See <a href=\"https://example.com\">docs</a></p>",
+///     render_html_block(&doc.blocks[1], Config::default(), Some(&index)),
+/// );
+/// ```
+pub fn render_html_blocks(
+    blocks: &[Block],
+    config: crate::html_printer::config::Config,
+    index: Option<&DocumentIndex>,
+) -> String {
+    let (fragment, index) = hoisted_fragment(blocks.to_vec(), index);
+    let state = State::with_index(config, index);
+    // Definitions of hoisted inline footnotes are not rendered
+    let doc = fragment.blocks[..blocks.len()]
+        .iter()
+        .collect::<Vec<_>>()
+        .to_doc(&state);
+    let mut result = String::new();
+    doc.render_fmt(state.config.width, &mut result)
+        .expect("writing to a String never fails");
+    result
+}
+
+/// Render a sequence of inlines to HTML without an enclosing paragraph, e.g. the
+/// content of a heading or a table cell. References are resolved as in
+/// [`render_html_blocks`].
+pub fn render_html_inlines(
+    inlines: &[Inline],
+    config: crate::html_printer::config::Config,
+    index: Option<&DocumentIndex>,
+) -> String {
+    let (fragment, index) = hoisted_fragment(vec![Block::Paragraph(inlines.to_vec())], index);
+    let Some(Block::Paragraph(inlines)) = fragment.blocks.first() else {
+        unreachable!("the fragment starts with the paragraph");
+    };
+    let state = State::with_index(config, index);
+    let doc = inlines.to_doc(&state);
+    let mut result = String::new();
+    doc.render_fmt(state.config.width, &mut result)
+        .expect("writing to a String never fails");
+    result
+}
+
+/// Checkbox states of the task list items of the document, indexed by the
+/// `data-task-index` attribute of checkboxes rendered with
+/// [`Config::with_interactive_task_lists`](crate::html_printer::config::Config::with_interactive_task_lists).
+///
+/// Items are numbered in the order they are rendered: items of the document body
+/// first, then items of the footnotes in the order of the footnotes section.
+/// Fragments rendered with [`render_html_blocks`] number their own items from 0, so
+/// the states of a fragment are those of a document made of its blocks.
+///
+/// ```rust
+/// use markdown_ppp::ast::TaskState;
+/// use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+///
+/// let mut doc = parse_markdown(MarkdownParserState::default(), "- [ ] one\n- [ ] two").unwrap();
+/// // Toggle the checkbox with `data-task-index="1"`
+/// *markdown_ppp::html_printer::task_states_mut(&mut doc)[1] = TaskState::Complete;
+/// ```
+pub fn task_states_mut(ast: &mut Document) -> Vec<&mut TaskState> {
+    // States of the items of footnotes are collected separately by label
+    fn collect<'a>(
+        blocks: &'a mut [Block],
+        states: &mut Vec<&'a mut TaskState>,
+        footnotes: &mut HashMap<String, Vec<&'a mut TaskState>>,
+    ) {
+        for block in blocks {
+            match block {
+                Block::List(v) => {
+                    for item in &mut v.items {
+                        if let Some(task) = &mut item.task {
+                            states.push(task);
+                        }
+                        collect(&mut item.blocks, states, footnotes);
+                    }
+                }
+                Block::BlockQuote(v) => collect(v, states, footnotes),
+                // Only the first definition of a label is rendered
+                Block::FootnoteDefinition(v) if !footnotes.contains_key(&v.label) => {
+                    let mut footnote_states = Vec::new();
+                    collect(&mut v.blocks, &mut footnote_states, footnotes);
+                    footnotes.insert(v.label.clone(), footnote_states);
+                }
+                Block::ContainerDirective(v) => collect(&mut v.blocks, states, footnotes),
+                _ => (),
+            }
+        }
+    }
+
+    let rendered_footnotes = {
+        let ast = crate::ast::footnotes::hoisted(ast);
+        let index = crate::renderer::index::Index::new(&ast);
+        index
+            .footnotes()
+            .iter()
+            .map(|footnote| footnote.label.clone())
+            .collect::<Vec<_>>()
+    };
+    let mut states = Vec::new();
+    let mut footnotes = HashMap::new();
+    collect(&mut ast.blocks, &mut states, &mut footnotes);
+    for label in rendered_footnotes {
+        states.extend(footnotes.remove(&label).unwrap_or_default());
+    }
+    states
 }
 
 trait ToDoc<'a> {
@@ -55,6 +291,27 @@ trait ToDoc<'a> {
 
 impl<'a> ToDoc<'a> for Document {
     fn to_doc(&self, state: &'a State<'a>) -> DocBuilder<'a, Arena<'a>, ()> {
-        self.blocks.to_doc(state)
+        let body = self.blocks.to_doc(state);
+        if state.index.footnotes().is_empty() {
+            return body;
+        }
+        let footnotes = state.arena.concat(
+            state
+                .index
+                .footnotes()
+                .iter()
+                .map(|footnote| footnote.to_doc(state)),
+        );
+        body.append(crate::html_printer::util::tag(
+            state,
+            "section",
+            crate::html_printer::util::class_attribute(
+                state,
+                &[crate::html_printer::config::CssClass::Footnotes],
+            )
+            .into_iter()
+            .collect(),
+            crate::html_printer::util::tag(state, "ol", Vec::new(), footnotes),
+        ))
     }
 }
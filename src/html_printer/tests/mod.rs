@@ -24,12 +24,16 @@ use rstest::rstest;
 )]
 #[case(
     "Hello[^1]\n\n[^1]: This is a footnote.",
-    "<p>Hello<a class=\"markdown-footnote-reference\" href=\"#1\">[1]</a></p><div class=\"markdown-footnote-definition\"><span class=\"markdown-footnote-definition-index\">1. </span><span class=\"markdown-footnote-definition-content\"><p>This is a footnote.</p></span></div>"
+    "<p>Hello<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup></p><section class=\"footnotes\"><ol><li id=\"fn-1\"><p>This is a footnote. <a href=\"#fnref-1\" class=\"footnote-backref\" aria-label=\"Back to reference 1\">↩</a></p></li></ol></section>"
 )]
 #[case(
     "![alt text](https://example.com/image.png)",
     "<p><img src=\"https://example.com/image.png\" alt=\"alt text\"></img></p>"
 )]
+#[case(
+    "See www.example.com or <foo@bar.com>.",
+    "<p>See <a href=\"http://www.example.com\">www.example.com</a> or <a href=\"mailto:foo@bar.com\">foo@bar.com</a>.</p>"
+)]
 #[case(
     "| Header 1 | Header 2 |
 | --- | --: |
@@ -45,3 +49,587 @@ fn render_to_html(#[case] input: &str, #[case] expected: &str) {
     let result = crate::html_printer::render_html(&ast, config);
     assert_eq!(expected, result);
 }
+
+#[test]
+fn wiki_links() {
+    use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+    let parser_config =
+        MarkdownParserConfig::default().with_inline_wiki_link_behavior(ElementBehavior::Parse);
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(parser_config),
+        "[[Page#Intro]], [[Page|alias]] and ![[image.png]]",
+    )
+    .unwrap();
+
+    let result =
+        crate::html_printer::render_html(&ast, crate::html_printer::config::Config::default());
+    assert_eq!(
+        "<p><a href=\"Page#Intro\">Page &gt; Intro</a>, <a href=\"Page\">alias</a> and <img src=\"image.png\" alt=\"image.png\"></img></p>",
+        result
+    );
+
+    let config = crate::html_printer::config::Config::default().with_wiki_link_resolver(
+        crate::Xrc::new(|link: &crate::ast::WikiLink| format!("/wiki/{}.html", link.target)),
+    );
+    let result = crate::html_printer::render_html(&ast, config);
+    assert_eq!(
+        "<p><a href=\"/wiki/Page.html\">Page &gt; Intro</a>, <a href=\"/wiki/Page.html\">alias</a> and <img src=\"/wiki/image.png.html\" alt=\"image.png\"></img></p>",
+        result
+    );
+}
+
+#[test]
+fn wiki_links_escaping() {
+    use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+    let parser_config =
+        MarkdownParserConfig::default().with_inline_wiki_link_behavior(ElementBehavior::Parse);
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(parser_config),
+        "[[Q&A]] and ![[a&b.png]]",
+    )
+    .unwrap();
+
+    let result =
+        crate::html_printer::render_html(&ast, crate::html_printer::config::Config::default());
+    assert_eq!(
+        "<p><a href=\"Q&amp;A\">Q&amp;A</a> and <img src=\"a&amp;b.png\" alt=\"a&amp;b.png\"></img></p>",
+        result
+    );
+}
+
+#[test]
+fn mentions() {
+    use crate::ast::Mention;
+    use crate::parser::config::MarkdownParserConfig;
+    use crate::parser::mention::{mention_parser, MentionConfig};
+    let parser_config = MarkdownParserConfig::default()
+        .with_custom_inline_parser_at_word_starts(mention_parser(MentionConfig::default()));
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(parser_config),
+        "@alice fixed #12 in a5c3785ed8d6",
+    )
+    .unwrap();
+
+    let config = crate::html_printer::config::Config::default().with_mention_resolver(
+        crate::Xrc::new(|mention: &Mention| match mention {
+            Mention::User(name) => Some(format!("https://github.com/{name}")),
+            Mention::Issue { number, .. } => Some(format!(
+                "https://github.com/acme/app/issues/{number}?a=1&b=2"
+            )),
+            Mention::Commit { .. } => None,
+        }),
+    );
+    let result = crate::html_printer::render_html(&ast, config);
+    assert_eq!(
+        "<p><a href=\"https://github.com/alice\">@alice</a> fixed <a href=\"https://github.com/acme/app/issues/12?a=1&amp;b=2\">#12</a> in a5c3785</p>",
+        result
+    );
+}
+
+#[test]
+fn directives() {
+    use crate::html_printer::config::{DirectiveKind, RenderedDirective};
+    use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+    let parser_config = MarkdownParserConfig::default()
+        .with_block_container_directive_behavior(ElementBehavior::Parse)
+        .with_block_leaf_directive_behavior(ElementBehavior::Parse)
+        .with_inline_text_directive_behavior(ElementBehavior::Parse);
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(parser_config),
+        ":::note[Heads up]{.info}\nPress :kbd[Ctrl]\n:::\n\n::youtube{vid=abc}",
+    )
+    .unwrap();
+
+    let result =
+        crate::html_printer::render_html(&ast, crate::html_printer::config::Config::default());
+    assert_eq!(
+        "<div data-directive=\"note\" class=\"info\"><p class=\"markdown-directive-label\">Heads up</p><p>Press <span data-directive=\"kbd\">Ctrl</span></p></div><div data-directive=\"youtube\" vid=\"abc\"></div>",
+        result
+    );
+
+    let config = crate::html_printer::config::Config::default()
+        .with_directive_renderer(
+            "note",
+            crate::Xrc::new(|d: &RenderedDirective| {
+                assert_eq!(d.kind, DirectiveKind::Container);
+                format!("<aside title=\"{}\">{}</aside>", d.label, d.content)
+            }),
+        )
+        .with_directive_renderer(
+            "kbd",
+            crate::Xrc::new(|d: &RenderedDirective| format!("<kbd>{}</kbd>", d.label)),
+        );
+    let result = crate::html_printer::render_html(&ast, config);
+    assert_eq!(
+        "<aside title=\"Heads up\"><p>Press <kbd>Ctrl</kbd></p></aside><div data-directive=\"youtube\" vid=\"abc\"></div>",
+        result
+    );
+}
+
+#[test]
+fn footnotes() {
+    let input = "A[^b] *and*[^a], again[^b].
+
+[^a]: Note a with a nested note[^c].
+
+[^b]: Note b.
+
+[^c]: Note c.
+
+[^unused]: Never referenced.
+
+Missing[^missing].";
+    let ast = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let config =
+        crate::html_printer::config::Config::default().with_anchor_prefix("doc-".to_owned());
+    let result = crate::html_printer::render_html(&ast, config);
+    assert_eq!(
+        concat!(
+            "<p>A<sup class=\"footnote-ref\"><a href=\"#doc-fn-1\" id=\"doc-fnref-1\">1</a></sup> ",
+            "<em>and</em><sup class=\"footnote-ref\"><a href=\"#doc-fn-2\" id=\"doc-fnref-2\">2</a></sup>, ",
+            "again<sup class=\"footnote-ref\"><a href=\"#doc-fn-1\" id=\"doc-fnref-1-2\">1</a></sup>.</p>",
+            "<p>Missing[^missing].</p>",
+            "<section class=\"footnotes\"><ol>",
+            "<li id=\"doc-fn-1\"><p>Note b. ",
+            "<a href=\"#doc-fnref-1\" class=\"footnote-backref\" aria-label=\"Back to reference 1\">↩</a> ",
+            "<a href=\"#doc-fnref-1-2\" class=\"footnote-backref\" aria-label=\"Back to reference 1-2\">↩<sup>2</sup></a>",
+            "</p></li>",
+            "<li id=\"doc-fn-2\"><p>Note a with a nested note",
+            "<sup class=\"footnote-ref\"><a href=\"#doc-fn-3\" id=\"doc-fnref-3\">3</a></sup>. ",
+            "<a href=\"#doc-fnref-2\" class=\"footnote-backref\" aria-label=\"Back to reference 2\">↩</a></p></li>",
+            "<li id=\"doc-fn-3\"><p>Note c. ",
+            "<a href=\"#doc-fnref-3\" class=\"footnote-backref\" aria-label=\"Back to reference 3\">↩</a></p></li>",
+            "</ol></section>",
+        ),
+        result
+    );
+}
+
+#[test]
+fn inline_footnotes() {
+    let input = "A[^x] b^[Inline *note*.]\n\n[^x]: Regular.";
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap();
+    let result =
+        crate::html_printer::render_html(&ast, crate::html_printer::config::Config::default());
+    assert_eq!(
+        concat!(
+            "<p>A<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup> ",
+            "b<sup class=\"footnote-ref\"><a href=\"#fn-2\" id=\"fnref-2\">2</a></sup></p>",
+            "<section class=\"footnotes\"><ol>",
+            "<li id=\"fn-1\"><p>Regular. ",
+            "<a href=\"#fnref-1\" class=\"footnote-backref\" aria-label=\"Back to reference 1\">↩</a></p></li>",
+            "<li id=\"fn-2\"><p>Inline <em>note</em>. ",
+            "<a href=\"#fnref-2\" class=\"footnote-backref\" aria-label=\"Back to reference 2\">↩</a></p></li>",
+            "</ol></section>",
+        ),
+        result
+    );
+}
+
+#[test]
+fn task_lists() {
+    let input = "- [ ] Todo\n- [x] Done\n    - [ ] Nested\n- Plain";
+    let mut ast =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+            .unwrap();
+    let result =
+        crate::html_printer::render_html(&ast, crate::html_printer::config::Config::default());
+    assert_eq!(
+        concat!(
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" disabled=\"\"> Todo</p></li>",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" disabled=\"\" checked=\"\"> Done</p>",
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" disabled=\"\"> Nested</p></li></ul></li>",
+            "<li><p>Plain</p></li></ul>",
+        ),
+        result
+    );
+
+    *crate::html_printer::task_states_mut(&mut ast)[2] = crate::ast::TaskState::Complete;
+    let config = crate::html_printer::config::Config::default().with_interactive_task_lists(true);
+    let result = crate::html_printer::render_html(&ast, config);
+    assert_eq!(
+        concat!(
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"0\"> Todo</p></li>",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"1\" checked=\"\"> Done</p>",
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"2\" checked=\"\"> Nested</p></li></ul></li>",
+            "<li><p>Plain</p></li></ul>",
+        ),
+        result
+    );
+}
+
+#[test]
+fn task_list_indices() {
+    // Inline footnotes make the printer render a hoisted copy of the document
+    let input = "Intro[^n] and^[inline].\n\n[^n]: - [ ] In footnote\n\n- [ ] First\n- [x] Second";
+    let parser_config = crate::parser::config::MarkdownParserConfig::default()
+        .with_inline_inline_footnote_behavior(crate::parser::config::ElementBehavior::Parse);
+    let mut ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(parser_config),
+        input,
+    )
+    .unwrap();
+    let config = crate::html_printer::config::Config::default().with_interactive_task_lists(true);
+
+    // Body items first, then footnote items
+    *crate::html_printer::task_states_mut(&mut ast)[2] = crate::ast::TaskState::Complete;
+    let result = crate::html_printer::render_html(&ast.clone(), config.clone());
+    assert!(result.contains("<input type=\"checkbox\" data-task-index=\"0\"> First"));
+    assert!(result.contains("<input type=\"checkbox\" data-task-index=\"1\" checked=\"\"> Second"));
+    assert!(
+        result.contains("<input type=\"checkbox\" data-task-index=\"2\" checked=\"\"> In footnote")
+    );
+
+    // Fragments number their own items
+    let index = crate::html_printer::DocumentIndex::new(&ast);
+    let result = crate::html_printer::render_html_blocks(&ast.blocks[2..], config, Some(&index));
+    assert_eq!(
+        concat!(
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"0\"> First</p></li>",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"1\" checked=\"\"> Second</p></li></ul>",
+        ),
+        result
+    );
+}
+
+#[test]
+fn css_classes() {
+    use crate::html_printer::config::{Config, CssClass, TableAlignmentStyle};
+    let input = "* [ ] Task\n\n| A | B | C |\n| :-: | --: | --- |\n| 1 | 2 | 3 |";
+    let ast = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+
+    let config = Config::default()
+        .with_class_prefix("ds-")
+        .with_class_name(CssClass::TaskListItem, Some("todo".to_owned()))
+        .with_class_name(CssClass::ContainsTaskList, None)
+        .with_class_name(CssClass::TableAlignRight, Some("text-end".to_owned()));
+    assert_eq!(
+        concat!(
+            "<ul class=\"ds-list-kind-star\"><li class=\"todo\"><p><input type=\"checkbox\" disabled=\"\"> Task</p></li></ul>",
+            "<table><thead><tr><th class=\"ds-table-align-center\">A</th><th class=\"text-end\">B</th><th class=\"ds-table-align-left\">C</th></tr></thead>",
+            "<tbody><tr><td class=\"ds-table-align-center\">1</td><td class=\"text-end\">2</td><td class=\"ds-table-align-left\">3</td></tr></tbody></table>",
+        ),
+        crate::html_printer::render_html(&ast, config)
+    );
+
+    let config = Config::default()
+        .with_css_classes(false)
+        .with_table_alignment_style(TableAlignmentStyle::Style);
+    assert_eq!(
+        concat!(
+            "<ul><li><p><input type=\"checkbox\" disabled=\"\"> Task</p></li></ul>",
+            "<table><thead><tr><th style=\"text-align: center\">A</th><th style=\"text-align: right\">B</th><th>C</th></tr></thead>",
+            "<tbody><tr><td style=\"text-align: center\">1</td><td style=\"text-align: right\">2</td><td>3</td></tr></tbody></table>",
+        ),
+        crate::html_printer::render_html(&ast, config)
+    );
+
+    let config = Config::default().with_table_alignment_style(TableAlignmentStyle::Attribute);
+    let result = crate::html_printer::render_html(&ast, config);
+    assert!(result.contains("<th align=\"center\">A</th><th align=\"right\">B</th><th>C</th>"));
+}
+
+#[test]
+fn render_hooks() {
+    use crate::ast::{Heading, HeadingKind, Image, Link, Table};
+    use crate::html_printer::config::{Config, RenderContext};
+    let input =
+        "# Title\n\n[ext](https://example.com) [int](/page) ![cat](cat.png)\n\n| A |\n| - |\n| 1 |";
+    let ast = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let config = Config::default()
+        .with_link_renderer(crate::Xrc::new(|link: &Link, ctx: &RenderContext| {
+            link.destination.starts_with("https://").then(|| {
+                format!(
+                    "<a href=\"{}\" rel=\"noopener\">{}</a>",
+                    ctx.escape(&link.destination),
+                    ctx.render_inlines(&link.children)
+                )
+            })
+        }))
+        .with_image_renderer(crate::Xrc::new(|image: &Image, ctx: &RenderContext| {
+            Some(format!(
+                "<img src=\"{}\" alt=\"{}\" loading=\"lazy\">",
+                ctx.escape(&image.destination),
+                ctx.escape(&image.alt)
+            ))
+        }))
+        .with_heading_renderer(crate::Xrc::new(|heading: &Heading, ctx: &RenderContext| {
+            let HeadingKind::Atx(level) = heading.kind else {
+                return None;
+            };
+            Some(format!(
+                "<h{level} id=\"title\">{}</h{level}>",
+                ctx.render_inlines(&heading.content)
+            ))
+        }))
+        .with_table_renderer(crate::Xrc::new(|_: &Table, ctx: &RenderContext| {
+            Some(format!(
+                "<div class=\"scroll\">{}</div>",
+                ctx.render_default()
+            ))
+        }))
+        .with_inline_html_renderer(crate::Xrc::new(|html: &str, _: &RenderContext| {
+            (html == "<kbd>").then(|| "<kbd class=\"key\">".to_owned())
+        }));
+    assert_eq!(
+        concat!(
+            "<h1 id=\"title\">Title</h1>",
+            "<p><a href=\"https://example.com\" rel=\"noopener\">ext</a> <a href=\"/page\">int</a> ",
+            "<img src=\"cat.png\" alt=\"cat\" loading=\"lazy\"></p>",
+            "<div class=\"scroll\"><table><thead><tr><th class=\"markdown-table-align-left\">A</th></tr></thead>",
+            "<tbody><tr><td class=\"markdown-table-align-left\">1</td></tr></tbody></table></div>",
+        ),
+        crate::html_printer::render_html(&ast, config.clone())
+    );
+
+    // Inline HTML is not produced by the parser
+    let ast = crate::ast::Document {
+        blocks: vec![crate::ast::Block::Paragraph(vec![
+            crate::ast::Inline::Html("<kbd>".to_owned()),
+            crate::ast::Inline::Text("K".to_owned()),
+            crate::ast::Inline::Html("</kbd>".to_owned()),
+        ])],
+    };
+    assert_eq!(
+        "<p><kbd class=\"key\">K</kbd></p>",
+        crate::html_printer::render_html(&ast, config)
+    );
+}
+
+#[test]
+fn url_rewriting() {
+    use crate::html_printer::config::Config;
+    use crate::html_printer::url::UrlRewriter;
+    let input = "[Intro](intro.md#setup) [Up](../api/index.md?v=1) [Top](/README.md) [Here](#x)\n\n![Logo](./img/logo.png) <https://github.com/org> [Docs][docs] [Self](https://docs.example.com/a)\n\n[docs]: https://example.org/manual";
+    let ast = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let config = Config::default().with_url_rewriter(
+        UrlRewriter::default()
+            .with_base_url("https://docs.example.com/guide/start.html")
+            .with_markdown_to_html(true)
+            .with_image_prefix("https://cdn.example.com/assets/")
+            .with_external_link_rel("noopener")
+            .with_external_link_target("_blank")
+            .with_internal_host("example.org"),
+    );
+    assert_eq!(
+        concat!(
+            "<p><a href=\"https://docs.example.com/guide/intro.html#setup\">Intro</a> ",
+            "<a href=\"https://docs.example.com/api/index.html?v=1\">Up</a> ",
+            "<a href=\"https://docs.example.com/README.html\">Top</a> ",
+            "<a href=\"#x\">Here</a></p>",
+            "<p><img src=\"https://cdn.example.com/assets/img/logo.png\" alt=\"Logo\"></img> ",
+            "<a href=\"https://github.com/org\" rel=\"noopener\" target=\"_blank\">https://github.com/org</a> ",
+            "<a href=\"https://example.org/manual\">Docs</a> ",
+            "<a href=\"https://docs.example.com/a\">Self</a></p>",
+        ),
+        crate::html_printer::render_html(&ast, config)
+    );
+}
+
+#[test]
+fn figures() {
+    use crate::html_printer::config::Config;
+    let input =
+        "![A cat](cat.png \"Our cat\")\n\n![Plain alt](dog.png)\n\nInline ![x](x.png) image";
+    let ast = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+
+    let config = Config::default().with_figures(true);
+    assert_eq!(
+        concat!(
+            "<figure><img src=\"cat.png\" alt=\"A cat\" title=\"Our cat\"></img><figcaption>Our cat</figcaption></figure>",
+            "<figure><img src=\"dog.png\" alt=\"Plain alt\"></img><figcaption>Plain alt</figcaption></figure>",
+            "<p>Inline <img src=\"x.png\" alt=\"x\"></img> image</p>",
+        ),
+        crate::html_printer::render_html(&ast, config)
+    );
+
+    let config = Config::default()
+        .with_figures(true)
+        .with_figure_label("Figure")
+        .with_lazy_images(true)
+        .with_image_size_resolver(crate::Xrc::new(|image: &crate::ast::Image| {
+            (image.destination == "cat.png").then_some((640, 480))
+        }));
+    assert_eq!(
+        concat!(
+            "<figure id=\"figure-1\"><img src=\"cat.png\" alt=\"A cat\" title=\"Our cat\" width=\"640\" height=\"480\" loading=\"lazy\"></img>",
+            "<figcaption>Figure 1: Our cat</figcaption></figure>",
+            "<figure id=\"figure-2\"><img src=\"dog.png\" alt=\"Plain alt\" loading=\"lazy\"></img>",
+            "<figcaption>Figure 2: Plain alt</figcaption></figure>",
+            "<p>Inline <img src=\"x.png\" alt=\"x\" loading=\"lazy\"></img> image</p>",
+        ),
+        crate::html_printer::render_html(&ast, config)
+    );
+}
+
+/// Writer which accepts `limit` bytes and then fails.
+struct FailingWriter {
+    limit: usize,
+}
+
+impl std::io::Write for FailingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.len() > self.limit {
+            return Err(std::io::Error::other("disk full"));
+        }
+        self.limit -= buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn streaming_output() {
+    use crate::html_printer::config::Config;
+
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::default(),
+        "# Title\n\nText^[note] with **bold**.\n\n- one\n- two",
+    )
+    .unwrap();
+    let expected = crate::html_printer::render_html(&ast, Config::default());
+
+    let mut bytes = Vec::new();
+    crate::html_printer::render_html_to(&mut bytes, &ast, Config::default()).unwrap();
+    assert_eq!(expected, String::from_utf8(bytes).unwrap());
+
+    let mut string = String::from("<!-- prefix -->");
+    crate::html_printer::render_html_to_fmt(&mut string, &ast, Config::default()).unwrap();
+    assert_eq!(format!("<!-- prefix -->{expected}"), string);
+
+    let error = crate::html_printer::render_html_to(
+        &mut FailingWriter { limit: 10 },
+        &ast,
+        Config::default(),
+    )
+    .unwrap_err();
+    assert_eq!("disk full", error.to_string());
+}
+
+#[test]
+fn fragments() {
+    use crate::html_printer::config::Config;
+    use crate::html_printer::{
+        render_html_block, render_html_blocks, render_html_inlines, DocumentIndex,
+    };
+
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::default(),
+        "# Intro to [links]\n\nText[^a] and *more*.\n\n| Cell[^b] |\n|---|\n| x |\n\n[links]: /links\n\n[^b]: Second.\n\n[^a]: First.",
+    )
+    .unwrap();
+    let crate::ast::Block::Heading(heading) = &ast.blocks[0] else {
+        panic!("expected a heading");
+    };
+    let crate::ast::Block::Table(table) = &ast.blocks[2] else {
+        panic!("expected a table");
+    };
+
+    // Without an index, definitions outside the fragment are unknown
+    assert_eq!(
+        "Intro to ",
+        render_html_inlines(&heading.content, Config::default(), None)
+    );
+    assert_eq!(
+        "<p>Text[^a] and <em>more</em>.</p>",
+        render_html_block(&ast.blocks[1], Config::default(), None)
+    );
+
+    let index = DocumentIndex::new(&ast);
+    assert_eq!(
+        "Intro to <a href=\"/links\">links</a>",
+        render_html_inlines(&heading.content, Config::default(), Some(&index))
+    );
+    assert_eq!(
+        "Cell<sup class=\"footnote-ref\"><a href=\"#fn-2\" id=\"fnref-2\">2</a></sup>",
+        render_html_inlines(&table.rows[0][0], Config::default(), Some(&index))
+    );
+    assert_eq!(
+        "<h1>Intro to <a href=\"/links\">links</a></h1><p>Text<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup> and <em>more</em>.</p>",
+        render_html_blocks(&ast.blocks[..2], Config::default(), Some(&index))
+    );
+
+    // Definitions inside the fragment are used without an index
+    assert_eq!(
+        "<p>Text<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup> and <em>more</em>.</p>",
+        render_html_blocks(
+            &[ast.blocks[1].clone(), ast.blocks[5].clone()],
+            Config::default(),
+            None
+        )
+    );
+}
+
+#[test]
+fn fragments_with_inline_footnotes() {
+    use crate::html_printer::config::Config;
+    use crate::html_printer::{render_html_blocks, render_html_inlines, DocumentIndex};
+
+    let parser_config = crate::parser::config::MarkdownParserConfig::default()
+        .with_inline_inline_footnote_behavior(crate::parser::config::ElementBehavior::Parse);
+    let ast = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(parser_config),
+        "Intro[^n].\n\n- [ ] Task^[Note ^[nested]]\n- [x] Done^[Other]\n\n[^n]: Regular.",
+    )
+    .unwrap();
+    let config = Config::default().with_interactive_task_lists(true);
+    let crate::ast::Block::List(list) = &ast.blocks[1] else {
+        panic!("expected a list");
+    };
+    let crate::ast::Block::Paragraph(done) = &list.items[1].blocks[0] else {
+        panic!("expected a paragraph");
+    };
+
+    // Without an index, inline footnotes are numbered within the fragment
+    assert_eq!(
+        concat!(
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"0\"> Task<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup></p></li>",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"1\" checked=\"\"> Done<sup class=\"footnote-ref\"><a href=\"#fn-2\" id=\"fnref-2\">2</a></sup></p></li></ul>",
+        ),
+        render_html_blocks(&ast.blocks[1..2], config.clone(), None)
+    );
+    assert_eq!(
+        "Done<sup class=\"footnote-ref\"><a href=\"#fn-1\" id=\"fnref-1\">1</a></sup>",
+        render_html_inlines(done, config.clone(), None)
+    );
+
+    // With an index, they are numbered as in the whole document
+    let index = DocumentIndex::new(&ast);
+    assert_eq!(
+        concat!(
+            "<ul class=\"markdown-list-kind-dash contains-task-list\">",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"0\"> Task<sup class=\"footnote-ref\"><a href=\"#fn-2\" id=\"fnref-2\">2</a></sup></p></li>",
+            "<li class=\"task-list-item\"><p><input type=\"checkbox\" data-task-index=\"1\" checked=\"\"> Done<sup class=\"footnote-ref\"><a href=\"#fn-3\" id=\"fnref-3\">3</a></sup></p></li></ul>",
+        ),
+        render_html_blocks(&ast.blocks[1..2], config.clone(), Some(&index))
+    );
+    assert_eq!(
+        "Done<sup class=\"footnote-ref\"><a href=\"#fn-3\" id=\"fnref-3\">3</a></sup>",
+        render_html_inlines(done, config, Some(&index))
+    );
+    assert!(crate::html_printer::render_html(&ast, Config::default())
+        .contains("Done<sup class=\"footnote-ref\"><a href=\"#fn-3\" id=\"fnref-3\">3</a></sup>"));
+}
@@ -0,0 +1,232 @@
+//! Rewriting of link and image destinations in HTML output.
+
+/// Rewrites destinations of links, images and autolinks, see
+/// [`Config::with_url_rewriter`](crate::html_printer::config::Config::with_url_rewriter).
+///
+/// ```rust
+/// use markdown_ppp::html_printer::config::Config;
+/// use markdown_ppp::html_printer::url::UrlRewriter;
+///
+/// let config = Config::default().with_url_rewriter(
+///     UrlRewriter::default()
+///         .with_base_url("https://docs.example.com/guide/")
+///         .with_markdown_to_html(true)
+///         .with_image_prefix("https://cdn.example.com/assets/")
+///         .with_external_link_rel("noopener noreferrer")
+///         .with_external_link_target("_blank"),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UrlRewriter {
+    base_url: Option<String>,
+    markdown_to_html: bool,
+    image_prefix: Option<String>,
+    external_link_rel: Option<String>,
+    external_link_target: Option<String>,
+    internal_hosts: Vec<String>,
+}
+
+impl UrlRewriter {
+    /// Resolve relative destinations against the base URL. A base URL without a
+    /// trailing `/` is treated as a document: `a.html` against
+    /// `https://example.com/docs/index.html` gives `https://example.com/docs/a.html`.
+    /// The host of the base URL is considered internal.
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: Some(base_url.into()),
+            ..self
+        }
+    }
+
+    /// Rewrite relative links to `.md` files to `.html`, keeping the query and fragment.
+    pub fn with_markdown_to_html(self, markdown_to_html: bool) -> Self {
+        Self {
+            markdown_to_html,
+            ..self
+        }
+    }
+
+    /// Resolve relative image destinations against this prefix instead of the base URL,
+    /// e.g. a CDN origin.
+    pub fn with_image_prefix(self, image_prefix: impl Into<String>) -> Self {
+        Self {
+            image_prefix: Some(image_prefix.into()),
+            ..self
+        }
+    }
+
+    /// Set the `rel` attribute of links to external hosts, e.g. `noopener`.
+    pub fn with_external_link_rel(self, rel: impl Into<String>) -> Self {
+        Self {
+            external_link_rel: Some(rel.into()),
+            ..self
+        }
+    }
+
+    /// Set the `target` attribute of links to external hosts, e.g. `_blank`.
+    pub fn with_external_link_target(self, target: impl Into<String>) -> Self {
+        Self {
+            external_link_target: Some(target.into()),
+            ..self
+        }
+    }
+
+    /// Add a host which is not considered external, besides the host of the base URL.
+    pub fn with_internal_host(mut self, host: impl Into<String>) -> Self {
+        self.internal_hosts.push(host.into());
+        self
+    }
+
+    /// Rewrite a link destination.
+    pub fn rewrite_link(&self, destination: &str) -> String {
+        if is_absolute(destination) || destination.starts_with('#') {
+            return destination.to_owned();
+        }
+        let destination = if self.markdown_to_html {
+            markdown_to_html(destination)
+        } else {
+            destination.to_owned()
+        };
+        match &self.base_url {
+            Some(base) => join(base, &destination),
+            None => destination,
+        }
+    }
+
+    /// Rewrite an image destination.
+    pub fn rewrite_image(&self, destination: &str) -> String {
+        if is_absolute(destination) {
+            return destination.to_owned();
+        }
+        match (&self.image_prefix, &self.base_url) {
+            (Some(prefix), _) => join_prefix(prefix, destination),
+            (None, Some(base)) => join(base, destination),
+            (None, None) => destination.to_owned(),
+        }
+    }
+
+    /// `rel` and `target` attributes for a link with the given (rewritten) destination.
+    pub fn link_attributes(&self, destination: &str) -> Vec<(String, String)> {
+        if !self.is_external(destination) {
+            return Vec::new();
+        }
+        let mut attributes = Vec::new();
+        if let Some(rel) = &self.external_link_rel {
+            attributes.push(("rel".to_owned(), rel.clone()));
+        }
+        if let Some(target) = &self.external_link_target {
+            attributes.push(("target".to_owned(), target.clone()));
+        }
+        attributes
+    }
+
+    fn is_external(&self, destination: &str) -> bool {
+        let Some(host) = host(destination) else {
+            return false;
+        };
+        let base_host = self.base_url.as_deref().and_then(self::host);
+        base_host != Some(host) && !self.internal_hosts.iter().any(|v| v == host)
+    }
+}
+
+/// Has a scheme (`https:`, `mailto:`) or is protocol-relative (`//host`).
+fn is_absolute(url: &str) -> bool {
+    if url.starts_with("//") {
+        return true;
+    }
+    match url.split_once(':') {
+        Some((scheme, _)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// Host of an `http(s)://` or protocol-relative URL.
+fn host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("//"))?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or_default();
+    Some(host.split(':').next().unwrap_or_default()).filter(|v| !v.is_empty())
+}
+
+fn markdown_to_html(destination: &str) -> String {
+    let split = destination.find(['?', '#']).unwrap_or(destination.len());
+    let (path, suffix) = destination.split_at(split);
+    match path.strip_suffix(".md") {
+        Some(stem) if !stem.is_empty() && !stem.ends_with('/') => format!("{stem}.html{suffix}"),
+        _ => destination.to_owned(),
+    }
+}
+
+/// Resolve a relative reference against a base URL.
+fn join(base: &str, reference: &str) -> String {
+    // Origin is `scheme://host`, or empty for a path-only base
+    let origin_end = match base.find("://") {
+        Some(i) => base[i + 3..]
+            .find('/')
+            .map(|j| i + 3 + j)
+            .unwrap_or(base.len()),
+        None => 0,
+    };
+    let (origin, base_path) = base.split_at(origin_end);
+    let base_path = base_path.split(['?', '#']).next().unwrap_or_default();
+
+    let path = if reference.starts_with('/') {
+        reference.to_owned()
+    } else if reference.starts_with('?') {
+        format!("{base_path}{reference}")
+    } else {
+        let directory = match base_path.rfind('/') {
+            Some(i) => &base_path[..=i],
+            None => "/",
+        };
+        format!("{directory}{reference}")
+    };
+
+    format!("{origin}{}", normalize(&path))
+}
+
+/// Join a prefix and a relative path with exactly one `/`.
+fn join_prefix(prefix: &str, path: &str) -> String {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    format!(
+        "{}/{}",
+        prefix.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    )
+}
+
+/// Remove `.` and `..` segments from the path, keeping the query and fragment.
+fn normalize(path: &str) -> String {
+    let split = path.find(['?', '#']).unwrap_or(path.len());
+    let (path, suffix) = path.split_at(split);
+    let mut segments: Vec<&str> = Vec::new();
+    let parts: Vec<&str> = path.split('/').collect();
+    for (i, segment) in parts.iter().enumerate() {
+        let last = i == parts.len() - 1;
+        match *segment {
+            "." => {
+                if last {
+                    segments.push("");
+                }
+            }
+            ".." => {
+                if segments.len() > 1 {
+                    segments.pop();
+                }
+                if last {
+                    segments.push("");
+                }
+            }
+            segment => segments.push(segment),
+        }
+    }
+    format!("{}{suffix}", segments.join("/"))
+}
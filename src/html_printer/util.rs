@@ -1,3 +1,4 @@
+use crate::html_printer::config::{CssClass, RenderContext, RenderHookFn};
 use pretty::{Arena, DocAllocator, DocBuilder};
 
 pub(crate) fn escape(value: &str) -> String {
@@ -20,6 +21,22 @@ pub(crate) fn tag<'a>(
     tag: &'static str,
     attributes: Vec<(String, String)>,
     inner: DocBuilder<'a, Arena<'a>, ()>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let close_tag = state
+        .arena
+        .text("</")
+        .append(state.arena.text(tag))
+        .append(state.arena.text(">"));
+    void_tag(state, tag, attributes)
+        .append(inner)
+        .append(close_tag)
+}
+
+/// Tag without content and closing tag, e.g. `<input>`.
+pub(crate) fn void_tag<'a>(
+    state: &'a crate::html_printer::State<'a>,
+    tag: &'static str,
+    attributes: Vec<(String, String)>,
 ) -> DocBuilder<'a, Arena<'a>, ()> {
     let mut attrs = state.arena.nil();
     for (key, value) in attributes {
@@ -32,16 +49,55 @@ pub(crate) fn tag<'a>(
             .append(state.arena.text("\""));
         attrs = attrs.append(attr);
     }
-    let open_tag = state
+    state
         .arena
         .text("<")
         .append(state.arena.text(tag))
         .append(attrs)
-        .append(state.arena.text(">"));
-    let close_tag = state
-        .arena
-        .text("</")
-        .append(state.arena.text(tag))
-        .append(state.arena.text(">"));
-    open_tag.append(inner).append(close_tag)
+        .append(state.arena.text(">"))
+}
+
+/// `class` attribute with the configured names of the given classes, if any.
+pub(crate) fn class_attribute(
+    state: &crate::html_printer::State<'_>,
+    classes: &[CssClass],
+) -> Option<(String, String)> {
+    let names: Vec<String> = classes
+        .iter()
+        .filter_map(|class| state.config.class_name(*class))
+        .collect();
+    if names.is_empty() {
+        None
+    } else {
+        Some(("class".to_owned(), names.join(" ")))
+    }
+}
+
+pub(crate) fn render_to_string<'a>(
+    state: &'a crate::html_printer::State<'a>,
+    doc: DocBuilder<'a, Arena<'a>, ()>,
+) -> String {
+    let mut buf = Vec::new();
+    doc.render(state.config.width, &mut buf).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Render the node with the configured hook, falling back to the default rendering.
+pub(crate) fn with_render_hook<'a, T: ?Sized>(
+    state: &'a crate::html_printer::State<'a>,
+    hook: &Option<RenderHookFn<T>>,
+    node: &T,
+    default: impl Fn() -> DocBuilder<'a, Arena<'a>, ()>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    if let Some(hook) = hook {
+        let render_default = || render_to_string(state, default());
+        let context = RenderContext {
+            state,
+            default: &render_default,
+        };
+        if let Some(html) = hook(node, &context) {
+            return state.arena.text(html);
+        }
+    }
+    default()
 }
@@ -0,0 +1,331 @@
+//! Rendering of the AST to Jira wiki markup.
+//!
+//! Code blocks become `{code}` macros, GitHub alerts (see
+//! [`alerts`](crate::ast::alerts)) become `{info}`, `{tip}`, `{note}` and `{warning}`
+//! panels and tables use header cells for the first row. Wiki markup has no ordered
+//! list start numbers, column alignment or footnotes; footnotes are numbered with
+//! superscripts. Lines are never wrapped, as a line break ends a paragraph line.
+//!
+//! ```rust
+//! use markdown_ppp::jira_printer::render_jira;
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "## Setup\n\nRun **`make`** first:\n\n- build\n    1. debug",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     "h2. Setup\n\nRun *{{make}}* first:\n\n* build\n*# debug",
+//!     render_jira(&doc)
+//! );
+//! ```
+
+mod tests;
+
+use crate::ast::alerts::{alert, AlertKind};
+use crate::ast::*;
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+use std::cell::{Cell, RefCell};
+
+/// Render the given Markdown AST to Jira wiki markup.
+pub fn render_jira(ast: &Document) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = JiraRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+        bold: Cell::new(false),
+        italic: Cell::new(false),
+        strike: Cell::new(false),
+        quoted: Cell::new(false),
+        list_markers: RefCell::new(String::new()),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, usize::MAX)
+}
+
+/// Escape a word for Jira wiki markup. Brackets, braces, `|`, `!` and `\` are always
+/// escaped, text effect markers only at the start or end of the word, where they
+/// could open or close an effect.
+pub fn escape(word: &str) -> String {
+    let count = word.chars().count();
+    let mut result = String::with_capacity(word.len());
+    for (i, c) in word.chars().enumerate() {
+        let escape = match c {
+            '[' | ']' | '{' | '}' | '|' | '!' | '\\' => true,
+            '*' | '_' | '-' | '+' | '^' | '~' | '?' | '#' => i == 0 || i + 1 == count,
+            _ => false,
+        };
+        if escape {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Link destination with the characters ending it percent-encoded.
+fn destination(url: &str) -> String {
+    url.replace('|', "%7C").replace(']', "%5D")
+}
+
+struct JiraRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+    // Text effects being rendered, an effect nested in itself would end it.
+    bold: Cell<bool>,
+    italic: Cell<bool>,
+    strike: Cell<bool>,
+    // Whether a block quote is being rendered, as quotes cannot be nested.
+    quoted: Cell<bool>,
+    // Markers of the enclosing lists, `*` for bullet lists and `#` for ordered lists.
+    list_markers: RefCell<String>,
+}
+
+impl<'a> JiraRenderer<'a> {
+    /// Content between `marker`s, unless already rendered in the effect.
+    fn styled(&self, style: &Cell<bool>, marker: &'static str, content: &[Inline]) -> Doc<'a> {
+        if style.replace(true) {
+            return self.render_inlines(content);
+        }
+        let content = self.render_inlines(content);
+        style.set(false);
+        self.arena
+            .text(marker)
+            .append(content)
+            .append(self.arena.text(marker))
+    }
+
+    /// `{name}` macro around content.
+    fn panel(&self, name: &str, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text(format!("{{{name}}}"))
+            .append(self.arena.hardline())
+            .append(content)
+            .append(self.arena.hardline())
+            .append(
+                self.arena
+                    .text(format!("{{{}}}", name.split(':').next().unwrap_or(name))),
+            )
+    }
+
+    fn link(&self, url: &str, text: Option<Doc<'a>>) -> Doc<'a> {
+        let url = destination(url);
+        match text {
+            Some(text) => self
+                .arena
+                .text("[")
+                .append(text)
+                .append(self.arena.text(format!("|{url}]"))),
+            None => self.arena.text(format!("[{url}]")),
+        }
+    }
+}
+
+impl<'a> Renderer<'a> for JiraRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// Blocks in list items are separated by line breaks only, so the list continues.
+    fn block_separator(&self) -> Doc<'a> {
+        match self.list_markers.borrow().is_empty() {
+            true => self.arena.hardline().append(self.arena.hardline()),
+            false => self.arena.hardline(),
+        }
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        self.arena
+            .text(format!("h{level}. "))
+            .append(self.render_inlines(&heading.content))
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("----")
+    }
+
+    /// GitHub alerts are rendered as panels, nested quotes are merged into the outer
+    /// one.
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        if let Some((kind, content)) = alert(blocks) {
+            let name = match kind {
+                AlertKind::Note | AlertKind::Important => "info",
+                AlertKind::Tip => "tip",
+                AlertKind::Warning => "note",
+                AlertKind::Caution => "warning",
+            };
+            return self.panel(name, self.render_blocks(&content));
+        }
+        if self.quoted.replace(true) {
+            return self.render_blocks(blocks);
+        }
+        let content = self.render_blocks(blocks);
+        self.quoted.set(false);
+        self.panel("quote", content)
+    }
+
+    fn render_list(&self, list: &List) -> Doc<'a> {
+        let marker = match list.kind {
+            ListKind::Ordered(_) => '#',
+            ListKind::Bullet(_) => '*',
+        };
+        self.list_markers.borrow_mut().push(marker);
+        let items = list
+            .items
+            .iter()
+            .map(|item| self.render_list_item(list, None, item))
+            .collect::<Vec<_>>();
+        self.list_markers.borrow_mut().pop();
+        self.arena.intersperse(items, self.arena.hardline())
+    }
+
+    /// Items are marked with the markers of all enclosing lists, so nested lists
+    /// follow their item without indentation.
+    fn render_list_item(&self, _list: &List, _number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let task = match item.task {
+            Some(TaskState::Complete) => "\u{2611} ",
+            Some(TaskState::Incomplete) => "\u{2610} ",
+            None => "",
+        };
+        self.arena
+            .text(format!("{} {task}", self.list_markers.borrow()))
+            .append(self.render_blocks(&item.blocks))
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let language = match &code_block.kind {
+            CodeBlockKind::Fenced { info: Some(info) } => info.split_whitespace().next(),
+            _ => None,
+        };
+        let name = match language {
+            Some(language) => format!("code:{language}"),
+            None => "noformat".to_owned(),
+        };
+        self.panel(
+            &name,
+            self.arena.intersperse(
+                literal
+                    .unwrap_or(&code_block.literal)
+                    .split('\n')
+                    .map(|line| self.arena.text(line.to_owned())),
+                self.arena.hardline(),
+            ),
+        )
+    }
+
+    /// The first row is rendered with header cells, alignment is not supported.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let rows = table.rows.iter().enumerate().map(|(i, row)| {
+            let separator = if i == 0 { "||" } else { "|" };
+            let cells = row.iter().map(|cell| match cell.is_empty() {
+                true => self.arena.text(" "),
+                false => self.render_inlines(cell),
+            });
+            self.arena
+                .text(separator)
+                .append(self.arena.intersperse(cells, self.arena.text(separator)))
+                .append(self.arena.text(separator))
+        });
+        self.arena.intersperse(rows, self.arena.hardline())
+    }
+
+    /// Render a referenced footnote as `^n^ content`.
+    fn render_footnote(&self, footnote: &FootnoteDefinition) -> Doc<'a> {
+        let number = self
+            .index
+            .footnote_index(&footnote.label)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| escape(&footnote.label));
+        self.arena
+            .text(format!("^{number}^ "))
+            .append(self.render_blocks(&footnote.blocks))
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            self.arena.softline(),
+        )
+    }
+
+    /// `\\` breaks the line without ending a list item or table cell.
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena.text("\\\\ ")
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        let code = code.split(' ').map(escape).collect::<Vec<_>>().join(" ");
+        self.arena.text(format!("{{{{{code}}}}}"))
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let same_text = matches!(
+            link.children.as_slice(),
+            [Inline::Text(text)] if *text == link.destination
+        );
+        let text = match same_text || link.children.is_empty() {
+            true => None,
+            false => Some(self.render_inlines(&link.children)),
+        };
+        self.link(&link.destination, text)
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        // Image attributes are separated by `,` and end at `!`
+        let alt = image
+            .alt
+            .split(['!', '|', ','])
+            .flat_map(str::split_whitespace)
+            .collect::<Vec<_>>()
+            .join(" ");
+        match alt.as_str() {
+            "" => self.arena.text(format!("!{}!", image.destination)),
+            alt => self
+                .arena
+                .text(format!("!{}|alt={alt}!", image.destination)),
+        }
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.italic, "_", children)
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.bold, "*", children)
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.strike, "-", children)
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        if link.starts_with("www.") {
+            self.link(&format!("http://{link}"), Some(self.render_text(link)))
+        } else if !link.contains(':') && link.contains('@') {
+            self.link(&format!("mailto:{link}"), Some(self.render_text(link)))
+        } else {
+            self.link(link, None)
+        }
+    }
+
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        match self.index.footnote_index(label) {
+            Some(n) => self.arena.text(format!("^{n}^")),
+            None => self.render_text(&format!("[^{label}]")),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+#![cfg(test)]
+use crate::jira_printer::render_jira;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_jira(&doc)
+}
+
+#[rstest]
+#[case("# Title", "h1. Title")]
+#[case("*em* **strong _both_** ~~gone~~", "_em_ *strong _both_* -gone-")]
+#[case("a]b {c} x|y -d e-f *g", "a\\]b \\{c\\} x\\|y \\-d e-f \\*g")]
+#[case("`a {b}`", "{{a \\{b\\}}}")]
+#[case("one  \ntwo", "one\\\\ two")]
+#[case(
+    "[docs](https://example.com/a|b) [x](x)",
+    "[docs|https://example.com/a%7Cb] [x]"
+)]
+#[case(
+    "<https://example.com> <user@example.com>",
+    "[https://example.com] [user@example.com|mailto:user@example.com]"
+)]
+#[case("![A, B](a.png)", "!a.png|alt=A B!")]
+#[case("---", "----")]
+#[case("> quoted\n>\n> > nested", "{quote}\nquoted\n\nnested\n{quote}")]
+#[case("> [!WARNING]\n> Careful.", "{note}\nCareful.\n{note}")]
+#[case("> [!NOTE] Title\n> body", "{quote}\n\\!NOTE Title body\n{quote}")]
+#[case("```rust\nfn a() {}\n```", "{code:rust}\nfn a() {}\n{code}")]
+#[case("```\n[x]\n```", "{noformat}\n[x]\n{noformat}")]
+#[case("Text[^n]\n\n[^n]: Note.", "Text^1^\n\n^1^ Note.")]
+fn jira(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input));
+}
+
+#[test]
+fn lists() {
+    let input = "- a\n    1. b\n    2. c\n        - d\n- [x] done\n- [ ] todo";
+    assert_eq!("* a\n*# b\n*# c\n*#* d\n* ☑ done\n* ☐ todo", render(input));
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        "||Name||Size||\n|{{a}}\\|b|1|\n| |2|",
+        render("| Name | Size |\n|------|-----:|\n| `a`\\|b | 1 |\n| | 2 |")
+    );
+}
@@ -0,0 +1,56 @@
+/// How code blocks are typeset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CodeBlockStyle {
+    /// `verbatim` environment.
+    #[default]
+    Verbatim,
+    /// `lstlisting` environment of the `listings` package, with the language of
+    /// fenced code blocks as `[language=...]`.
+    Listings,
+}
+
+pub struct Config {
+    pub(crate) width: usize,
+    pub(crate) standalone: bool,
+    pub(crate) document_class: String,
+    pub(crate) code_block_style: CodeBlockStyle,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            standalone: false,
+            document_class: "article".to_owned(),
+            code_block_style: CodeBlockStyle::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn with_width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Render a complete document with `\documentclass`, the required packages and
+    /// a `document` environment. By default only the body is rendered, to be
+    /// `\input` into another document.
+    pub fn with_standalone(self, standalone: bool) -> Self {
+        Self { standalone, ..self }
+    }
+
+    /// Document class of standalone documents, `article` by default.
+    pub fn with_document_class(self, document_class: impl Into<String>) -> Self {
+        Self {
+            document_class: document_class.into(),
+            ..self
+        }
+    }
+
+    pub fn with_code_block_style(self, code_block_style: CodeBlockStyle) -> Self {
+        Self {
+            code_block_style,
+            ..self
+        }
+    }
+}
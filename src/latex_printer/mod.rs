@@ -0,0 +1,422 @@
+//! Rendering of the AST to LaTeX.
+//!
+//! Fragments use commands of the `hyperref` (links), `graphicx` (images), `ulem`
+//! (strikethrough), with [`CodeBlockStyle::Listings`](config::CodeBlockStyle)
+//! `listings` and, for code containing the end of its environment, `fancyvrb`
+//! packages, which standalone documents load automatically.
+//!
+//! ```rust
+//! use markdown_ppp::latex_printer::{config::Config, render_latex};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(MarkdownParserState::default(), "# Costs\n\n100% *net*").unwrap();
+//! assert_eq!(
+//!     "\\section{Costs}\n\n100\\% \\emph{net}",
+//!     render_latex(&doc, Config::default())
+//! );
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::ast::*;
+use crate::latex_printer::config::{CodeBlockStyle, Config};
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Render the given Markdown AST to LaTeX.
+pub fn render_latex(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = LatexRenderer {
+        arena: &arena,
+        config: &config,
+        index: Index::new(&ast),
+        footnote_references_rendered: RefCell::new(HashMap::new()),
+        open_footnotes: RefCell::new(Vec::new()),
+        enumerate_depth: Cell::new(0),
+        fancy_verbatim: Cell::new(false),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+/// Escape text for use in LaTeX content. Dashes are kept from forming the `--` and
+/// `---` ligatures.
+pub fn escape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '-' if chars.peek() == Some(&'-') => result.push_str("-{}"),
+            '\\' => result.push_str("\\textbackslash{}"),
+            '~' => result.push_str("\\textasciitilde{}"),
+            '^' => result.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Escape a URL for `\href` and `\url`, which take most characters literally.
+fn escape_url(url: &str) -> String {
+    let mut result = String::with_capacity(url.len());
+    for c in url.chars() {
+        if matches!(c, '\\' | '#' | '%' | '&' | '{' | '}') {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Escape the command characters `\`, `{` and `}` of a line of a `Verbatim`
+/// environment with `commandchars=\\\{\}`.
+fn escape_verbatim(line: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    for c in line.chars() {
+        match c {
+            '\\' => result.push_str("\\textbackslash{}"),
+            '{' | '}' => {
+                result.push('\\');
+                result.push(c);
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+struct LatexRenderer<'a> {
+    arena: &'a Arena<'a>,
+    config: &'a Config,
+    index: Index,
+    // Number of already rendered references to each footnote.
+    footnote_references_rendered: RefCell<HashMap<String, usize>>,
+    // Footnotes being rendered, to stop on footnotes referencing themselves.
+    open_footnotes: RefCell<Vec<String>>,
+    enumerate_depth: Cell<usize>,
+    // Whether a `Verbatim` environment of the `fancyvrb` package was rendered.
+    fancy_verbatim: Cell<bool>,
+}
+
+/// Whether the text contains `\end{name}`, possibly with spaces before the brace,
+/// which would end a verbatim environment early.
+fn contains_end(text: &str, name: &str) -> bool {
+    text.match_indices("\\end").any(|(i, end)| {
+        text[i + end.len()..]
+            .trim_start_matches(' ')
+            .starts_with(&format!("{{{name}}}"))
+    })
+}
+
+impl<'a> LatexRenderer<'a> {
+    /// `\command{content}`
+    fn command(&self, command: &str, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text(format!("\\{command}{{"))
+            .append(content)
+            .append(self.arena.text("}"))
+    }
+
+    /// `\begin{name}` … `\end{name}` on separate lines.
+    fn environment(&self, name: &str, options: &str, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text(format!("\\begin{{{name}}}{options}"))
+            .append(self.arena.hardline())
+            .append(content)
+            .append(self.arena.hardline())
+            .append(self.arena.text(format!("\\end{{{name}}}")))
+    }
+
+    fn preamble(&self) -> Doc<'a> {
+        let mut packages = vec![
+            "\\usepackage[utf8]{inputenc}",
+            "\\usepackage[T1]{fontenc}",
+            "\\usepackage{graphicx}",
+            "\\usepackage[normalem]{ulem}",
+        ];
+        if self.config.code_block_style == CodeBlockStyle::Listings {
+            packages.push("\\usepackage{listings}");
+        }
+        if self.fancy_verbatim.get() {
+            packages.push("\\usepackage{fancyvrb}");
+        }
+        // hyperref should be loaded last
+        packages.push("\\usepackage{hyperref}");
+
+        self.arena
+            .text(format!("\\documentclass{{{}}}", self.config.document_class))
+            .append(self.arena.hardline())
+            .append(self.arena.intersperse(packages, self.arena.hardline()))
+            .append(self.arena.hardline())
+            .append(self.arena.hardline())
+    }
+}
+
+impl<'a> Renderer<'a> for LatexRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// Footnotes are rendered at their references, so only the body is rendered. The
+    /// body is rendered before the preamble, which loads the packages it uses.
+    fn render_document(&self, document: &Document) -> Doc<'a> {
+        let body = self.render_blocks(&document.blocks);
+        if !self.config.standalone {
+            return body;
+        }
+        self.preamble()
+            .append(self.environment("document", "", body))
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let command = match heading.kind {
+            HeadingKind::Atx(1) | HeadingKind::Setext(SetextHeading::Level1) => "section",
+            HeadingKind::Atx(2) | HeadingKind::Setext(SetextHeading::Level2) => "subsection",
+            HeadingKind::Atx(3) => "subsubsection",
+            HeadingKind::Atx(4) => "paragraph",
+            HeadingKind::Atx(_) => "subparagraph",
+        };
+        self.command(command, self.render_inlines(&heading.content))
+            .group()
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("\\noindent\\rule{\\linewidth}{0.4pt}")
+    }
+
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        self.environment("quote", "", self.render_blocks(blocks))
+    }
+
+    fn render_list(&self, list: &List) -> Doc<'a> {
+        let items = || {
+            self.arena.intersperse(
+                list.items
+                    .iter()
+                    .map(|item| self.render_list_item(list, None, item)),
+                self.arena.hardline(),
+            )
+        };
+        match &list.kind {
+            ListKind::Bullet(_) => self.environment("itemize", "", items()),
+            ListKind::Ordered(options) => {
+                let depth = self.enumerate_depth.get();
+                self.enumerate_depth.set(depth + 1);
+                let items = items();
+                self.enumerate_depth.set(depth);
+
+                // Counters of the four enumerate levels
+                let counter = ["enumi", "enumii", "enumiii", "enumiv"].get(depth);
+                let items = match counter {
+                    Some(counter) if options.start != 1 => self
+                        .arena
+                        .text(format!(
+                            "\\setcounter{{{counter}}}{{{}}}",
+                            options.start.saturating_sub(1)
+                        ))
+                        .append(self.arena.hardline())
+                        .append(items),
+                    _ => items,
+                };
+                self.environment("enumerate", "", items)
+            }
+        }
+    }
+
+    fn render_list_item(&self, _list: &List, _number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let marker = match item.task {
+            Some(TaskState::Complete) => "\\item[{[x]}] ",
+            Some(TaskState::Incomplete) => "\\item[{[ ]}] ",
+            None => "\\item ",
+        };
+        self.arena
+            .text(marker)
+            .append(self.render_blocks(&item.blocks))
+    }
+
+    /// Code is written as it is, indenting it would change the output.
+    /// Code containing the end of its environment is rendered as a `Verbatim`
+    /// environment of the `fancyvrb` package with escaped command characters.
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let literal = literal.unwrap_or(&code_block.literal);
+        let environment = match self.config.code_block_style {
+            CodeBlockStyle::Verbatim => "verbatim",
+            CodeBlockStyle::Listings => "lstlisting",
+        };
+        let escaped = contains_end(literal, environment);
+        let lines = self.arena.intersperse(
+            literal.split('\n').map(|line| match escaped {
+                true => self.arena.text(escape_verbatim(line)),
+                false => self.arena.text(line.to_owned()),
+            }),
+            self.arena.hardline(),
+        );
+        if escaped {
+            self.fancy_verbatim.set(true);
+            return self.environment("Verbatim", "[commandchars=\\\\\\{\\}]", lines);
+        }
+        match self.config.code_block_style {
+            CodeBlockStyle::Verbatim => self.environment("verbatim", "", lines),
+            CodeBlockStyle::Listings => {
+                let language = match &code_block.kind {
+                    CodeBlockKind::Fenced { info: Some(info) } => {
+                        info.split_whitespace().next().unwrap_or_default()
+                    }
+                    _ => "",
+                };
+                let options = match language {
+                    "" => String::new(),
+                    language => format!("[language={language}]"),
+                };
+                self.environment("lstlisting", &options, lines)
+            }
+        }
+    }
+
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let columns = table.rows.first().map(|row| row.len()).unwrap_or_default();
+        let spec: String = (0..columns)
+            .map(
+                |i| match table.alignments.get(i).copied().unwrap_or_default() {
+                    Alignment::Center => 'c',
+                    Alignment::Right => 'r',
+                    Alignment::Left | Alignment::None => 'l',
+                },
+            )
+            .collect();
+        let row_to_doc = |row: &TableRow| {
+            self.arena
+                .intersperse(
+                    row.iter().map(|cell| self.render_inlines(cell).group()),
+                    self.arena.text(" & "),
+                )
+                .append(self.arena.text(" \\\\"))
+        };
+        let hline = || self.arena.text("\\hline");
+
+        let mut content = hline();
+        if let Some((header, rows)) = table.rows.split_first() {
+            content = content
+                .append(self.arena.hardline())
+                .append(row_to_doc(header))
+                .append(self.arena.hardline())
+                .append(hline());
+            for row in rows {
+                content = content
+                    .append(self.arena.hardline())
+                    .append(row_to_doc(row));
+            }
+            if !rows.is_empty() {
+                content = content.append(self.arena.hardline()).append(hline());
+            }
+        }
+        self.environment("tabular", &format!("{{{spec}}}"), content)
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            self.arena.softline(),
+        )
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena.text("\\\\").append(self.arena.hardline())
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        self.command("texttt", self.arena.text(escape(code)))
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        self.arena
+            .text(format!("\\href{{{}}}{{", escape_url(&link.destination)))
+            .append(self.render_inlines(&link.children))
+            .append(self.arena.text("}"))
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        self.arena.text(format!(
+            "\\includegraphics{{{}}}",
+            escape_url(&image.destination)
+        ))
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.command("emph", self.render_inlines(children))
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.command("textbf", self.render_inlines(children))
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.command("sout", self.render_inlines(children))
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        let destination = if link.starts_with("www.") {
+            format!("http://{link}")
+        } else if !link.contains(':') && link.contains('@') {
+            format!("mailto:{link}")
+        } else {
+            link.to_owned()
+        };
+        if destination == link {
+            self.arena.text(format!("\\url{{{}}}", escape_url(link)))
+        } else {
+            self.arena.text(format!(
+                "\\href{{{}}}{{{}}}",
+                escape_url(&destination),
+                escape(link)
+            ))
+        }
+    }
+
+    /// The first reference to a footnote typesets its content with `\footnote`,
+    /// further references repeat its number with `\footnotemark`.
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        let Some(n) = self.index.footnote_index(label) else {
+            return self.render_text(&format!("[^{label}]"));
+        };
+        let first = {
+            let mut rendered = self.footnote_references_rendered.borrow_mut();
+            let counter = rendered.entry(label.to_owned()).or_default();
+            *counter += 1;
+            *counter == 1
+        };
+        let open = self.open_footnotes.borrow().iter().any(|v| v == label);
+        let definition = self
+            .index
+            .footnotes()
+            .iter()
+            .find(|footnote| footnote.label == label);
+        match definition {
+            Some(definition) if first && !open => {
+                self.open_footnotes.borrow_mut().push(label.to_owned());
+                let content = self.render_blocks(&definition.blocks);
+                self.open_footnotes.borrow_mut().pop();
+                self.command("footnote", content)
+            }
+            _ => self.arena.text(format!("\\footnotemark[{n}]")),
+        }
+    }
+
+    fn render_inline_footnote(&self, content: &[Inline]) -> Doc<'a> {
+        self.command("footnote", self.render_inlines(content))
+    }
+}
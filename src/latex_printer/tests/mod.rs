@@ -0,0 +1,194 @@
+#![cfg(test)]
+use crate::latex_printer::config::{CodeBlockStyle, Config};
+use crate::latex_printer::render_latex;
+use rstest::rstest;
+
+fn render(input: &str, config: Config) -> String {
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap();
+    render_latex(&doc, config)
+}
+
+#[rstest]
+#[case("# One", "\\section{One}")]
+#[case("## Two", "\\subsection{Two}")]
+#[case("### Three", "\\subsubsection{Three}")]
+#[case("#### Four", "\\paragraph{Four}")]
+#[case("###### Six", "\\subparagraph{Six}")]
+#[case("Setext\n---", "\\subsection{Setext}")]
+#[case(
+    "*em* **strong** ~~gone~~ `a_b`",
+    "\\emph{em} \\textbf{strong} \\sout{gone} \\texttt{a\\_b}"
+)]
+#[case(
+    "5$ & 10% #1_a {x} ~ ^ a\\b",
+    "5\\$ \\& 10\\% \\#1\\_a \\{x\\} \\textasciitilde{} \\textasciicircum{} a\\textbackslash{}b"
+)]
+#[case(
+    "[docs](https://example.com/a#b%20c) and ![logo](img/logo.png)",
+    "\\href{https://example.com/a\\#b\\%20c}{docs} and \\includegraphics{img/logo.png}"
+)]
+#[case(
+    "| [q](https://example.com/?a=1&b=2) |\n|---|",
+    "\\begin{tabular}{l}\n\\hline\n\\href{https://example.com/?a=1\\&b=2}{q} \\\\\n\\hline\n\\end{tabular}"
+)]
+#[case(
+    "<https://example.com> www.example.com",
+    "\\url{https://example.com} \\href{http://www.example.com}{www.example.com}"
+)]
+#[case("a -- b --- c - d", "a -{}- b -{}-{}- c - d")]
+#[case("line  \nbreak", "line\\\\\nbreak")]
+#[case("> quoted", "\\begin{quote}\nquoted\n\\end{quote}")]
+#[case("***", "\\noindent\\rule{\\linewidth}{0.4pt}")]
+fn latex(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input, Config::default()));
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        concat!(
+            "\\begin{itemize}\n",
+            "\\item one\n",
+            "\\item two\n",
+            "\\begin{enumerate}\n",
+            "\\setcounter{enumi}{2}\n",
+            "\\item three\n",
+            "\\end{enumerate}\n",
+            "\\end{itemize}\n",
+            "\n",
+            "\\begin{enumerate}\n",
+            "\\item[{[x]}] done\n",
+            "\\item[{[ ]}] todo\n",
+            "\\end{enumerate}",
+        ),
+        render(
+            "- one\n- two\n\n    3. three\n\n<!-- -->\n\n1. [x] done\n2. [ ] todo",
+            Config::default()
+        )
+        .replace(
+            "\n\n\\begin{enumerate}\n\\setcounter",
+            "\n\\begin{enumerate}\n\\setcounter"
+        )
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        concat!(
+            "\\begin{tabular}{lcr}\n",
+            "\\hline\n",
+            "Name & Kind & Size \\\\\n",
+            "\\hline\n",
+            "\\texttt{a.rs} & file & 10\\% \\\\\n",
+            "b & dir & 0 \\\\\n",
+            "\\hline\n",
+            "\\end{tabular}",
+        ),
+        render(
+            "| Name | Kind | Size |\n|:--|:-:|--:|\n| `a.rs` | file | 10% |\n| b | dir | 0 |",
+            Config::default()
+        )
+    );
+}
+
+#[test]
+fn code_blocks() {
+    let input = "```rust\nfn main() {\n    let _a = \"%\";\n}\n```";
+    assert_eq!(
+        "\\begin{verbatim}\nfn main() {\n    let _a = \"%\";\n}\n\\end{verbatim}",
+        render(input, Config::default())
+    );
+    assert_eq!(
+        "\\begin{lstlisting}[language=rust]\nfn main() {\n    let _a = \"%\";\n}\n\\end{lstlisting}",
+        render(
+            input,
+            Config::default().with_code_block_style(CodeBlockStyle::Listings)
+        )
+    );
+}
+
+/// Code containing the end of its environment falls back to `Verbatim`.
+#[test]
+fn code_blocks_with_environment_end() {
+    let input = "```tex\n\\begin{verbatim}\n{x}\n\\end {verbatim}\n\\end{lstlisting}\n```";
+    let expected = concat!(
+        "\\begin{Verbatim}[commandchars=\\\\\\{\\}]\n",
+        "\\textbackslash{}begin\\{verbatim\\}\n",
+        "\\{x\\}\n",
+        "\\textbackslash{}end \\{verbatim\\}\n",
+        "\\textbackslash{}end\\{lstlisting\\}\n",
+        "\\end{Verbatim}",
+    );
+    assert_eq!(expected, render(input, Config::default()));
+    assert_eq!(
+        expected,
+        render(
+            input,
+            Config::default().with_code_block_style(CodeBlockStyle::Listings)
+        )
+    );
+    assert_eq!(
+        "\\begin{verbatim}\n\\end{lstlisting}\n\\end{verbatim}",
+        render("```\n\\end{lstlisting}\n```", Config::default())
+    );
+    assert!(render(input, Config::default().with_standalone(true))
+        .contains("\\usepackage{fancyvrb}\n\\usepackage{hyperref}"));
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        "Text\\footnote{First \\emph{note}.} again\\footnotemark[1] and\\footnote{Inline.}\nmissing [\\textasciicircum{}x].",
+        render(
+            "Text[^a] again[^a] and^[Inline.] missing [^x].\n\n[^a]: First *note*.",
+            Config::default()
+        )
+    );
+}
+
+#[test]
+fn standalone() {
+    assert_eq!(
+        concat!(
+            "\\documentclass{report}\n",
+            "\\usepackage[utf8]{inputenc}\n",
+            "\\usepackage[T1]{fontenc}\n",
+            "\\usepackage{graphicx}\n",
+            "\\usepackage[normalem]{ulem}\n",
+            "\\usepackage{hyperref}\n",
+            "\n",
+            "\\begin{document}\n",
+            "\\section{Title}\n",
+            "\n",
+            "Body\n",
+            "\\end{document}",
+        ),
+        render(
+            "# Title\n\nBody",
+            Config::default()
+                .with_standalone(true)
+                .with_document_class("report")
+        )
+    );
+}
+
+#[test]
+fn wrapping() {
+    assert_eq!(
+        "one two three\nfour\n\\emph{five}\nsix",
+        render(
+            "one two three four *five* six",
+            Config::default().with_width(14)
+        )
+    );
+}
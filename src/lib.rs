@@ -1,7 +1,15 @@
 #[cfg(feature = "sync")]
+#[cfg_attr(
+    not(any(feature = "parser", feature = "printer", feature = "html-printer")),
+    allow(dead_code)
+)]
 pub(crate) type Xrc<T> = std::sync::Arc<T>;
 
 #[cfg(not(feature = "sync"))]
+#[cfg_attr(
+    not(any(feature = "parser", feature = "printer", feature = "html-printer")),
+    allow(dead_code)
+)]
 pub(crate) type Xrc<T> = std::rc::Rc<T>;
 
 pub mod ast;
@@ -12,5 +20,47 @@ pub mod parser;
 #[cfg(feature = "printer")]
 pub mod printer;
 
+#[cfg(feature = "ansi-printer")]
+pub mod ansi_printer;
+
+#[cfg(feature = "asciidoc-printer")]
+pub mod asciidoc_printer;
+
+#[cfg(feature = "confluence-printer")]
+pub mod confluence_printer;
+
+#[cfg(feature = "docx-printer")]
+pub mod docx_printer;
+
+#[cfg(feature = "html-parser")]
+pub mod html_parser;
+
 #[cfg(feature = "html-printer")]
 pub mod html_printer;
+
+#[cfg(feature = "jira-printer")]
+pub mod jira_printer;
+
+#[cfg(feature = "latex-printer")]
+pub mod latex_printer;
+
+#[cfg(feature = "man-printer")]
+pub mod man_printer;
+
+#[cfg(feature = "mdast")]
+pub mod mdast;
+
+#[cfg(feature = "renderer")]
+pub mod renderer;
+
+#[cfg(feature = "rst-printer")]
+pub mod rst_printer;
+
+#[cfg(feature = "slack-printer")]
+pub mod slack_printer;
+
+#[cfg(feature = "telegram-printer")]
+pub mod telegram_printer;
+
+#[cfg(feature = "typst-printer")]
+pub mod typst_printer;
@@ -0,0 +1,68 @@
+/// Metadata of the `.TH` title line.
+pub struct Config {
+    pub(crate) width: usize,
+    pub(crate) title: String,
+    pub(crate) section: String,
+    pub(crate) date: Option<String>,
+    pub(crate) source: Option<String>,
+    pub(crate) manual: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            title: String::new(),
+            section: "1".to_owned(),
+            date: None,
+            source: None,
+            manual: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn with_width(self, width: usize) -> Self {
+        Self { width, ..self }
+    }
+
+    /// Page title, conventionally the command name in upper case.
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            ..self
+        }
+    }
+
+    /// Manual section, `1` (user commands) by default.
+    pub fn with_section(self, section: impl Into<String>) -> Self {
+        Self {
+            section: section.into(),
+            ..self
+        }
+    }
+
+    /// Date of the last change, shown in the footer.
+    pub fn with_date(self, date: impl Into<String>) -> Self {
+        Self {
+            date: Some(date.into()),
+            ..self
+        }
+    }
+
+    /// Source of the command, e.g. the project name and version, shown in the footer.
+    pub fn with_source(self, source: impl Into<String>) -> Self {
+        Self {
+            source: Some(source.into()),
+            ..self
+        }
+    }
+
+    /// Title of the manual, shown in the header.
+    pub fn with_manual(self, manual: impl Into<String>) -> Self {
+        Self {
+            manual: Some(manual.into()),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,503 @@
+//! Rendering of the AST to roff with the `man` macros.
+//!
+//! Level 1 headings become `.SH` sections in upper case, level 2 headings `.SS`
+//! subsections. Items of bullet lists which start with inline code or strong text,
+//! such as ``- `--verbose`: print more``, become `.TP` tagged paragraphs, other items
+//! `.IP` paragraphs. Tables are rendered for the `tbl` preprocessor. Footnotes are
+//! collected in a `NOTES` section.
+//!
+//! ```rust
+//! use markdown_ppp::man_printer::{config::Config, render_man};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "# Name\n\ntool - does things\n\n# Options\n\n- `--verbose`: print more",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     ".TH \"TOOL\" \"1\"\n.SH NAME\n.PP\ntool \\- does things\n.SH OPTIONS\n.TP\n\\f(CR\\-\\-verbose\\fR\nprint more",
+//!     render_man(&doc, Config::default().with_title("TOOL"))
+//! );
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::ast::*;
+use crate::man_printer::config::Config;
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+use std::cell::{Cell, RefCell};
+
+/// Render the given Markdown AST to a man page.
+pub fn render_man(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = ManRenderer {
+        arena: &arena,
+        config: &config,
+        index: Index::new(&ast),
+        fonts: RefCell::new(Vec::new()),
+        wrap: Cell::new(true),
+        uppercase: Cell::new(false),
+        line_start: Cell::new(false),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+/// Escape a word for use in a roff text line. Backslashes and hyphens are escaped,
+/// and a word starting with `.` or `'` is protected with `\&`, since at the start of
+/// a line it would be read as a request.
+pub fn escape(word: &str) -> String {
+    escape_word(word, true)
+}
+
+fn escape_word(word: &str, line_start: bool) -> String {
+    let mut result = String::with_capacity(word.len());
+    if line_start && word.starts_with(['.', '\'']) {
+        result.push_str("\\&");
+    }
+    for c in word.chars() {
+        match c {
+            '\\' => result.push_str("\\e"),
+            '-' => result.push_str("\\-"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Quoted macro argument.
+fn quote(argument: &str) -> String {
+    format!("\"{}\"", escape(argument).replace('"', "\\(dq"))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Font {
+    Roman,
+    Italic,
+    Bold,
+    BoldItalic,
+    Code,
+}
+
+impl Font {
+    fn escape(self) -> &'static str {
+        match self {
+            Font::Roman => "\\fR",
+            Font::Italic => "\\fI",
+            Font::Bold => "\\fB",
+            Font::BoldItalic => "\\f(BI",
+            Font::Code => "\\f(CR",
+        }
+    }
+}
+
+struct ManRenderer<'a> {
+    arena: &'a Arena<'a>,
+    config: &'a Config,
+    index: Index,
+    // Fonts of the enclosing emphasis, strong and code spans.
+    fonts: RefCell<Vec<Font>>,
+    // Whether text may be wrapped at spaces, macro arguments must stay on one line.
+    wrap: Cell<bool>,
+    uppercase: Cell<bool>,
+    // Whether the next text starts a line.
+    line_start: Cell<bool>,
+}
+
+impl<'a> ManRenderer<'a> {
+    fn request(&self, request: &str) -> Doc<'a> {
+        self.arena.text(request.to_owned())
+    }
+
+    /// Render inlines in `font`, combined with the font of the enclosing span.
+    fn font(&self, font: Font, content: impl FnOnce() -> Doc<'a>) -> Doc<'a> {
+        let outer = self.fonts.borrow().last().copied().unwrap_or(Font::Roman);
+        let font = match (outer, font) {
+            (Font::Italic, Font::Bold) | (Font::Bold, Font::Italic) => Font::BoldItalic,
+            (Font::BoldItalic, Font::Bold | Font::Italic) => Font::BoldItalic,
+            (_, font) => font,
+        };
+        self.fonts.borrow_mut().push(font);
+        let content = content();
+        self.fonts.borrow_mut().pop();
+        self.arena
+            .text(font.escape())
+            .append(content)
+            .append(self.arena.text(outer.escape()))
+    }
+
+    /// Render inlines starting a text line.
+    fn text_line(&self, inlines: &[Inline]) -> Doc<'a> {
+        self.line_start.set(true);
+        self.render_inlines(inlines)
+    }
+
+    /// Render inlines on a single line.
+    fn line(&self, inlines: &[Inline]) -> Doc<'a> {
+        let wrap = self.wrap.replace(false);
+        let content = self.render_inlines(inlines);
+        self.wrap.set(wrap);
+        content
+    }
+
+    /// Space between words, a possible line break unless on a single line.
+    fn space(&self) -> Doc<'a> {
+        match self.wrap.get() {
+            true => self.arena.softline(),
+            false => self.arena.text(" "),
+        }
+    }
+
+    fn title(&self) -> Doc<'a> {
+        let mut arguments = vec![
+            Some(self.config.title.as_str()),
+            Some(self.config.section.as_str()),
+            self.config.date.as_deref(),
+            self.config.source.as_deref(),
+            self.config.manual.as_deref(),
+        ];
+        while let Some(None) = arguments.last() {
+            arguments.pop();
+        }
+        let arguments: Vec<String> = arguments
+            .into_iter()
+            .map(|argument| quote(argument.unwrap_or_default()))
+            .collect();
+        self.request(&format!(".TH {}", arguments.join(" ")))
+    }
+
+    /// Tag and description of a `.TP` item: a leading code span or strong text,
+    /// followed by the rest of the first paragraph without a `:` or dash separator.
+    fn split_tag(inlines: &[Inline]) -> Option<(&Inline, Vec<Inline>)> {
+        let (tag, rest) = inlines.split_first()?;
+        if !matches!(tag, Inline::Code(_) | Inline::Strong(_)) || rest.is_empty() {
+            return None;
+        }
+        let mut rest = rest.to_vec();
+        if let Some(Inline::Text(text)) = rest.first_mut() {
+            let trimmed = text.trim_start();
+            let trimmed = trimmed
+                .strip_prefix([':', '-', '\u{2013}', '\u{2014}'])
+                .unwrap_or(trimmed)
+                .trim_start();
+            *text = trimmed.to_owned();
+        }
+        Some((tag, rest))
+    }
+}
+
+impl<'a> Renderer<'a> for ManRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    fn render_document(&self, document: &Document) -> Doc<'a> {
+        let mut doc = self.arena.nil();
+        // Tells man to run tbl
+        if document
+            .blocks
+            .iter()
+            .any(|block| matches!(block, Block::Table(_)))
+        {
+            doc = doc
+                .append(self.request("'\\\" t"))
+                .append(self.arena.hardline());
+        }
+        doc = doc.append(self.title());
+        let body = self.render_blocks(&document.blocks);
+        if !matches!(*body, pretty::Doc::Nil) {
+            doc = doc.append(self.block_separator()).append(body);
+        }
+        if !self.index.footnotes().is_empty() {
+            doc = doc
+                .append(self.block_separator())
+                .append(self.render_footnotes(self.index.footnotes()));
+        }
+        doc
+    }
+
+    /// Requests go on their own lines, blank lines would add vertical space.
+    fn block_separator(&self) -> Doc<'a> {
+        self.arena.hardline()
+    }
+
+    fn render_paragraph(&self, inlines: &[Inline]) -> Doc<'a> {
+        self.request(".PP")
+            .append(self.arena.hardline())
+            .append(self.text_line(inlines))
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        match level {
+            1 => {
+                self.uppercase.set(true);
+                let content = self.line(&heading.content);
+                self.uppercase.set(false);
+                self.request(".SH ").append(content)
+            }
+            2 => self.request(".SS ").append(self.line(&heading.content)),
+            _ => self
+                .request(".PP")
+                .append(self.arena.hardline())
+                .append(self.font(Font::Bold, || self.line(&heading.content))),
+        }
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.request(".PP")
+            .append(self.arena.hardline())
+            .append(self.request(".ce"))
+            .append(self.arena.hardline())
+            .append(self.arena.text("* * *"))
+    }
+
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        self.request(".RS")
+            .append(self.arena.hardline())
+            .append(self.render_blocks(blocks))
+            .append(self.arena.hardline())
+            .append(self.request(".RE"))
+    }
+
+    fn render_list_item(&self, list: &List, number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let (first, rest) = match item.blocks.split_first() {
+            Some((Block::Paragraph(inlines), rest)) => (Some(inlines), rest),
+            _ => (None, item.blocks.as_slice()),
+        };
+
+        let tagged = match (&list.kind, item.task, first) {
+            (ListKind::Bullet(_), None, Some(inlines)) => Self::split_tag(inlines),
+            _ => None,
+        };
+        let mut doc = match tagged {
+            Some((tag, description)) => self
+                .request(".TP")
+                .append(self.arena.hardline())
+                .append({
+                    self.line_start.set(true);
+                    self.line(std::slice::from_ref(tag))
+                })
+                .append(self.arena.hardline())
+                .append(self.text_line(&description)),
+            None => {
+                let marker = match (number, item.task) {
+                    (_, Some(TaskState::Complete)) => "[x]".to_owned(),
+                    (_, Some(TaskState::Incomplete)) => "[ ]".to_owned(),
+                    (Some(n), None) => format!("{n}."),
+                    (None, None) => "\\(bu".to_owned(),
+                };
+                let indent = if number.is_some() || item.task.is_some() {
+                    4
+                } else {
+                    2
+                };
+                let doc = self.request(&format!(".IP {marker} {indent}"));
+                match first {
+                    Some(inlines) => doc
+                        .append(self.arena.hardline())
+                        .append(self.text_line(inlines)),
+                    None => doc,
+                }
+            }
+        };
+
+        // Further paragraphs continue the item, nested lists are indented
+        for block in rest {
+            let block = match block {
+                Block::Paragraph(inlines) => self
+                    .request(".IP")
+                    .append(self.arena.hardline())
+                    .append(self.text_line(inlines)),
+                Block::List(_) => self
+                    .request(".RS")
+                    .append(self.arena.hardline())
+                    .append(self.render_block(block))
+                    .append(self.arena.hardline())
+                    .append(self.request(".RE")),
+                block => self.render_block(block),
+            };
+            if !matches!(*block, pretty::Doc::Nil) {
+                doc = doc.append(self.arena.hardline()).append(block);
+            }
+        }
+        doc
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let literal = literal.unwrap_or(&code_block.literal);
+        let lines = literal
+            .split('\n')
+            .map(|line| self.arena.text(escape(line)));
+        self.request(".PP")
+            .append(self.arena.hardline())
+            .append(self.request(".EX"))
+            .append(self.arena.hardline())
+            .append(self.arena.intersperse(lines, self.arena.hardline()))
+            .append(self.arena.hardline())
+            .append(self.request(".EE"))
+    }
+
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let columns = table.rows.first().map(|row| row.len()).unwrap_or_default();
+        let formats: Vec<char> = (0..columns)
+            .map(
+                |i| match table.alignments.get(i).copied().unwrap_or_default() {
+                    Alignment::Center => 'c',
+                    Alignment::Right => 'r',
+                    Alignment::Left | Alignment::None => 'l',
+                },
+            )
+            .collect();
+        let format = |suffix: &str| {
+            formats
+                .iter()
+                .map(|c| format!("{c}{suffix}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        // Cells are separated by tabs, the default tbl separator
+        let row_to_doc = |row: &TableRow| {
+            self.arena.intersperse(
+                row.iter().map(|cell| {
+                    self.line_start.set(true);
+                    let cell = self.line(cell);
+                    let mut text = String::new();
+                    cell.render_fmt(usize::MAX, &mut text)
+                        .expect("writing to a String never fails");
+                    self.arena.text(text.replace('\t', " "))
+                }),
+                self.arena.text("\t"),
+            )
+        };
+
+        let mut doc = self
+            .request(".TS")
+            .append(self.arena.hardline())
+            .append(self.arena.text(format("b")))
+            .append(self.arena.hardline())
+            .append(self.arena.text(format!("{}.", format(""))));
+        if let Some((header, rows)) = table.rows.split_first() {
+            doc = doc
+                .append(self.arena.hardline())
+                .append(row_to_doc(header))
+                .append(self.arena.hardline())
+                .append(self.arena.text("_"));
+            for row in rows {
+                doc = doc.append(self.arena.hardline()).append(row_to_doc(row));
+            }
+        }
+        doc.append(self.arena.hardline())
+            .append(self.request(".TE"))
+    }
+
+    fn render_footnotes(&self, footnotes: &[FootnoteDefinition]) -> Doc<'a> {
+        self.request(".SH NOTES").append(
+            self.arena.concat(
+                footnotes
+                    .iter()
+                    .map(|footnote| self.arena.hardline().append(self.render_footnote(footnote))),
+            ),
+        )
+    }
+
+    fn render_footnote(&self, footnote: &FootnoteDefinition) -> Doc<'a> {
+        let n = self
+            .index
+            .footnote_index(&footnote.label)
+            .unwrap_or_default();
+        let content = self.arena.intersperse(
+            footnote.blocks.iter().map(|block| match block {
+                Block::Paragraph(inlines) => self.text_line(inlines),
+                block => self.render_block(block),
+            }),
+            self.arena
+                .hardline()
+                .append(self.request(".IP"))
+                .append(self.arena.hardline()),
+        );
+        self.request(&format!(".IP [{n}] 4"))
+            .append(self.arena.hardline())
+            .append(content)
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        let text = match self.uppercase.get() {
+            true => text.to_uppercase(),
+            false => text.to_owned(),
+        };
+        // Wrapping may put any word but the first at the start of a line
+        let line_start = self.line_start.replace(false);
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .enumerate()
+                .map(|(i, word)| self.arena.text(escape_word(word, i > 0 || line_start))),
+            self.space(),
+        )
+    }
+
+    fn render_inlines(&self, inlines: &[Inline]) -> Doc<'a> {
+        self.arena.concat(inlines.iter().map(|inline| {
+            let doc = self.render_inline(inline);
+            self.line_start.set(false);
+            doc
+        }))
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.line_start.set(true);
+        self.arena
+            .hardline()
+            .append(self.request(".br"))
+            .append(self.arena.hardline())
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        self.font(Font::Code, || {
+            self.arena.intersperse(
+                code.split(' ').map(|word| self.arena.text(escape(word))),
+                self.arena.text("\\ "),
+            )
+        })
+    }
+
+    /// Render the link text followed by the destination in angle brackets, or only
+    /// the destination if it is the same as the text.
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let destination = self.arena.text(format!("<{}>", escape(&link.destination)));
+        match link.children.as_slice() {
+            [Inline::Text(text)] if *text == link.destination => {
+                self.arena.text(escape(&link.destination))
+            }
+            children => self
+                .render_inlines(children)
+                .append(self.space())
+                .append(destination),
+        }
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.font(Font::Italic, || self.render_inlines(children))
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.font(Font::Bold, || self.render_inlines(children))
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        self.arena.text(escape(link))
+    }
+}
@@ -0,0 +1,143 @@
+#![cfg(test)]
+use crate::man_printer::config::Config;
+use crate::man_printer::render_man;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap();
+    render_man(&doc, Config::default().with_title("T"))
+}
+
+#[rstest]
+#[case("# Synopsis", ".SH SYNOPSIS")]
+#[case("## Exit *status*", ".SS Exit \\fIstatus\\fR")]
+#[case("### Minor", ".PP\n\\fBMinor\\fR")]
+#[case(
+    "*em* **strong _both_** `a b`",
+    ".PP\n\\fIem\\fR \\fBstrong \\f(BIboth\\fB\\fR \\f(CRa\\ b\\fR"
+)]
+#[case(
+    ".start a\\b --flag\n.next 'quote'.",
+    ".PP\n\\&.start a\\eb \\-\\-flag \\&.next \\&'quote'."
+)]
+#[case(
+    "[docs](https://example.com) <https://example.com>",
+    ".PP\ndocs <https://example.com> https://example.com"
+)]
+#[case("line  \nbreak", ".PP\nline\n.br\nbreak")]
+#[case("> quoted", ".RS\n.PP\nquoted\n.RE")]
+#[case("```\n.nf\nrm -rf \\\n```", ".PP\n.EX\n\\&.nf\nrm \\-rf \\e\n.EE")]
+fn man(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(format!(".TH \"T\" \"1\"\n{expected}"), render(input));
+}
+
+#[test]
+fn title() {
+    let doc =
+        crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), "").unwrap();
+    assert_eq!(
+        ".TH \"TOOL\" \"8\" \"\" \"tool 1.0\"",
+        render_man(
+            &doc,
+            Config::default()
+                .with_title("TOOL")
+                .with_section("8")
+                .with_source("tool 1.0")
+        )
+    );
+    assert_eq!(
+        ".TH \"TOOL\" \"1\" \"2024\\-01\\-02\" \"tool\" \"User \\(dqCommands\\(dq\"",
+        render_man(
+            &doc,
+            Config::default()
+                .with_title("TOOL")
+                .with_date("2024-01-02")
+                .with_source("tool")
+                .with_manual("User \"Commands\"")
+        )
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        concat!(
+            ".TH \"T\" \"1\"\n",
+            ".IP \\(bu 2\n",
+            "one\n",
+            ".IP\n",
+            "continued\n",
+            ".RS\n",
+            ".IP 3. 4\n",
+            "three\n",
+            ".IP 4. 4\n",
+            "four\n",
+            ".RE\n",
+            ".IP [x] 4\n",
+            "done",
+        ),
+        render("- one\n\n    continued\n\n    3. three\n    4. four\n\n- [x] done")
+    );
+}
+
+#[test]
+fn tagged_paragraphs() {
+    assert_eq!(
+        concat!(
+            ".TH \"T\" \"1\"\n",
+            ".TP\n",
+            "\\f(CR\\-v\\fR\n",
+            "Print more.\n",
+            ".TP\n",
+            "\\fBconfig\\fR\n",
+            "Path to the \\fIconfig\\fR.\n",
+            ".IP \\(bu 2\n",
+            "\\fBplain\\fR",
+        ),
+        render("- `-v`: Print more.\n- **config** - Path to the *config*.\n- **plain**")
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        concat!(
+            "'\\\" t\n",
+            ".TH \"T\" \"1\"\n",
+            ".TS\n",
+            "lb cb rb\n",
+            "l c r.\n",
+            "Name\tKind\tSize\n",
+            "_\n",
+            "\\f(CRa.rs\\fR\tfile\t10\n",
+            ".TE",
+        ),
+        render("| Name | Kind | Size |\n|:--|:-:|--:|\n| `a.rs` | file | 10 |")
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        concat!(
+            ".TH \"T\" \"1\"\n",
+            ".PP\n",
+            "Text[1] and[2].\n",
+            ".SH NOTES\n",
+            ".IP [1] 4\n",
+            "First.\n",
+            ".IP [2] 4\n",
+            "Inline.",
+        ),
+        render("Text[^a] and^[Inline.].\n\n[^a]: First.")
+    );
+}
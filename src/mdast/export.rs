@@ -0,0 +1,264 @@
+use crate::ast::*;
+use crate::mdast::identifier;
+use serde_json::{json, Map, Value};
+
+/// Convert the AST to an mdast `root` node.
+pub fn to_mdast(ast: &Document) -> Value {
+    let ast = crate::ast::footnotes::hoisted(ast);
+    json!({ "type": "root", "children": blocks(&ast.blocks) })
+}
+
+fn blocks(blocks: &[Block]) -> Vec<Value> {
+    blocks.iter().filter_map(block).collect()
+}
+
+fn block(block: &Block) -> Option<Value> {
+    let node = match block {
+        Block::Paragraph(content) => json!({ "type": "paragraph", "children": inlines(content) }),
+        Block::Heading(heading) => {
+            let depth = match heading.kind {
+                HeadingKind::Atx(level) => level,
+                HeadingKind::Setext(SetextHeading::Level1) => 1,
+                HeadingKind::Setext(SetextHeading::Level2) => 2,
+            };
+            json!({ "type": "heading", "depth": depth, "children": inlines(&heading.content) })
+        }
+        Block::ThematicBreak => json!({ "type": "thematicBreak" }),
+        Block::BlockQuote(content) => json!({ "type": "blockquote", "children": blocks(content) }),
+        Block::List(list) => {
+            let (ordered, start) = match &list.kind {
+                ListKind::Ordered(options) => (true, Some(options.start)),
+                ListKind::Bullet(_) => (false, None),
+            };
+            let items = list
+                .items
+                .iter()
+                .map(|item| {
+                    let checked = item.task.map(|task| task == TaskState::Complete);
+                    json!({
+                        "type": "listItem",
+                        "spread": false,
+                        "checked": checked,
+                        "children": blocks(&item.blocks),
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({
+                "type": "list",
+                "ordered": ordered,
+                "start": start,
+                "spread": false,
+                "children": items,
+            })
+        }
+        Block::CodeBlock(code_block) => {
+            let info = match &code_block.kind {
+                CodeBlockKind::Fenced { info } => info.as_deref().map(str::trim),
+                CodeBlockKind::Indented => None,
+            };
+            let (lang, meta) = match info.and_then(|info| info.split_once(char::is_whitespace)) {
+                Some((lang, meta)) => (Some(lang), Some(meta.trim_start())),
+                None => (info.filter(|info| !info.is_empty()), None),
+            };
+            json!({ "type": "code", "lang": lang, "meta": meta, "value": code_block.literal })
+        }
+        Block::HtmlBlock(value) => json!({ "type": "html", "value": value }),
+        Block::Definition(definition) => {
+            let label = text_content(&definition.label);
+            json!({
+                "type": "definition",
+                "identifier": identifier(&label),
+                "label": label,
+                "url": definition.destination,
+                "title": definition.title,
+            })
+        }
+        Block::Table(table) => {
+            let align = table
+                .alignments
+                .iter()
+                .map(|alignment| match alignment {
+                    Alignment::None => None,
+                    Alignment::Left => Some("left"),
+                    Alignment::Center => Some("center"),
+                    Alignment::Right => Some("right"),
+                })
+                .collect::<Vec<_>>();
+            let rows = table
+                .rows
+                .iter()
+                .map(|row| {
+                    let cells = row
+                        .iter()
+                        .map(|cell| json!({ "type": "tableCell", "children": inlines(cell) }))
+                        .collect::<Vec<_>>();
+                    json!({ "type": "tableRow", "children": cells })
+                })
+                .collect::<Vec<_>>();
+            json!({ "type": "table", "align": align, "children": rows })
+        }
+        Block::FootnoteDefinition(footnote) => json!({
+            "type": "footnoteDefinition",
+            "identifier": identifier(&footnote.label),
+            "label": footnote.label,
+            "children": blocks(&footnote.blocks),
+        }),
+        Block::ContainerDirective(directive) => {
+            // The label is a paragraph marked in its data, before the content
+            let mut children = Vec::new();
+            if !directive.label.is_empty() {
+                children.push(json!({
+                    "type": "paragraph",
+                    "data": { "directiveLabel": true },
+                    "children": inlines(&directive.label),
+                }));
+            }
+            children.extend(blocks(&directive.blocks));
+            json!({
+                "type": "containerDirective",
+                "name": directive.name,
+                "attributes": attributes(&directive.attributes),
+                "children": children,
+            })
+        }
+        Block::LeafDirective(directive) => self::directive("leafDirective", directive),
+        Block::Empty => return None,
+    };
+    Some(node)
+}
+
+fn inlines(inlines: &[Inline]) -> Vec<Value> {
+    inlines.iter().filter_map(inline).collect()
+}
+
+fn inline(inline: &Inline) -> Option<Value> {
+    let node = match inline {
+        Inline::Text(value) => text(value),
+        Inline::LineBreak => json!({ "type": "break" }),
+        Inline::Code(value) => json!({ "type": "inlineCode", "value": value }),
+        Inline::Html(value) => json!({ "type": "html", "value": value }),
+        Inline::Link(link) => json!({
+            "type": "link",
+            "url": link.destination,
+            "title": link.title,
+            "children": inlines(&link.children),
+        }),
+        Inline::LinkReference(link) => {
+            let label = text_content(&link.label);
+            let reference_type = match link.label == link.text {
+                true => "shortcut",
+                false => "full",
+            };
+            json!({
+                "type": "linkReference",
+                "identifier": identifier(&label),
+                "label": label,
+                "referenceType": reference_type,
+                "children": inlines(&link.text),
+            })
+        }
+        Inline::Image(image) => json!({
+            "type": "image",
+            "url": image.destination,
+            "title": image.title,
+            "alt": image.alt,
+        }),
+        Inline::Emphasis(children) => json!({ "type": "emphasis", "children": inlines(children) }),
+        Inline::Strong(children) => json!({ "type": "strong", "children": inlines(children) }),
+        Inline::Strikethrough(children) => {
+            json!({ "type": "delete", "children": inlines(children) })
+        }
+        Inline::Autolink(link) => {
+            let url = if link.starts_with("www.") {
+                format!("http://{link}")
+            } else if !link.contains(':') && link.contains('@') {
+                format!("mailto:{link}")
+            } else {
+                link.clone()
+            };
+            json!({ "type": "link", "url": url, "title": null, "children": [text(link)] })
+        }
+        Inline::FootnoteReference(label) => json!({
+            "type": "footnoteReference",
+            "identifier": identifier(label),
+            "label": label,
+        }),
+        // Hoisted before the conversion
+        Inline::InlineFootnote(_) => return None,
+        Inline::TextDirective(directive) => self::directive("textDirective", directive),
+        Inline::WikiLink(link) => {
+            let value = match &link.fragment {
+                Some(fragment) => format!("{}#{fragment}", link.target),
+                None => link.target.clone(),
+            };
+            let mut node = json!({
+                "type": "wikiLink",
+                "value": value,
+                "data": { "alias": link.alias },
+            });
+            if link.embed {
+                node["embed"] = Value::Bool(true);
+            }
+            node
+        }
+        Inline::Mention(mention) => text(&match mention {
+            Mention::User(user) => format!("@{user}"),
+            Mention::Issue { repository, number } => {
+                format!("{}#{number}", repository.as_deref().unwrap_or_default())
+            }
+            Mention::Commit {
+                repository: Some(repository),
+                sha,
+            } => format!("{repository}@{sha}"),
+            Mention::Commit {
+                repository: None,
+                sha,
+            } => sha.clone(),
+        }),
+        Inline::Empty => return None,
+    };
+    Some(node)
+}
+
+fn text(value: &str) -> Value {
+    json!({ "type": "text", "value": value })
+}
+
+fn directive(kind: &str, directive: &Directive) -> Value {
+    json!({
+        "type": kind,
+        "name": directive.name,
+        "attributes": attributes(&directive.attributes),
+        "children": inlines(&directive.label),
+    })
+}
+
+fn attributes(attributes: &[(String, String)]) -> Map<String, Value> {
+    attributes
+        .iter()
+        .map(|(key, value)| (key.clone(), Value::String(value.clone())))
+        .collect()
+}
+
+/// Text of a label, as written without markup.
+fn text_content(inlines: &[Inline]) -> String {
+    let mut result = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(value) | Inline::Code(value) | Inline::Html(value) => {
+                result.push_str(value)
+            }
+            Inline::LineBreak => result.push('\n'),
+            Inline::Link(Link { children, .. })
+            | Inline::LinkReference(LinkReference { text: children, .. })
+            | Inline::Emphasis(children)
+            | Inline::Strong(children)
+            | Inline::Strikethrough(children)
+            | Inline::InlineFootnote(children) => result.push_str(&text_content(children)),
+            Inline::Image(image) => result.push_str(&image.alt),
+            Inline::Autolink(value) => result.push_str(value),
+            _ => (),
+        }
+    }
+    result
+}
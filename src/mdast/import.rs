@@ -0,0 +1,403 @@
+use crate::ast::*;
+use crate::mdast::{identifier, Error};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Convert an mdast `root` node to the AST.
+///
+/// Unknown fields, `data` and `position` are ignored. Nodes of types from other
+/// plugins are kept as raw HTML holding their `value`, such as `math`, or dropped
+/// when they have none. `yaml` and `toml` front matter is dropped.
+pub fn from_mdast(root: &Value) -> Result<Document, Error> {
+    let kind = kind(root)?;
+    if kind != "root" {
+        return Err(Error::new(format!("expected `root`, found `{kind}`")));
+    }
+    let mut importer = Importer {
+        definitions: HashMap::new(),
+    };
+    importer.collect_definitions(root);
+    Ok(Document {
+        blocks: importer.blocks(children(root)?)?,
+    })
+}
+
+struct Importer<'a> {
+    /// Destination and title of definitions by identifier, for `imageReference` nodes
+    /// which have no AST equivalent.
+    definitions: HashMap<String, (&'a str, Option<&'a str>)>,
+}
+
+impl<'a> Importer<'a> {
+    fn collect_definitions(&mut self, node: &'a Value) {
+        if node["type"] == "definition" {
+            if let (Some(identifier), Some(url)) =
+                (node["identifier"].as_str(), node["url"].as_str())
+            {
+                let title = node["title"].as_str();
+                self.definitions
+                    .entry(self::identifier(identifier))
+                    .or_insert((url, title));
+            }
+        }
+        if let Some(children) = node["children"].as_array() {
+            for child in children {
+                self.collect_definitions(child);
+            }
+        }
+    }
+
+    fn blocks(&self, nodes: &[Value]) -> Result<Vec<Block>, Error> {
+        nodes
+            .iter()
+            .filter_map(|node| self.block(node).transpose())
+            .collect()
+    }
+
+    fn block(&self, node: &Value) -> Result<Option<Block>, Error> {
+        let kind = kind(node)?;
+        let block = match kind {
+            "paragraph" => Block::Paragraph(self.inlines(children(node)?)?),
+            "heading" => {
+                let depth = node["depth"]
+                    .as_u64()
+                    .filter(|depth| (1..=6).contains(depth))
+                    .ok_or_else(|| Error::new("`heading` without a `depth` from 1 to 6"))?;
+                Block::Heading(Heading {
+                    kind: HeadingKind::Atx(depth as u8),
+                    content: self.inlines(children(node)?)?,
+                })
+            }
+            "thematicBreak" => Block::ThematicBreak,
+            "blockquote" => Block::BlockQuote(self.blocks(children(node)?)?),
+            "list" => {
+                let kind = match node["ordered"].as_bool().unwrap_or(false) {
+                    true => ListKind::Ordered(ListOrderedKindOptions {
+                        start: node["start"].as_u64().unwrap_or(1),
+                    }),
+                    false => ListKind::Bullet(ListBulletKind::Dash),
+                };
+                let items = children(node)?
+                    .iter()
+                    .map(|item| self.list_item(item))
+                    .collect::<Result<_, _>>()?;
+                Block::List(List { kind, items })
+            }
+            "code" => {
+                let info = match optional_string(node, "lang")? {
+                    Some(lang) => match optional_string(node, "meta")? {
+                        Some(meta) => Some(format!("{lang} {meta}")),
+                        None => Some(lang.to_owned()),
+                    },
+                    None => None,
+                };
+                Block::CodeBlock(CodeBlock {
+                    kind: CodeBlockKind::Fenced { info },
+                    literal: string(node, "value")?.to_owned(),
+                })
+            }
+            "html" => Block::HtmlBlock(string(node, "value")?.to_owned()),
+            "definition" => Block::Definition(LinkDefinition {
+                label: vec![Inline::Text(label(node)?.to_owned())],
+                destination: string(node, "url")?.to_owned(),
+                title: optional_string(node, "title")?.map(str::to_owned),
+            }),
+            "table" => self.table(node)?,
+            "footnoteDefinition" => Block::FootnoteDefinition(FootnoteDefinition {
+                label: label(node)?.to_owned(),
+                blocks: self.blocks(children(node)?)?,
+            }),
+            "containerDirective" => {
+                // The label is a paragraph marked in its data, before the content
+                let mut children = children(node)?;
+                let mut label = Vec::new();
+                if let [first, rest @ ..] = children {
+                    if first["type"] == "paragraph" && first["data"]["directiveLabel"] == true {
+                        label = self.inlines(self::children(first)?)?;
+                        children = rest;
+                    }
+                }
+                Block::ContainerDirective(ContainerDirective {
+                    name: string(node, "name")?.to_owned(),
+                    label,
+                    attributes: attributes(node)?,
+                    blocks: self.blocks(children)?,
+                })
+            }
+            "leafDirective" => Block::LeafDirective(self.directive(node)?),
+            _ => return unknown(node, kind, "block", Block::HtmlBlock),
+        };
+        Ok(Some(block))
+    }
+
+    fn list_item(&self, node: &Value) -> Result<ListItem, Error> {
+        let kind = kind(node)?;
+        if kind != "listItem" {
+            return Err(Error::new(format!("`{kind}` in `list`")));
+        }
+        let task = match node["checked"].as_bool() {
+            Some(true) => Some(TaskState::Complete),
+            Some(false) => Some(TaskState::Incomplete),
+            None => None,
+        };
+        Ok(ListItem {
+            task,
+            blocks: self.blocks(children(node)?)?,
+        })
+    }
+
+    fn table(&self, node: &Value) -> Result<Block, Error> {
+        let mut alignments = match &node["align"] {
+            Value::Array(align) => align
+                .iter()
+                .map(|alignment| match alignment.as_str() {
+                    Some("left") => Alignment::Left,
+                    Some("center") => Alignment::Center,
+                    Some("right") => Alignment::Right,
+                    _ => Alignment::None,
+                })
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        let mut rows = Vec::new();
+        for row in children(node)? {
+            let kind = kind(row)?;
+            if kind != "tableRow" {
+                return Err(Error::new(format!("`{kind}` in `table`")));
+            }
+            let mut cells = Vec::new();
+            for cell in children(row)? {
+                let kind = self::kind(cell)?;
+                if kind != "tableCell" {
+                    return Err(Error::new(format!("`{kind}` in `tableRow`")));
+                }
+                cells.push(self.inlines(children(cell)?)?);
+            }
+            rows.push(cells);
+        }
+        let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+        alignments.resize(columns, Alignment::None);
+        Ok(Block::Table(Table { rows, alignments }))
+    }
+
+    fn inlines(&self, nodes: &[Value]) -> Result<Vec<Inline>, Error> {
+        nodes
+            .iter()
+            .filter_map(|node| self.inline(node).transpose())
+            .collect()
+    }
+
+    fn inline(&self, node: &Value) -> Result<Option<Inline>, Error> {
+        let kind = kind(node)?;
+        let inline = match kind {
+            "text" => Inline::Text(string(node, "value")?.to_owned()),
+            "break" => Inline::LineBreak,
+            "inlineCode" => Inline::Code(string(node, "value")?.to_owned()),
+            "html" => Inline::Html(string(node, "value")?.to_owned()),
+            "link" => {
+                let destination = string(node, "url")?;
+                let children = self.inlines(children(node)?)?;
+                match autolink(destination, &children) {
+                    Some(link) => Inline::Autolink(link.to_owned()),
+                    None => Inline::Link(Link {
+                        destination: destination.to_owned(),
+                        title: optional_string(node, "title")?.map(str::to_owned),
+                        children,
+                    }),
+                }
+            }
+            "linkReference" => {
+                let text = self.inlines(children(node)?)?;
+                let label = match node["referenceType"].as_str() {
+                    Some("shortcut" | "collapsed") => text.clone(),
+                    _ => vec![Inline::Text(label(node)?.to_owned())],
+                };
+                Inline::LinkReference(LinkReference { label, text })
+            }
+            "image" => Inline::Image(Image {
+                destination: string(node, "url")?.to_owned(),
+                title: optional_string(node, "title")?.map(str::to_owned),
+                alt: optional_string(node, "alt")?.unwrap_or_default().to_owned(),
+            }),
+            "imageReference" => {
+                let alt = optional_string(node, "alt")?.unwrap_or_default();
+                match self.definitions.get(&identifier(label(node)?)) {
+                    Some((destination, title)) => Inline::Image(Image {
+                        destination: (*destination).to_owned(),
+                        title: title.map(str::to_owned),
+                        alt: alt.to_owned(),
+                    }),
+                    None => Inline::Text(format!("![{alt}]")),
+                }
+            }
+            "emphasis" => Inline::Emphasis(self.inlines(children(node)?)?),
+            "strong" => Inline::Strong(self.inlines(children(node)?)?),
+            "delete" => Inline::Strikethrough(self.inlines(children(node)?)?),
+            "footnoteReference" => Inline::FootnoteReference(label(node)?.to_owned()),
+            "footnote" => Inline::InlineFootnote(self.inlines(children(node)?)?),
+            "textDirective" => Inline::TextDirective(self.directive(node)?),
+            "wikiLink" => {
+                let value = string(node, "value")?;
+                let (target, fragment) = match value.split_once('#') {
+                    Some((target, fragment)) => (target, Some(fragment.to_owned())),
+                    None => (value, None),
+                };
+                Inline::WikiLink(WikiLink {
+                    target: target.to_owned(),
+                    fragment,
+                    alias: node["data"]["alias"]
+                        .as_str()
+                        .filter(|alias| *alias != value)
+                        .map(str::to_owned),
+                    embed: node["embed"].as_bool().unwrap_or(false),
+                })
+            }
+            _ => return unknown(node, kind, "inline", Inline::Html),
+        };
+        Ok(Some(inline))
+    }
+
+    fn directive(&self, node: &Value) -> Result<Directive, Error> {
+        Ok(Directive {
+            name: string(node, "name")?.to_owned(),
+            label: self.inlines(children(node)?)?,
+            attributes: attributes(node)?,
+        })
+    }
+}
+
+/// Node types of mdast and the supported extensions.
+const KNOWN_NODES: [&str; 30] = [
+    "root",
+    "paragraph",
+    "heading",
+    "thematicBreak",
+    "blockquote",
+    "list",
+    "listItem",
+    "code",
+    "html",
+    "definition",
+    "table",
+    "tableRow",
+    "tableCell",
+    "footnoteDefinition",
+    "containerDirective",
+    "leafDirective",
+    "textDirective",
+    "text",
+    "break",
+    "inlineCode",
+    "link",
+    "linkReference",
+    "image",
+    "imageReference",
+    "emphasis",
+    "strong",
+    "delete",
+    "footnoteReference",
+    "footnote",
+    "wikiLink",
+];
+
+/// A node of a type from another plugin as raw HTML, or nothing. Known nodes out of
+/// place are errors.
+fn unknown<T>(
+    node: &Value,
+    kind: &str,
+    context: &str,
+    html: fn(String) -> T,
+) -> Result<Option<T>, Error> {
+    if KNOWN_NODES.contains(&kind) {
+        return Err(Error::new(format!("unsupported {context} node `{kind}`")));
+    }
+    match (kind, node["value"].as_str()) {
+        ("yaml" | "toml", _) | (_, None) => Ok(None),
+        (_, Some(value)) => Ok(Some(html(value.to_owned()))),
+    }
+}
+
+/// The text of a link whose text is its own destination, as in `<https://example.com>`.
+fn autolink<'a>(destination: &'a str, children: &[Inline]) -> Option<&'a str> {
+    let [Inline::Text(text)] = children else {
+        return None;
+    };
+    let link = [
+        destination,
+        destination.strip_prefix("mailto:").unwrap_or_default(),
+        destination.strip_prefix("http://").unwrap_or_default(),
+    ]
+    .into_iter()
+    .find(|link| !link.is_empty() && link == text)?;
+    // Without a scheme only `www.` links and email addresses are autolinks
+    match link == destination || link.starts_with("www.") || link.contains('@') {
+        true => Some(link),
+        false => None,
+    }
+}
+
+fn kind(node: &Value) -> Result<&str, Error> {
+    node["type"]
+        .as_str()
+        .ok_or_else(|| Error::new("node without `type`"))
+}
+
+/// Type of a node for error messages.
+fn type_name(node: &Value) -> &str {
+    node["type"].as_str().unwrap_or_default()
+}
+
+fn children(node: &Value) -> Result<&[Value], Error> {
+    match &node["children"] {
+        Value::Array(children) => Ok(children),
+        Value::Null => Ok(&[]),
+        _ => Err(Error::new(format!(
+            "`children` of `{}` is not an array",
+            kind(node)?
+        ))),
+    }
+}
+
+fn string<'a>(node: &'a Value, name: &str) -> Result<&'a str, Error> {
+    optional_string(node, name)?
+        .ok_or_else(|| Error::new(format!("`{}` without `{name}`", type_name(node))))
+}
+
+/// A string field, `None` if missing or `null`.
+fn optional_string<'a>(node: &'a Value, name: &str) -> Result<Option<&'a str>, Error> {
+    match &node[name] {
+        Value::String(value) => Ok(Some(value)),
+        Value::Null => Ok(None),
+        _ => Err(Error::new(format!(
+            "`{name}` of `{}` is not a string",
+            type_name(node)
+        ))),
+    }
+}
+
+/// Label of an association, or its identifier if the label is missing.
+fn label(node: &Value) -> Result<&str, Error> {
+    match optional_string(node, "label")? {
+        Some(label) => Ok(label),
+        None => string(node, "identifier"),
+    }
+}
+
+/// Directive attributes, a `null` value is an attribute without value.
+fn attributes(node: &Value) -> Result<Vec<(String, String)>, Error> {
+    match &node["attributes"] {
+        Value::Object(attributes) => attributes
+            .iter()
+            .map(|(key, value)| match value {
+                Value::String(value) => Ok((key.clone(), value.clone())),
+                Value::Null => Ok((key.clone(), String::new())),
+                _ => Err(Error::new(format!("attribute `{key}` is not a string"))),
+            })
+            .collect(),
+        Value::Null => Ok(Vec::new()),
+        _ => Err(Error::new(format!(
+            "`attributes` of `{}` is not an object",
+            type_name(node)
+        ))),
+    }
+}
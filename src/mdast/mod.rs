@@ -0,0 +1,76 @@
+//! Conversion between the AST and [mdast](https://github.com/syntax-tree/mdast), the
+//! JSON syntax tree of the unified/remark ecosystem.
+//!
+//! Besides the core node types, the GFM nodes (`delete`, `table`, `footnoteReference`,
+//! `footnoteDefinition`, task list items with `checked`) and the directive nodes of
+//! `mdast-util-directive` are supported. Nodes without an mdast equivalent are
+//! exported in the closest form: autolinks become links, mentions become text, wiki
+//! links become `wikiLink` nodes as in `remark-wiki-link`, and inline footnotes are
+//! numbered as footnote definitions first. When importing, nodes from other plugins
+//! become raw HTML holding their `value`, and front matter is dropped.
+//!
+//! The AST does not record source positions, so exported nodes have no `position`
+//! and positions of imported nodes are ignored. mdast does not distinguish indented
+//! from fenced code, setext from ATX headings or list bullet characters; imported
+//! nodes get fenced code, ATX headings and `-` bullets.
+//!
+//! ```rust
+//! use markdown_ppp::mdast::{from_mdast, to_mdast};
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//!
+//! let doc = parse_markdown(MarkdownParserState::default(), "# Hi *there*").unwrap();
+//! let json = to_mdast(&doc);
+//! assert_eq!(
+//!     serde_json::json!({
+//!         "type": "root",
+//!         "children": [{
+//!             "type": "heading",
+//!             "depth": 1,
+//!             "children": [
+//!                 { "type": "text", "value": "Hi " },
+//!                 { "type": "emphasis", "children": [{ "type": "text", "value": "there" }] },
+//!             ],
+//!         }],
+//!     }),
+//!     json
+//! );
+//! assert_eq!(doc, from_mdast(&json).unwrap());
+//! ```
+
+mod export;
+mod import;
+mod tests;
+
+pub use export::to_mdast;
+pub use import::from_mdast;
+
+/// JSON which is not a supported mdast tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error {
+    message: String,
+}
+
+impl Error {
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid mdast: {}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Identifier of a label, compared case-insensitively with collapsed whitespace.
+fn identifier(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -0,0 +1,309 @@
+#![cfg(test)]
+use crate::ast::*;
+use crate::mdast::{from_mdast, to_mdast};
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+use crate::printer::{config::Config, render_markdown};
+use rstest::rstest;
+use serde_json::json;
+
+fn parse(input: &str) -> Document {
+    let config = MarkdownParserConfig::default()
+        .with_inline_inline_footnote_behavior(ElementBehavior::Parse);
+    parse_markdown(MarkdownParserState::with_config(config), input).unwrap()
+}
+
+fn markdown(mdast: serde_json::Value) -> String {
+    render_markdown(&from_mdast(&mdast).unwrap(), Config::default())
+}
+
+#[rstest]
+#[case("# Title\n\nSome *emphasis*, **strong**, ~~deleted~~ and `code`.")]
+#[case("> quoted\n> \n> > nested")]
+#[case("1. one\n2. two")]
+#[case("3. three\n4. four")]
+#[case(" - [ ] todo\n - [x] done\n - plain")]
+#[case("```rust title=\"main.rs\"\nfn main() {}\n```")]
+#[case("```\nno info\n```")]
+#[case("<div>\nhtml\n</div>\n\nInline <b>html</b>.")]
+#[case("[link](/url \"Title\") and ![alt](/img.png)")]
+#[case("[full][Ref], [Ref][] and [Ref]\n\n[ref]: /url \"Title\"")]
+#[case("<https://example.com> and <me@example.com>")]
+#[case("one  \ntwo")]
+#[case("Note[^1].\n\n[^1]: The note.")]
+#[case("| a | b | c | d |\n|---|:--|:-:|--:|\n| 1 | 2 | 3 | 4 |")]
+#[case("---")]
+#[case(":::note[Label]{#id .warn}\nInside.\n:::")]
+#[case("::video{src=\"a.mp4\"}")]
+#[case("Text :abbr[HTML]{title=\"Markup\"}.")]
+#[case("[[Page#Heading|Alias]] and ![[image.png]]")]
+fn round_trip(#[case] input: &str) {
+    let doc = parse(input);
+    assert_eq!(doc, from_mdast(&to_mdast(&doc)).unwrap());
+}
+
+#[rstest]
+#[case("Title\n=====", "# Title")]
+#[case("    indented", "```\nindented\n```")]
+#[case(" * star\n * list", " - star\n - list")]
+#[case("Note^[inline].", "Note[^1].\n\n[^1]: inline")]
+#[case("@user and #12", "@user and #12")]
+fn round_trip_normalized(#[case] input: &str, #[case] expected: &str) {
+    let mdast = to_mdast(&parse(input));
+    assert_eq!(expected, markdown(mdast));
+}
+
+#[test]
+fn export() {
+    let doc = parse("- [x] [a](/b)\n\n```js meta data\nx\n```\n\n[^n]: Foot");
+    assert_eq!(
+        json!({
+            "type": "root",
+            "children": [
+                {
+                    "type": "list",
+                    "ordered": false,
+                    "start": null,
+                    "spread": false,
+                    "children": [{
+                        "type": "listItem",
+                        "spread": false,
+                        "checked": true,
+                        "children": [{
+                            "type": "paragraph",
+                            "children": [{
+                                "type": "link",
+                                "url": "/b",
+                                "title": null,
+                                "children": [{ "type": "text", "value": "a" }],
+                            }],
+                        }],
+                    }],
+                },
+                { "type": "code", "lang": "js", "meta": "meta data", "value": "x" },
+                {
+                    "type": "footnoteDefinition",
+                    "identifier": "n",
+                    "label": "n",
+                    "children": [{
+                        "type": "paragraph",
+                        "children": [{ "type": "text", "value": "Foot" }],
+                    }],
+                },
+            ],
+        }),
+        to_mdast(&doc)
+    );
+}
+
+#[test]
+fn export_labels() {
+    let mdast = to_mdast(&parse("[Te  xt][Foo  Bar]\n\n[FOO bar]: /x"));
+    let reference = &mdast["children"][0]["children"][0];
+    assert_eq!("foo bar", reference["identifier"]);
+    assert_eq!("Foo  Bar", reference["label"]);
+    assert_eq!("full", reference["referenceType"]);
+    assert_eq!("foo bar", mdast["children"][1]["identifier"]);
+}
+
+/// Tree as produced by `remark-parse` with `remark-gfm`, including positions.
+#[test]
+fn import_remark() {
+    let position = json!({
+        "start": { "line": 1, "column": 1, "offset": 0 },
+        "end": { "line": 1, "column": 6, "offset": 5 },
+    });
+    let mdast = json!({
+        "type": "root",
+        "children": [
+            {
+                "type": "heading",
+                "depth": 2,
+                "children": [{ "type": "text", "value": "Hello", "position": position }],
+                "position": position,
+            },
+            {
+                "type": "paragraph",
+                "children": [
+                    { "type": "text", "value": "See " },
+                    {
+                        "type": "linkReference",
+                        "identifier": "docs",
+                        "label": "Docs",
+                        "referenceType": "collapsed",
+                        "children": [{ "type": "text", "value": "Docs" }],
+                    },
+                    { "type": "text", "value": ", " },
+                    {
+                        "type": "imageReference",
+                        "identifier": "logo",
+                        "label": "logo",
+                        "referenceType": "full",
+                        "alt": "Logo",
+                    },
+                    { "type": "text", "value": " and " },
+                    {
+                        "type": "link",
+                        "url": "http://www.example.com",
+                        "title": null,
+                        "children": [{ "type": "text", "value": "www.example.com" }],
+                    },
+                    { "type": "text", "value": "." },
+                ],
+            },
+            {
+                "type": "list",
+                "ordered": true,
+                "start": 7,
+                "spread": false,
+                "children": [{
+                    "type": "listItem",
+                    "spread": false,
+                    "checked": false,
+                    "children": [{
+                        "type": "paragraph",
+                        "children": [{ "type": "text", "value": "item" }],
+                    }],
+                }],
+            },
+            {
+                "type": "table",
+                "align": ["right", null],
+                "children": [
+                    {
+                        "type": "tableRow",
+                        "children": [
+                            { "type": "tableCell", "children": [{ "type": "text", "value": "a" }] },
+                            { "type": "tableCell", "children": [{ "type": "text", "value": "b" }] },
+                        ],
+                    },
+                ],
+            },
+            {
+                "type": "definition",
+                "identifier": "docs",
+                "label": "Docs",
+                "url": "/docs",
+                "title": null,
+            },
+            {
+                "type": "definition",
+                "identifier": "logo",
+                "label": "logo",
+                "url": "/logo.png",
+                "title": "The logo",
+            },
+        ],
+        "position": position,
+    });
+    assert_eq!(
+        "## Hello\n\nSee [Docs], ![Logo](/logo.png \"The logo\") and www.example.com.\n\n 7. [ ] item\n\n|  a | b |\n| -: | - |\n\n[Docs]: /docs\n\n[logo]: /logo.png \"The logo\"",
+        markdown(mdast)
+    );
+}
+
+#[test]
+fn import_directive_and_wiki_link() {
+    let mdast = json!({
+        "type": "root",
+        "children": [{
+            "type": "containerDirective",
+            "name": "tip",
+            "attributes": { "open": null },
+            "children": [{
+                "type": "paragraph",
+                "children": [
+                    { "type": "wikiLink", "value": "Page", "data": { "alias": "Page" } },
+                    { "type": "text", "value": " and " },
+                    { "type": "footnote", "children": [{ "type": "text", "value": "legacy" }] },
+                ],
+            }],
+        }],
+    });
+    let doc = from_mdast(&mdast).unwrap();
+    let Block::ContainerDirective(directive) = &doc.blocks[0] else {
+        panic!("expected a container directive: {doc:?}");
+    };
+    assert_eq!("tip", directive.name);
+    assert!(directive.label.is_empty());
+    assert_eq!(
+        vec![("open".to_owned(), String::new())],
+        directive.attributes
+    );
+    assert_eq!(
+        vec![Block::Paragraph(vec![
+            Inline::WikiLink(WikiLink {
+                target: "Page".to_owned(),
+                fragment: None,
+                alias: None,
+                embed: false,
+            }),
+            Inline::Text(" and ".to_owned()),
+            Inline::InlineFootnote(vec![Inline::Text("legacy".to_owned())]),
+        ])],
+        directive.blocks
+    );
+}
+
+#[test]
+fn import_unknown_nodes() {
+    let mdast = json!({
+        "type": "root",
+        "children": [
+            { "type": "yaml", "value": "title: Notes" },
+            { "type": "toml", "value": "title = \"Notes\"" },
+            { "type": "math", "value": "x^2" },
+            {
+                "type": "paragraph",
+                "children": [
+                    { "type": "text", "value": "Area " },
+                    { "type": "inlineMath", "value": "\\pi r^2" },
+                    { "type": "mdxJsxTextElement", "name": "Badge", "children": [] },
+                ],
+            },
+        ],
+    });
+    assert_eq!(
+        Document {
+            blocks: vec![
+                Block::HtmlBlock("x^2".to_owned()),
+                Block::Paragraph(vec![
+                    Inline::Text("Area ".to_owned()),
+                    Inline::Html("\\pi r^2".to_owned()),
+                ]),
+            ]
+        },
+        from_mdast(&mdast).unwrap()
+    );
+}
+
+#[rstest]
+#[case(json!({ "type": "paragraph" }), "invalid mdast: expected `root`, found `paragraph`")]
+#[case(json!({ "children": [] }), "invalid mdast: node without `type`")]
+#[case(
+    json!({ "type": "root", "children": [{ "type": "text", "value": "loose" }] }),
+    "invalid mdast: unsupported block node `text`"
+)]
+#[case(
+    json!({ "type": "root", "children": [{ "type": "heading", "depth": 7, "children": [] }] }),
+    "invalid mdast: `heading` without a `depth` from 1 to 6"
+)]
+#[case(
+    json!({ "type": "root", "children": [{ "type": "code", "value": 1 }] }),
+    "invalid mdast: `value` of `code` is not a string"
+)]
+#[case(
+    json!({ "type": "root", "children": [{ "type": "html" }] }),
+    "invalid mdast: `html` without `value`"
+)]
+#[case(
+    json!({ "type": "root", "children": [{ "type": "list", "children": [{ "type": "paragraph" }] }] }),
+    "invalid mdast: `paragraph` in `list`"
+)]
+#[case(
+    json!({ "type": "root", "children": { "type": "text" } }),
+    "invalid mdast: `children` of `root` is not an array"
+)]
+fn import_errors(#[case] mdast: serde_json::Value, #[case] expected: &str) {
+    assert_eq!(expected, from_mdast(&mdast).unwrap_err().to_string());
+}
@@ -0,0 +1,99 @@
+use crate::ast::{ContainerDirective, Directive};
+use crate::parser::directive_util::directive;
+use crate::parser::util::*;
+use crate::parser::MarkdownParserState;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, space0},
+    combinator::recognize,
+    multi::{many0, many_m_n},
+    sequence::{delimited, preceded, terminated},
+    IResult, Parser,
+};
+
+/// Parse leaf directives in format:
+///      ::name[label]{attributes}
+pub(crate) fn leaf_directive<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Directive> {
+    move |input: &'a str| {
+        line_terminated(delimited(
+            (many_m_n(0, 3, char(' ')), tag("::")),
+            directive(state.clone()),
+            space0,
+        ))
+        .parse(input)
+    }
+}
+
+/// Parse container directives in format:
+///      :::name[label]{attributes}
+///      content
+///      :::
+///
+/// The closing fence must be at least as long as the opening one. Nested containers
+/// may use fences of any length. Without a closing fence, the container ends with
+/// its parent.
+pub(crate) fn container_directive<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, ContainerDirective> {
+    move |input: &'a str| {
+        let (mut input, (fence, directive)) = line_terminated((
+            preceded(many_m_n(0, 3, char(' ')), fence),
+            terminated(directive(state.clone()), space0),
+        ))
+        .parse(input)?;
+
+        let mut lines = Vec::new();
+        let mut depth = 0;
+        loop {
+            // Like a fenced code block, an unclosed container runs to the end of
+            // its parent
+            if input.is_empty() {
+                break;
+            }
+            if let Ok((tail, closing)) = closing_fence(input) {
+                if depth == 0 && closing.len() >= fence.len() {
+                    input = tail;
+                    break;
+                }
+                depth = usize::saturating_sub(depth, 1);
+            } else if opening_fence(state.clone()).parse(input).is_ok() {
+                depth += 1;
+            }
+            let (tail, line) = line_terminated(not_eof_or_eol0).parse(input)?;
+            lines.push(line);
+            input = tail;
+        }
+
+        let content = lines.join("\n");
+        let (_, blocks) = many0(crate::parser::blocks::block(state.clone()))
+            .parse(&content)
+            .map_err(|err| err.map_input(|_| input))?;
+
+        let container = ContainerDirective {
+            name: directive.name,
+            label: directive.label,
+            attributes: directive.attributes,
+            blocks,
+        };
+
+        Ok((input, container))
+    }
+}
+
+fn fence(input: &str) -> IResult<&str, &str> {
+    recognize(many_m_n(3, usize::MAX, char(':'))).parse(input)
+}
+
+fn opening_fence<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Directive> {
+    move |input: &'a str| {
+        preceded((many_m_n(0, 3, char(' ')), fence), directive(state.clone())).parse(input)
+    }
+}
+
+fn closing_fence(input: &str) -> IResult<&str, &str> {
+    line_terminated(delimited(many_m_n(0, 3, char(' ')), fence, space0)).parse(input)
+}
@@ -1,5 +1,6 @@
 mod blockquote;
 mod code_block;
+mod directive;
 mod footnote_definition;
 mod heading;
 mod html_block;
@@ -72,6 +73,20 @@ pub(crate) fn block<'a>(
                         |s| Block::HtmlBlock(s.to_owned()),
                     ),
                 ),
+                conditional_block(
+                    state.config.block_container_directive_behavior.clone(),
+                    map(
+                        crate::parser::blocks::directive::container_directive(state.clone()),
+                        Block::ContainerDirective,
+                    ),
+                ),
+                conditional_block(
+                    state.config.block_leaf_directive_behavior.clone(),
+                    map(
+                        crate::parser::blocks::directive::leaf_directive(state.clone()),
+                        Block::LeafDirective,
+                    ),
+                ),
                 // Alway try before link definition
                 conditional_block(
                     state.config.block_footnote_definition_behavior.clone(),
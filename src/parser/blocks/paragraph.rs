@@ -94,6 +94,20 @@ pub(crate) fn is_paragraph_line_start<'a>(
                     crate::parser::blocks::html_block::html_block(state.clone()),
                 ),
             ),
+            conditional_block_unit(
+                state.config.block_container_directive_behavior.clone(),
+                value(
+                    (),
+                    crate::parser::blocks::directive::container_directive(state.clone()),
+                ),
+            ),
+            conditional_block_unit(
+                state.config.block_leaf_directive_behavior.clone(),
+                value(
+                    (),
+                    crate::parser::blocks::directive::leaf_directive(state.clone()),
+                ),
+            ),
             conditional_block_unit(
                 state.config.block_link_definition_behavior.clone(),
                 value(
@@ -0,0 +1,222 @@
+use crate::ast::*;
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn state() -> MarkdownParserState {
+    MarkdownParserState::with_config(
+        MarkdownParserConfig::default()
+            .with_block_container_directive_behavior(ElementBehavior::Parse)
+            .with_block_leaf_directive_behavior(ElementBehavior::Parse)
+            .with_inline_text_directive_behavior(ElementBehavior::Parse),
+    )
+}
+
+#[test]
+fn leaf_directive1() {
+    let doc = parse_markdown(
+        state(),
+        "::youtube[Video of a **cat**]{#cat-video .big .wide vid=7cNKRGQhXNc autoplay}",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::LeafDirective(Directive {
+                name: "youtube".to_owned(),
+                label: vec![
+                    Inline::Text("Video of a ".to_owned()),
+                    Inline::Strong(vec![Inline::Text("cat".to_owned())]),
+                ],
+                attributes: vec![
+                    ("id".to_owned(), "cat-video".to_owned()),
+                    ("class".to_owned(), "big wide".to_owned()),
+                    ("vid".to_owned(), "7cNKRGQhXNc".to_owned()),
+                    ("autoplay".to_owned(), String::new()),
+                ],
+            })]
+        }
+    );
+}
+
+#[test]
+fn leaf_directive2() {
+    let doc = parse_markdown(state(), "::toc\n\nText").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::LeafDirective(Directive {
+                    name: "toc".to_owned(),
+                    label: vec![],
+                    attributes: vec![],
+                }),
+                Block::Paragraph(vec![Inline::Text("Text".to_owned())]),
+            ]
+        }
+    );
+}
+
+#[test]
+fn container_directive1() {
+    let doc = parse_markdown(
+        state(),
+        ":::note[Heads up]{title='A \"quoted\" title'}\nSome *text*.\n\n- item\n:::\n\nAfter",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::ContainerDirective(ContainerDirective {
+                    name: "note".to_owned(),
+                    label: vec![Inline::Text("Heads up".to_owned())],
+                    attributes: vec![("title".to_owned(), "A \"quoted\" title".to_owned())],
+                    blocks: vec![
+                        Block::Paragraph(vec![
+                            Inline::Text("Some ".to_owned()),
+                            Inline::Emphasis(vec![Inline::Text("text".to_owned())]),
+                            Inline::Text(".".to_owned()),
+                        ]),
+                        Block::List(List {
+                            kind: ListKind::Bullet(ListBulletKind::Dash),
+                            items: vec![ListItem {
+                                task: None,
+                                blocks: vec![Block::Paragraph(vec![Inline::Text(
+                                    "item".to_owned()
+                                )])],
+                            }],
+                        }),
+                    ],
+                }),
+                Block::Paragraph(vec![Inline::Text("After".to_owned())]),
+            ]
+        }
+    );
+}
+
+#[test]
+fn container_directive2() {
+    // Nested containers, the outer one uses a longer fence
+    let doc = parse_markdown(state(), "::::outer\n:::inner\nText\n:::\n::::").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::ContainerDirective(ContainerDirective {
+                name: "outer".to_owned(),
+                label: vec![],
+                attributes: vec![],
+                blocks: vec![Block::ContainerDirective(ContainerDirective {
+                    name: "inner".to_owned(),
+                    label: vec![],
+                    attributes: vec![],
+                    blocks: vec![Block::Paragraph(vec![Inline::Text("Text".to_owned())])],
+                })],
+            })]
+        }
+    );
+}
+
+#[test]
+fn container_directive3() {
+    // Nested containers with equal fences
+    let doc = parse_markdown(state(), ":::outer\n:::inner\nText\n:::\n:::").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::ContainerDirective(ContainerDirective {
+                name: "outer".to_owned(),
+                label: vec![],
+                attributes: vec![],
+                blocks: vec![Block::ContainerDirective(ContainerDirective {
+                    name: "inner".to_owned(),
+                    label: vec![],
+                    attributes: vec![],
+                    blocks: vec![Block::Paragraph(vec![Inline::Text("Text".to_owned())])],
+                })],
+            })]
+        }
+    );
+}
+
+#[test]
+fn container_directive_unclosed() {
+    let doc = parse_markdown(state(), "> :::note\n> Inside\n\nAfter\n\n:::tip\nText").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::BlockQuote(vec![Block::ContainerDirective(ContainerDirective {
+                    name: "note".to_owned(),
+                    label: vec![],
+                    attributes: vec![],
+                    blocks: vec![Block::Paragraph(vec![Inline::Text("Inside".to_owned())])],
+                })]),
+                Block::Paragraph(vec![Inline::Text("After".to_owned())]),
+                Block::ContainerDirective(ContainerDirective {
+                    name: "tip".to_owned(),
+                    label: vec![],
+                    attributes: vec![],
+                    blocks: vec![Block::Paragraph(vec![Inline::Text("Text".to_owned())])],
+                }),
+            ]
+        }
+    );
+}
+
+#[test]
+fn text_directive1() {
+    let doc = parse_markdown(state(), "Press :kbd[Ctrl]{.key} to copy, or see :abbr.").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Press ".to_owned()),
+                Inline::TextDirective(Directive {
+                    name: "kbd".to_owned(),
+                    label: vec![Inline::Text("Ctrl".to_owned())],
+                    attributes: vec![("class".to_owned(), "key".to_owned())],
+                }),
+                Inline::Text(" to copy, or see ".to_owned()),
+                Inline::TextDirective(Directive {
+                    name: "abbr".to_owned(),
+                    label: vec![],
+                    attributes: vec![],
+                }),
+                Inline::Text(".".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn text_directive2() {
+    // Not at a word start
+    let doc = parse_markdown(state(), "Time 10:30 and https://example.com").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Time 10:30 and ".to_owned()),
+                Inline::Autolink("https://example.com".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn directives_ignored_by_default() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "::toc\n\n:::note\nText\n:::",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![
+                Block::Paragraph(vec![Inline::Text("::toc".to_owned())]),
+                Block::Paragraph(vec![Inline::Text(":::note\nText\n:::".to_owned())]),
+            ]
+        }
+    );
+}
@@ -1,6 +1,7 @@
 mod blockquote;
 mod code_block;
 mod custom_parser;
+mod directive;
 mod footnote_definition;
 mod heading;
 mod html_block;
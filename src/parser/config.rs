@@ -3,14 +3,14 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 
 /// Function type for mapping elements.
-type ElementMapFn<ELT> = crate::Xrc<RefCell<Box<dyn FnMut(ELT) -> ELT>>>;
+pub type ElementMapFn<ELT> = crate::Xrc<RefCell<Box<dyn FnMut(ELT) -> ELT>>>;
 
 /// Function type for custom block parsers.
-type CustomBlockParserFn =
+pub type CustomBlockParserFn =
     crate::Xrc<RefCell<Box<dyn for<'a> FnMut(&'a str) -> IResult<&'a str, crate::ast::Block>>>>;
 
 /// Function type for custom inline parsers.
-type CustomInlineParserFn =
+pub type CustomInlineParserFn =
     crate::Xrc<RefCell<Box<dyn for<'a> FnMut(&'a str) -> IResult<&'a str, crate::ast::Inline>>>>;
 
 /// Behavior of the parser when encountering certain elements.
@@ -72,18 +72,38 @@ pub struct MarkdownParserConfig {
     /// The behavior of the parser when encountering block paragraphs.
     pub(crate) block_paragraph_behavior: ElementBehavior<crate::ast::Block>,
 
+    /// The behavior of the parser when encountering container directives (e.g.,
+    /// `:::name` … `:::`). Ignored by default.
+    pub(crate) block_container_directive_behavior: ElementBehavior<crate::ast::Block>,
+
+    /// The behavior of the parser when encountering leaf directives (e.g., `::name[label]`).
+    /// Ignored by default.
+    pub(crate) block_leaf_directive_behavior: ElementBehavior<crate::ast::Block>,
+
     /// The behavior of the parser when encountering inline autolinks.
     pub(crate) inline_autolink_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// The behavior of the parser when encountering GFM extended autolinks: bare URLs,
+    /// `www.` domains and email addresses.
+    pub(crate) inline_extended_autolink_behavior: ElementBehavior<crate::ast::Inline>,
+
     /// The behavior of the parser when encountering inline links.
     pub(crate) inline_link_behavior: ElementBehavior<crate::ast::Inline>,
 
     /// The behavior of the parser when encountering inline footnote references.
     pub(crate) inline_footnote_reference_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// The behavior of the parser when encountering inline footnotes (e.g., `^[note]`).
+    /// Ignored by default.
+    pub(crate) inline_inline_footnote_behavior: ElementBehavior<crate::ast::Inline>,
+
     /// The behavior of the parser when encountering inline reference links.
     pub(crate) inline_reference_link_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// The behavior of the parser when encountering wiki-links and embeds (e.g.,
+    /// `[[Page|label]]`, `![[file]]`). Ignored by default.
+    pub(crate) inline_wiki_link_behavior: ElementBehavior<crate::ast::Inline>,
+
     /// The behavior of the parser when encountering inline hard newlines.
     pub(crate) inline_hard_newline_behavior: ElementBehavior<crate::ast::Inline>,
 
@@ -99,6 +119,10 @@ pub struct MarkdownParserConfig {
     /// The behavior of the parser when encountering inline strikethrough.
     pub(crate) inline_strikethrough_behavior: ElementBehavior<crate::ast::Inline>,
 
+    /// The behavior of the parser when encountering text directives (e.g., `:name[label]`).
+    /// Ignored by default.
+    pub(crate) inline_text_directive_behavior: ElementBehavior<crate::ast::Inline>,
+
     /// The behavior of the parser when encountering inline text.
     pub(crate) inline_text_behavior: ElementBehavior<crate::ast::Inline>,
 
@@ -107,6 +131,13 @@ pub struct MarkdownParserConfig {
 
     /// A custom parser for inlines. This is a function that takes a string and returns a `Inline`.
     pub(crate) custom_inline_parser: Option<CustomInlineParserFn>,
+
+    /// Whether the custom parser for inlines is tried at the start of every word,
+    /// including inside text, instead of only where other inlines end.
+    pub(crate) custom_inline_parser_at_word_starts: bool,
+
+    /// If set, smart punctuation is applied to the parsed document.
+    pub(crate) smart_punctuation: Option<crate::ast::typographer::TypographerConfig>,
 }
 
 impl Default for MarkdownParserConfig {
@@ -125,18 +156,26 @@ impl Default for MarkdownParserConfig {
             block_link_definition_behavior: ElementBehavior::Parse,
             block_table_behavior: ElementBehavior::Parse,
             block_paragraph_behavior: ElementBehavior::Parse,
+            block_container_directive_behavior: ElementBehavior::Ignore,
+            block_leaf_directive_behavior: ElementBehavior::Ignore,
             inline_autolink_behavior: ElementBehavior::Parse,
+            inline_extended_autolink_behavior: ElementBehavior::Parse,
             inline_link_behavior: ElementBehavior::Parse,
             inline_footnote_reference_behavior: ElementBehavior::Parse,
+            inline_inline_footnote_behavior: ElementBehavior::Ignore,
             inline_reference_link_behavior: ElementBehavior::Parse,
+            inline_wiki_link_behavior: ElementBehavior::Ignore,
             inline_hard_newline_behavior: ElementBehavior::Parse,
             inline_image_behavior: ElementBehavior::Parse,
             inline_code_span_behavior: ElementBehavior::Parse,
             inline_emphasis_behavior: ElementBehavior::Parse,
             inline_strikethrough_behavior: ElementBehavior::Parse,
+            inline_text_directive_behavior: ElementBehavior::Ignore,
             inline_text_behavior: ElementBehavior::Parse,
             custom_block_parser: None,
             custom_inline_parser: None,
+            custom_inline_parser_at_word_starts: false,
+            smart_punctuation: None,
         }
     }
 }
@@ -158,6 +197,18 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Apply smart punctuation (typographic quotes, dashes and ellipses) to the text of
+    /// the parsed document. See [`crate::ast::typographer`].
+    pub fn with_smart_punctuation(
+        self,
+        config: crate::ast::typographer::TypographerConfig,
+    ) -> Self {
+        Self {
+            smart_punctuation: Some(config),
+            ..self
+        }
+    }
+
     /// Set a custom map of HTML entities.
     pub fn with_html_entities_map(
         self,
@@ -284,6 +335,30 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set the behavior of the parser when encountering container directives (e.g.,
+    /// `:::name` … `:::`). Directives are ignored by default.
+    pub fn with_block_container_directive_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Block>,
+    ) -> Self {
+        Self {
+            block_container_directive_behavior: behavior,
+            ..self
+        }
+    }
+
+    /// Set the behavior of the parser when encountering leaf directives (e.g.,
+    /// `::name[label]`). Directives are ignored by default.
+    pub fn with_block_leaf_directive_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Block>,
+    ) -> Self {
+        Self {
+            block_leaf_directive_behavior: behavior,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline autolinks.
     pub fn with_inline_autolink_behavior(
         self,
@@ -295,6 +370,19 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set the behavior of the parser when encountering GFM extended autolinks: bare URLs,
+    /// `www.` domains and email addresses. They are parsed by default, like on GitHub;
+    /// set [`ElementBehavior::Ignore`] to keep them as text.
+    pub fn with_inline_extended_autolink_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_extended_autolink_behavior: behavior,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline links.
     pub fn with_inline_link_behavior(self, behavior: ElementBehavior<crate::ast::Inline>) -> Self {
         Self {
@@ -314,6 +402,18 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set the behavior of the parser when encountering inline footnotes (e.g., `^[note]`).
+    /// Inline footnotes are ignored by default.
+    pub fn with_inline_inline_footnote_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_inline_footnote_behavior: behavior,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline reference links.
     pub fn with_inline_reference_link_behavior(
         self,
@@ -325,6 +425,18 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set the behavior of the parser when encountering wiki-links and embeds (e.g.,
+    /// `[[Page|label]]`, `![[file]]`). Wiki-links are ignored by default.
+    pub fn with_inline_wiki_link_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_wiki_link_behavior: behavior,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline hard newlines.
     pub fn with_inline_hard_newline_behavior(
         self,
@@ -377,6 +489,18 @@ impl MarkdownParserConfig {
         }
     }
 
+    /// Set the behavior of the parser when encountering text directives (e.g.,
+    /// `:name[label]`). Directives are ignored by default.
+    pub fn with_inline_text_directive_behavior(
+        self,
+        behavior: ElementBehavior<crate::ast::Inline>,
+    ) -> Self {
+        Self {
+            inline_text_directive_behavior: behavior,
+            ..self
+        }
+    }
+
     /// Set the behavior of the parser when encountering inline text.
     pub fn with_inline_text_behavior(self, behavior: ElementBehavior<crate::ast::Inline>) -> Self {
         Self {
@@ -397,6 +521,18 @@ impl MarkdownParserConfig {
     pub fn with_custom_inline_parser(self, parser: CustomInlineParserFn) -> Self {
         Self {
             custom_inline_parser: Some(parser),
+            custom_inline_parser_at_word_starts: false,
+            ..self
+        }
+    }
+
+    /// Set a custom parser for inlines which is tried only at the start of a word,
+    /// including words inside text, such as
+    /// [`mention_parser`](crate::parser::mention::mention_parser).
+    pub fn with_custom_inline_parser_at_word_starts(self, parser: CustomInlineParserFn) -> Self {
+        Self {
+            custom_inline_parser: Some(parser),
+            custom_inline_parser_at_word_starts: true,
             ..self
         }
     }
@@ -0,0 +1,129 @@
+use crate::ast::{Directive, Inline};
+use crate::parser::MarkdownParserState;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::{char, multispace0, none_of, satisfy},
+    combinator::{map, opt, recognize, value},
+    multi::{fold_many0, many0},
+    sequence::{delimited, pair, preceded, separated_pair},
+    IResult, Parser,
+};
+
+/// name[label]{attributes}, both label and attributes are optional
+pub(crate) fn directive<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Directive> {
+    move |input: &'a str| {
+        let (input, (name, label, attributes)) = (
+            directive_name,
+            opt(directive_label(state.clone())),
+            opt(directive_attributes),
+        )
+            .parse(input)?;
+
+        let directive = Directive {
+            name: name.to_owned(),
+            label: label.unwrap_or_default(),
+            attributes: attributes.unwrap_or_default(),
+        };
+
+        Ok((input, directive))
+    }
+}
+
+/// name: [a-zA-Z][a-zA-Z0-9_-]*
+fn directive_name(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        satisfy(|c| c.is_ascii_alphabetic()),
+        take_while(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+    ))
+    .parse(input)
+}
+
+fn directive_label<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>> {
+    move |input: &'a str| {
+        alt((
+            value(Vec::new(), tag("[]")),
+            crate::parser::link_util::link_label(state.clone()),
+        ))
+        .parse(input)
+    }
+}
+
+/// {#id .class key=value key="value" key='value' key}
+fn directive_attributes(input: &str) -> IResult<&str, Vec<(String, String)>> {
+    let (input, attributes) = delimited(
+        char('{'),
+        many0(preceded(multispace0, attribute)),
+        preceded(multispace0, char('}')),
+    )
+    .parse(input)?;
+
+    let mut result: Vec<(String, String)> = Vec::new();
+    for (key, value) in attributes {
+        match result.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) if key == "class" => {
+                existing.push(' ');
+                existing.push_str(&value);
+            }
+            Some((_, existing)) => *existing = value,
+            None => result.push((key, value)),
+        }
+    }
+
+    Ok((input, result))
+}
+
+fn attribute(input: &str) -> IResult<&str, (String, String)> {
+    alt((
+        map(preceded(char('#'), attribute_shortcut), |v: &str| {
+            ("id".to_owned(), v.to_owned())
+        }),
+        map(preceded(char('.'), attribute_shortcut), |v: &str| {
+            ("class".to_owned(), v.to_owned())
+        }),
+        map(
+            separated_pair(attribute_key, char('='), attribute_value),
+            |(k, v): (&str, String)| (k.to_owned(), v),
+        ),
+        map(attribute_key, |k: &str| (k.to_owned(), String::new())),
+    ))
+    .parse(input)
+}
+
+fn attribute_shortcut(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| !c.is_whitespace() && !"{}#.\"'=<>`".contains(c)).parse(input)
+}
+
+fn attribute_key(input: &str) -> IResult<&str, &str> {
+    recognize(pair(
+        satisfy(|c| c.is_ascii_alphabetic() || c == '_' || c == ':'),
+        take_while(|c: char| c.is_ascii_alphanumeric() || "_:.-".contains(c)),
+    ))
+    .parse(input)
+}
+
+fn attribute_value(input: &str) -> IResult<&str, String> {
+    alt((
+        delimited(char('"'), quoted_value('"'), char('"')),
+        delimited(char('\''), quoted_value('\''), char('\'')),
+        map(
+            take_while1(|c: char| !c.is_whitespace() && !"{}\"'=<>`".contains(c)),
+            |v: &str| v.to_owned(),
+        ),
+    ))
+    .parse(input)
+}
+
+fn quoted_value(end_delim: char) -> impl FnMut(&str) -> IResult<&str, String> {
+    move |input: &str| {
+        fold_many0(none_of(&[end_delim][..]), String::new, |mut acc, c| {
+            acc.push(c);
+            acc
+        })
+        .parse(input)
+    }
+}
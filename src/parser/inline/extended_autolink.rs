@@ -0,0 +1,170 @@
+use crate::parser::MarkdownParserState;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, tag_no_case, take_while, take_while1},
+    character::complete::char,
+    combinator::{recognize, verify},
+    sequence::{pair, preceded},
+    IResult, Parser,
+};
+
+/// GFM extended autolink: bare `http://`, `https://`, `ftp://` URLs, `www.`
+/// domains and email addresses. Must only be tried at the start of a word,
+/// see [`is_word_boundary`].
+/// Entities in the link are decoded.
+pub(crate) fn extended_autolink(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&str) -> IResult<&str, String> {
+    move |input: &str| {
+        let (rest, link) = alt((extended_url, extended_www, extended_email)).parse(input)?;
+        Ok((rest, decode_entities(state.clone(), &link)))
+    }
+}
+
+fn decode_entities(state: crate::Xrc<MarkdownParserState>, link: &str) -> String {
+    let mut result = String::with_capacity(link.len());
+    let mut rest = link;
+    while let Some(c) = rest.chars().next() {
+        match crate::parser::inline::html_entity::html_entity(state.clone())(rest) {
+            Ok((after, entity)) => {
+                result.push_str(&entity);
+                rest = after;
+            }
+            Err(_) => {
+                result.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    result
+}
+
+/// Characters after which an extended autolink may start.
+pub(crate) fn is_word_boundary(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '*' | '_' | '~' | '(')
+}
+
+/// url: ("http://" | "https://" | "ftp://") valid-domain path
+fn extended_url(input: &str) -> IResult<&str, String> {
+    let (_, candidate) = recognize((
+        alt((
+            tag_no_case("http://"),
+            tag_no_case("https://"),
+            tag_no_case("ftp://"),
+        )),
+        take_while1(is_domain_char),
+        take_while(is_path_char),
+    ))
+    .parse(input)?;
+
+    finish(input, candidate, |link| {
+        let host = &link[link.find("://").unwrap_or(0) + 3..];
+        is_valid_domain(host)
+    })
+}
+
+/// www: "www." valid-domain path
+fn extended_www(input: &str) -> IResult<&str, String> {
+    let (_, candidate) = recognize((
+        tag("www."),
+        take_while1(is_domain_char),
+        take_while(is_path_char),
+    ))
+    .parse(input)?;
+
+    finish(input, candidate, is_valid_domain)
+}
+
+/// email: [a-zA-Z0-9.+_-]+ "@" [a-zA-Z0-9_-]+ ("." [a-zA-Z0-9_-]+)+
+fn extended_email(input: &str) -> IResult<&str, String> {
+    let (_, candidate) = recognize(pair(
+        take_while1(|c: char| c.is_ascii_alphanumeric() || ".+_-".contains(c)),
+        preceded(
+            char('@'),
+            verify(take_while1(is_domain_char), |v: &str| {
+                v.chars().next().is_some_and(|c| c.is_ascii_alphanumeric())
+            }),
+        ),
+    ))
+    .parse(input)?;
+
+    let link = candidate.trim_end_matches('.');
+    let domain = &link[link.find('@').unwrap_or(0) + 1..];
+    if !domain.contains('.') || link.ends_with(['-', '_']) {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+
+    Ok((&input[link.len()..], link.to_owned()))
+}
+
+/// Trim trailing punctuation from `candidate`, validate the result and split the input.
+fn finish<'a>(
+    input: &'a str,
+    candidate: &'a str,
+    is_valid: impl Fn(&str) -> bool,
+) -> IResult<&'a str, String> {
+    let link = trim_trailing_punctuation(candidate);
+    if !is_valid(link) {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Verify,
+        )));
+    }
+
+    Ok((&input[link.len()..], link.to_owned()))
+}
+
+/// Apply the GFM rules for the end of an extended autolink: trailing
+/// punctuation is dropped, unbalanced closing parentheses are dropped and a
+/// trailing entity-like `&name;` is dropped.
+fn trim_trailing_punctuation(link: &str) -> &str {
+    let mut link = link;
+    loop {
+        match link.chars().last() {
+            Some('?' | '!' | '.' | ',' | ':' | '*' | '_' | '~') => {
+                link = &link[..link.len() - 1];
+            }
+            Some(')') if link.matches(')').count() > link.matches('(').count() => {
+                link = &link[..link.len() - 1];
+            }
+            Some(';') => {
+                let body = &link[..link.len() - 1];
+                match body.rfind('&') {
+                    Some(amp)
+                        if amp + 1 < body.len()
+                            && body[amp + 1..].chars().all(|c| c.is_ascii_alphanumeric()) =>
+                    {
+                        link = &link[..amp];
+                    }
+                    _ => return link,
+                }
+            }
+            _ => return link,
+        }
+    }
+}
+
+/// Valid domain: segments of alphanumerics, `_` and `-` separated by `.`,
+/// at least one `.` and no `_` in the last two segments.
+fn is_valid_domain(link: &str) -> bool {
+    let host_end = link
+        .find(|c: char| !is_domain_char(c))
+        .unwrap_or(link.len());
+    let host = link[..host_end].trim_end_matches('.');
+    let segments: Vec<&str> = host.split('.').collect();
+    if segments.len() < 2 || segments.iter().any(|s| s.is_empty()) {
+        return false;
+    }
+    !segments.iter().rev().take(2).any(|s| s.contains('_'))
+}
+
+fn is_domain_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.')
+}
+
+fn is_path_char(c: char) -> bool {
+    !c.is_whitespace() && c != '<'
+}
@@ -14,7 +14,9 @@ pub(crate) fn html_entity(
     move |input: &str| alt((html_entity_alpha(state.clone()), html_entity_numeric)).parse(input)
 }
 
-fn html_entity_alpha(state: crate::Xrc<MarkdownParserState>) -> impl FnMut(&str) -> IResult<&str, String> {
+fn html_entity_alpha(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&str) -> IResult<&str, String> {
     move |input: &str| {
         map_opt(recognize((char('&'), alpha1, char(';'))), |s: &str| {
             state
@@ -0,0 +1,51 @@
+use crate::ast::Inline;
+use crate::parser::MarkdownParserState;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, none_of},
+    combinator::{map, opt, verify},
+    multi::fold_many1,
+    sequence::delimited,
+    IResult, Parser,
+};
+
+// ^[note text]
+pub(crate) fn inline_footnote<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    move |input: &'a str| {
+        let (input, content) = delimited(
+            tag("^["),
+            verify(note_chars, |note: &str| !note.trim().is_empty()),
+            char(']'),
+        )
+        .parse(input)?;
+
+        let (_, content) = crate::parser::inline::inline_many1(state.clone())
+            .parse(content.as_str())
+            .map_err(|err| err.map_input(|_| input))?;
+
+        Ok((input, Inline::InlineFootnote(content)))
+    }
+}
+
+/// Note characters with balanced nested brackets, so the note may contain links and
+/// other inline footnotes.
+fn note_chars(input: &str) -> IResult<&str, String> {
+    fold_many1(
+        alt((
+            map(tag("\\]"), |_| "]".to_owned()),
+            map(delimited(char('['), opt(note_chars), char(']')), |inner| {
+                format!("[{}]", inner.unwrap_or_default())
+            }),
+            map(none_of("[]"), |c| c.to_string()),
+        )),
+        String::new,
+        |mut acc, item| {
+            acc.push_str(&item);
+            acc
+        },
+    )
+    .parse(input)
+}
@@ -1,5 +1,5 @@
 use crate::ast::Link;
-use crate::parser::link_util::{link_destination, link_label, link_title};
+use crate::parser::link_util::{link_destination, link_text, link_title};
 use crate::parser::MarkdownParserState;
 use nom::{
     character::complete::{char, multispace0},
@@ -13,7 +13,7 @@ pub(crate) fn inline_link<'a>(
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Link> {
     move |input: &'a str| {
         let (input, (children, (destination, title))) = (
-            link_label(state.clone()),
+            link_text(state.clone()),
             delimited(
                 char('('),
                 (
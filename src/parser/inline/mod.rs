@@ -1,14 +1,18 @@
 mod autolink;
 mod code_span;
 mod emphasis;
+mod extended_autolink;
 mod footnote_reference;
 mod hard_newline;
 mod html_entity;
 mod image;
+mod inline_footnote;
 mod inline_link;
 mod reference_link;
 mod strikethrough;
 mod text;
+mod text_directive;
+mod wiki_link;
 
 #[cfg(test)]
 mod tests;
@@ -18,26 +22,68 @@ use crate::parser::MarkdownParserState;
 use nom::{
     branch::alt,
     combinator::{fail, map},
-    multi::{many0, many1},
     IResult, Parser,
 };
 
 use super::util::conditional_inline;
+use crate::parser::config::ElementBehavior;
+use extended_autolink::is_word_boundary;
 
 pub(crate) fn inline_many0<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>> {
-    move |input: &'a str| many0(inline(state.clone())).parse(input)
+    move |input: &'a str| inline_sequence(state.clone(), input, 0)
 }
 
 pub(crate) fn inline_many1<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Inline>> {
-    move |input: &'a str| many1(inline(state.clone())).parse(input)
+    move |input: &'a str| inline_sequence(state.clone(), input, 1)
 }
 
-pub(crate) fn inline<'a>(
+/// At least `min` inlines. Constructs which may only start a word are tried only
+/// at the start of the sequence or after an inline ending with a word boundary.
+fn inline_sequence(
     state: crate::Xrc<MarkdownParserState>,
+    mut input: &str,
+    min: usize,
+) -> IResult<&str, Vec<Inline>> {
+    let mut inlines = Vec::new();
+    loop {
+        let word_start = inlines.last().is_none_or(ends_with_word_boundary);
+        match inline(state.clone(), word_start).parse(input) {
+            Ok((rest, inline)) if rest.len() < input.len() => {
+                inlines.push(inline);
+                input = rest;
+            }
+            Ok(_) | Err(nom::Err::Error(_)) => break,
+            Err(err) => return Err(err),
+        }
+    }
+    if inlines.len() < min {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Many1,
+        )));
+    }
+    Ok((input, inlines))
+}
+
+/// Whether the source of the inline ends with a character after which a word
+/// starts. Emphasis and strikethrough end with `*`, `_` or `~`.
+fn ends_with_word_boundary(inline: &Inline) -> bool {
+    match inline {
+        Inline::Text(text) => text.chars().last().is_some_and(is_word_boundary),
+        Inline::LineBreak | Inline::Emphasis(_) | Inline::Strong(_) | Inline::Strikethrough(_) => {
+            true
+        }
+        _ => false,
+    }
+}
+
+fn inline<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+    word_start: bool,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
     move |input: &'a str| {
         alt((
@@ -45,6 +91,15 @@ pub(crate) fn inline<'a>(
                 state.config.inline_autolink_behavior.clone(),
                 map(crate::parser::inline::autolink::autolink, Inline::Autolink),
             ),
+            // Always try before links, images and reference links
+            conditional_inline(
+                state.config.inline_wiki_link_behavior.clone(),
+                crate::parser::inline::wiki_link::wiki_link,
+            ),
+            conditional_inline(
+                state.config.inline_inline_footnote_behavior.clone(),
+                crate::parser::inline::inline_footnote::inline_footnote(state.clone()),
+            ),
             conditional_inline(
                 state.config.inline_link_behavior.clone(),
                 map(
@@ -80,7 +135,14 @@ pub(crate) fn inline<'a>(
                 state.config.inline_strikethrough_behavior.clone(),
                 crate::parser::inline::strikethrough::strikethrough(state.clone()),
             ),
-            custom_parser(state.clone()),
+            |input| match word_start {
+                true => word_start_inline(state.clone()).parse(input),
+                false => fail().parse(input),
+            },
+            |input| match state.config.custom_inline_parser_at_word_starts {
+                true => fail().parse(input),
+                false => custom_parser(state.clone()).parse(input),
+            },
             conditional_inline(
                 state.config.inline_text_behavior.clone(),
                 crate::parser::inline::text::text(state.clone()),
@@ -90,7 +152,40 @@ pub(crate) fn inline<'a>(
     }
 }
 
-fn custom_parser(state: crate::Xrc<MarkdownParserState>) -> impl FnMut(&str) -> IResult<&str, Inline> {
+/// Constructs which may only start a word, see [`is_word_boundary`].
+pub(crate) fn word_start_inline<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    move |input: &'a str| {
+        // Links may not be nested
+        let extended_autolink_behavior = match state.link_text {
+            true => ElementBehavior::Ignore,
+            false => state.config.inline_extended_autolink_behavior.clone(),
+        };
+        alt((
+            conditional_inline(
+                extended_autolink_behavior,
+                map(
+                    crate::parser::inline::extended_autolink::extended_autolink(state.clone()),
+                    Inline::Autolink,
+                ),
+            ),
+            conditional_inline(
+                state.config.inline_text_directive_behavior.clone(),
+                crate::parser::inline::text_directive::text_directive(state.clone()),
+            ),
+            |input| match state.config.custom_inline_parser_at_word_starts {
+                true => custom_parser(state.clone()).parse(input),
+                false => fail().parse(input),
+            },
+        ))
+        .parse(input)
+    }
+}
+
+pub(crate) fn custom_parser(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&str) -> IResult<&str, Inline> {
     move |input: &str| {
         if let Some(custom_parser) = state.config.custom_inline_parser.as_ref() {
             let mut p = (**custom_parser).borrow_mut();
@@ -1,5 +1,5 @@
 use crate::ast::{Inline, LinkReference};
-use crate::parser::link_util::link_label;
+use crate::parser::link_util::link_text;
 use crate::parser::MarkdownParserState;
 use nom::{branch::alt, bytes::complete::tag, sequence::terminated, IResult, Parser};
 
@@ -21,7 +21,7 @@ pub(crate) fn reference_link_full<'a>(
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
     move |input: &'a str| {
         let (input, (text, label)) =
-            (link_label(state.clone()), link_label(state.clone())).parse(input)?;
+            (link_text(state.clone()), link_text(state.clone())).parse(input)?;
         let link_reference = LinkReference { label, text };
         Ok((input, Inline::LinkReference(link_reference)))
     }
@@ -31,7 +31,7 @@ pub(crate) fn reference_link_collapsed<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
     move |input: &'a str| {
-        let (input, text) = terminated(link_label(state.clone()), tag("[]")).parse(input)?;
+        let (input, text) = terminated(link_text(state.clone()), tag("[]")).parse(input)?;
         let link_reference = LinkReference {
             label: text.clone(),
             text,
@@ -44,7 +44,7 @@ pub(crate) fn reference_link_shortcut<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
     move |input: &'a str| {
-        let (input, text) = link_label(state.clone()).parse(input)?;
+        let (input, text) = link_text(state.clone()).parse(input)?;
         let link_reference = LinkReference {
             label: text.clone(),
             text,
@@ -0,0 +1,227 @@
+use crate::ast::*;
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+#[test]
+fn extended_autolink1() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "Visit www.commonmark.org/help for more information.",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Visit ".to_owned()),
+                Inline::Autolink("www.commonmark.org/help".to_owned()),
+                Inline::Text(" for more information.".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink2() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "Visit www.commonmark.org/a.b.",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Visit ".to_owned()),
+                Inline::Autolink("www.commonmark.org/a.b".to_owned()),
+                Inline::Text(".".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink3() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "(www.google.com/search?q=Markup+(business))",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("(".to_owned()),
+                Inline::Autolink("www.google.com/search?q=Markup+(business)".to_owned()),
+                Inline::Text(")".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink4() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "www.google.com/search?q=commonmark&hl;",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Autolink("www.google.com/search?q=commonmark".to_owned()),
+                Inline::Text("&hl;".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink5() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "https://www.example.com/path, then foo@bar.example.com.",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Autolink("https://www.example.com/path".to_owned()),
+                Inline::Text(", then ".to_owned()),
+                Inline::Autolink("foo@bar.example.com".to_owned()),
+                Inline::Text(".".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink6() {
+    // Not at the start of a word, invalid domain and trailing `-` in an email
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "xhttps://example.com www.a_b.c_d a@b.c-",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "xhttps://example.com www.a_b.c_d a@b.c-".to_owned()
+            )])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink7() {
+    let doc = parse_markdown(MarkdownParserState::default(), "*see https://example.com*").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Emphasis(vec![
+                Inline::Text("see ".to_owned()),
+                Inline::Autolink("https://example.com".to_owned()),
+            ])])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink_ignore() {
+    let config = MarkdownParserConfig::default()
+        .with_inline_extended_autolink_behavior(ElementBehavior::Ignore);
+    let doc = parse_markdown(
+        MarkdownParserState::with_config(config),
+        "Visit www.commonmark.org",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "Visit www.commonmark.org".to_owned()
+            )])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink8() {
+    // Not after a code span or an entity, which do not end with a word boundary
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "`code`www.example.com &amp;www.example.com",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Code("code".to_owned()),
+                Inline::Text("www.example.com &www.example.com".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink9() {
+    // Links may not be nested
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "[see www.example.com](/x) and [www.example.com][ref]\n\n[ref]: /y",
+    )
+    .unwrap();
+    assert_eq!(
+        doc.blocks[0],
+        Block::Paragraph(vec![
+            Inline::Link(Link {
+                destination: "/x".to_owned(),
+                title: None,
+                children: vec![Inline::Text("see www.example.com".to_owned())],
+            }),
+            Inline::Text(" and ".to_owned()),
+            Inline::LinkReference(LinkReference {
+                label: vec![Inline::Text("ref".to_owned())],
+                text: vec![Inline::Text("www.example.com".to_owned())],
+            }),
+        ])
+    );
+}
+
+#[test]
+fn extended_autolink10() {
+    // Strikethrough ends with a word boundary
+    let doc = parse_markdown(MarkdownParserState::default(), "~~gone~~www.example.com").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Strikethrough(vec![Inline::Text("gone".to_owned())]),
+                Inline::Autolink("www.example.com".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn extended_autolink_entities() {
+    let doc = parse_markdown(
+        MarkdownParserState::default(),
+        "See https://example.com/?a=1&amp;b=2&#38;c=3&amp;.",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("See ".to_owned()),
+                Inline::Autolink("https://example.com/?a=1&b=2&c=3".to_owned()),
+                Inline::Text("&.".to_owned()),
+            ])]
+        }
+    );
+}
@@ -0,0 +1,75 @@
+use crate::ast::*;
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn state() -> MarkdownParserState {
+    MarkdownParserState::with_config(
+        MarkdownParserConfig::default()
+            .with_inline_inline_footnote_behavior(ElementBehavior::Parse),
+    )
+}
+
+#[test]
+fn inline_footnote1() {
+    let doc = parse_markdown(
+        state(),
+        "Text^[A note with *emphasis* and [a link](/url).] more",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Text".to_owned()),
+                Inline::InlineFootnote(vec![
+                    Inline::Text("A note with ".to_owned()),
+                    Inline::Emphasis(vec![Inline::Text("emphasis".to_owned())]),
+                    Inline::Text(" and ".to_owned()),
+                    Inline::Link(Link {
+                        destination: "/url".to_owned(),
+                        title: None,
+                        children: vec![Inline::Text("a link".to_owned())],
+                    }),
+                    Inline::Text(".".to_owned()),
+                ]),
+                Inline::Text(" more".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn inline_footnote2() {
+    let doc = parse_markdown(state(), "x^[outer ^[inner]] 2^3").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("x".to_owned()),
+                Inline::InlineFootnote(vec![
+                    Inline::Text("outer ".to_owned()),
+                    Inline::InlineFootnote(vec![Inline::Text("inner".to_owned())]),
+                ]),
+                Inline::Text(" 2^3".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn inline_footnote_ignore() {
+    // Not parsed by default
+    let doc = parse_markdown(MarkdownParserState::default(), "a^[b]").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("a^".to_owned()),
+                Inline::LinkReference(LinkReference {
+                    label: vec![Inline::Text("b".to_owned())],
+                    text: vec![Inline::Text("b".to_owned())],
+                }),
+            ])]
+        }
+    );
+}
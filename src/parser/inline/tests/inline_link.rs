@@ -45,3 +45,22 @@ fn inline_link3() {
         }
     );
 }
+
+#[test]
+fn inline_link_balanced_brackets() {
+    let doc = parse_markdown(MarkdownParserState::default(), "[a[^n] \\[b](/url)").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Link(Link {
+                destination: "/url".to_owned(),
+                title: None,
+                children: vec![
+                    Inline::Text("a".to_owned()),
+                    Inline::FootnoteReference("n".to_owned()),
+                    Inline::Text(" \\[b".to_owned()),
+                ]
+            })])]
+        }
+    );
+}
@@ -0,0 +1,157 @@
+use crate::ast::*;
+use crate::parser::config::MarkdownParserConfig;
+use crate::parser::mention::{mention_parser, MentionConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+use nom::Parser;
+
+fn state(config: MentionConfig) -> MarkdownParserState {
+    MarkdownParserState::with_config(
+        MarkdownParserConfig::default()
+            .with_custom_inline_parser_at_word_starts(mention_parser(config)),
+    )
+}
+
+#[test]
+fn mention1() {
+    let doc = parse_markdown(
+        state(MentionConfig::default()),
+        "Thanks @alice and @acme/core-team!",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Thanks ".to_owned()),
+                Inline::Mention(Mention::User("alice".to_owned())),
+                Inline::Text(" and ".to_owned()),
+                Inline::Mention(Mention::User("acme/core-team".to_owned())),
+                Inline::Text("!".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn mention2() {
+    let doc = parse_markdown(
+        state(MentionConfig::default()),
+        "Fixes #123 and acme/widgets#45 (see a5c3785ed8 and acme/widgets@0a1b2c3).",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("Fixes ".to_owned()),
+                Inline::Mention(Mention::Issue {
+                    repository: None,
+                    number: 123
+                }),
+                Inline::Text(" and ".to_owned()),
+                Inline::Mention(Mention::Issue {
+                    repository: Some("acme/widgets".to_owned()),
+                    number: 45
+                }),
+                Inline::Text(" (see ".to_owned()),
+                Inline::Mention(Mention::Commit {
+                    repository: None,
+                    sha: "a5c3785ed8".to_owned()
+                }),
+                Inline::Text(" and ".to_owned()),
+                Inline::Mention(Mention::Commit {
+                    repository: Some("acme/widgets".to_owned()),
+                    sha: "0a1b2c3".to_owned()
+                }),
+                Inline::Text(").".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn mention3() {
+    // Not at the start of a word, plain words and numbers, too short SHA
+    let doc = parse_markdown(
+        state(MentionConfig::default()),
+        "user@localhost page#1 defaced 1234567 abc123 #12a",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "user@localhost page#1 defaced 1234567 abc123 #12a".to_owned()
+            )])]
+        }
+    );
+}
+
+#[test]
+fn mention4() {
+    let doc = parse_markdown(
+        state(
+            MentionConfig::default()
+                .with_users(false)
+                .with_commits(false),
+        ),
+        "@alice #7 a5c3785",
+    )
+    .unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("@alice ".to_owned()),
+                Inline::Mention(Mention::Issue {
+                    repository: None,
+                    number: 7
+                }),
+                Inline::Text(" a5c3785".to_owned()),
+            ])]
+        }
+    );
+}
+
+fn custom(input: &str) -> nom::IResult<&str, Inline> {
+    nom::combinator::map(nom::bytes::complete::tag("@x"), |_| {
+        Inline::Html("<x>".to_owned())
+    })
+    .parse(input)
+}
+
+#[test]
+fn custom_inline_parser_not_at_word_start() {
+    // A custom inline parser is only tried where other inlines end
+    let config = MarkdownParserConfig::default()
+        .with_custom_inline_parser(crate::Xrc::new(std::cell::RefCell::new(Box::new(custom))));
+    let doc = parse_markdown(MarkdownParserState::with_config(config), "@x and @x").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Html("<x>".to_owned()),
+                Inline::Text(" and @x".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn custom_inline_parser_at_word_starts() {
+    let config = MarkdownParserConfig::default().with_custom_inline_parser_at_word_starts(
+        crate::Xrc::new(std::cell::RefCell::new(Box::new(custom))),
+    );
+    let doc = parse_markdown(MarkdownParserState::with_config(config), "@x and @x a@x").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Html("<x>".to_owned()),
+                Inline::Text(" and ".to_owned()),
+                Inline::Html("<x>".to_owned()),
+                Inline::Text(" a@x".to_owned()),
+            ])]
+        }
+    );
+}
@@ -1,10 +1,15 @@
 mod autolink;
 mod code_span;
 mod emphasis;
+mod extended_autolink;
 mod footnote_reference;
 mod hard_newline;
 mod html_entity;
 mod image;
+mod inline_footnote;
 mod inline_link;
+mod mention;
 mod reference_link;
+mod smart_punctuation;
 mod strikethrough;
+mod wiki_link;
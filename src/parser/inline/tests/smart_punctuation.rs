@@ -0,0 +1,80 @@
+use crate::ast::typographer::{smart_punctuation, Locale, TypographerConfig};
+use crate::ast::*;
+use crate::parser::config::MarkdownParserConfig;
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn parse(locale: Locale, input: &str) -> Document {
+    let config = MarkdownParserConfig::default()
+        .with_smart_punctuation(TypographerConfig::default().with_locale(locale));
+    parse_markdown(MarkdownParserState::with_config(config), input).unwrap()
+}
+
+#[test]
+fn smart_punctuation1() {
+    let doc = parse(
+        Locale::English,
+        "\"Hello,\" she said -- 'it's the '90s' --- and waited...",
+    );
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "“Hello,” she said – ‘it’s the ’90s’ — and waited…".to_owned()
+            )])]
+        }
+    );
+}
+
+#[test]
+fn smart_punctuation2() {
+    // Quotes are paired across emphasis, code spans are kept as is
+    let doc = parse(Locale::English, "\"*Quoted*\" and `\"code\" -- ...`'s");
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("“".to_owned()),
+                Inline::Emphasis(vec![Inline::Text("Quoted".to_owned())]),
+                Inline::Text("” and ".to_owned()),
+                Inline::Code("\"code\" -- ...".to_owned()),
+                Inline::Text("’s".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn smart_punctuation3() {
+    let doc = parse(Locale::German, "\"Das ist 'gut'\", sagte er.");
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "„Das ist ‚gut‘“, sagte er.".to_owned()
+            )])]
+        }
+    );
+}
+
+#[test]
+fn smart_punctuation4() {
+    let doc = parse(Locale::French, "Il a dit \"bonjour\" et 'salut'.");
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::Text(
+                "Il a dit «\u{a0}bonjour\u{a0}» et ‹\u{a0}salut\u{a0}›.".to_owned()
+            )])]
+        }
+    );
+}
+
+#[test]
+fn smart_punctuation5() {
+    // Code blocks, HTML and escaped characters are not changed
+    let input = "```\n\"a\" -- b\n```\n\n<div>\"a\" -- b</div>\n\nx \\\"a\\\" \\-- b";
+    let mut doc = parse_markdown(MarkdownParserState::default(), input).unwrap();
+    let expected = doc.clone();
+    smart_punctuation(&mut doc, &TypographerConfig::default());
+    assert_eq!(doc, expected);
+}
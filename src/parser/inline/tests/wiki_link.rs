@@ -0,0 +1,79 @@
+use crate::ast::*;
+use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+use crate::parser::{parse_markdown, MarkdownParserState};
+
+fn state() -> MarkdownParserState {
+    MarkdownParserState::with_config(
+        MarkdownParserConfig::default().with_inline_wiki_link_behavior(ElementBehavior::Parse),
+    )
+}
+
+#[test]
+fn wiki_link1() {
+    let doc = parse_markdown(state(), "See [[Page]].").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::Text("See ".to_owned()),
+                Inline::WikiLink(WikiLink {
+                    target: "Page".to_owned(),
+                    fragment: None,
+                    alias: None,
+                    embed: false,
+                }),
+                Inline::Text(".".to_owned()),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn wiki_link2() {
+    let doc = parse_markdown(state(), "[[Some Page#Intro|the intro]]").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![Inline::WikiLink(WikiLink {
+                target: "Some Page".to_owned(),
+                fragment: Some("Intro".to_owned()),
+                alias: Some("the intro".to_owned()),
+                embed: false,
+            })])]
+        }
+    );
+}
+
+#[test]
+fn wiki_link3() {
+    let doc = parse_markdown(state(), "![[diagram.png]] and [[#Heading]]").unwrap();
+    assert_eq!(
+        doc,
+        Document {
+            blocks: vec![Block::Paragraph(vec![
+                Inline::WikiLink(WikiLink {
+                    target: "diagram.png".to_owned(),
+                    fragment: None,
+                    alias: None,
+                    embed: true,
+                }),
+                Inline::Text(" and ".to_owned()),
+                Inline::WikiLink(WikiLink {
+                    target: "".to_owned(),
+                    fragment: Some("Heading".to_owned()),
+                    alias: None,
+                    embed: false,
+                }),
+            ])]
+        }
+    );
+}
+
+#[test]
+fn wiki_link_disabled_by_default() {
+    let doc = parse_markdown(MarkdownParserState::default(), "[[Page]]").unwrap();
+    assert!(!matches!(
+        &doc.blocks[0],
+        Block::Paragraph(inlines) if matches!(inlines[0], Inline::WikiLink(_))
+    ));
+}
@@ -1,10 +1,10 @@
+use crate::parser::inline::extended_autolink::is_word_boundary;
 use crate::parser::MarkdownParserState;
 use crate::{ast::Inline, parser::util::conditional_inline_unit};
 use nom::{
     branch::alt,
     character::complete::{anychar, char, one_of},
-    combinator::{map, not, peek, recognize, value},
-    multi::many1,
+    combinator::{not, peek, value},
     sequence::preceded,
     IResult, Parser,
 };
@@ -13,35 +13,93 @@ pub(crate) fn text<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
     move |input: &'a str| {
-        map(
-            many1(alt((
-                map(escaped_char, |c| c.to_string()),
-                map(
-                    crate::parser::inline::html_entity::html_entity(state.clone()),
-                    |c| c.to_string(),
-                ),
-                map(
-                    recognize(many1(preceded(peek(is_text(state.clone())), anychar))),
-                    |c| c.to_string(),
-                ),
-            ))),
-            |vec| Inline::Text(vec.join("")),
-        )
-        .parse(input)
+        let mut text = String::new();
+        let mut rest = input;
+        // Constructs starting a word at the beginning of the text have already been
+        // tried by the caller
+        let mut previous = None;
+        // Escapes are only decoded at the start of a run of text characters
+        let mut in_run = false;
+        loop {
+            if previous.is_some_and(is_word_boundary) && word_start(state.clone())(rest).is_ok() {
+                break;
+            }
+            if let Ok((after, c)) = escaped_char(rest) {
+                // Escapes inside a run are kept, but the escaped character never
+                // starts markup
+                if in_run {
+                    text.push('\\');
+                }
+                text.push(c);
+                previous = Some(c);
+                rest = after;
+                continue;
+            }
+            if let Ok((after, entity)) =
+                crate::parser::inline::html_entity::html_entity(state.clone())(rest)
+            {
+                text.push_str(&entity);
+                previous = entity.chars().last();
+                rest = after;
+                in_run = false;
+                continue;
+            }
+            match preceded(peek(is_text(state.clone())), anychar).parse(rest) {
+                Ok((after, c)) => {
+                    text.push(c);
+                    previous = Some(c);
+                    rest = after;
+                    in_run = true;
+                }
+                Err(nom::Err::Error(_)) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        if text.is_empty() {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Many1,
+            )));
+        }
+        Ok((rest, Inline::Text(text)))
+    }
+}
+
+/// Constructs that are recognized only at the beginning of a word.
+fn word_start<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    move |input: &'a str| {
+        value((), crate::parser::inline::word_start_inline(state.clone())).parse(input)
     }
 }
 
-fn is_text<'a>(state: crate::Xrc<MarkdownParserState>) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+fn is_text<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
     move |input: &'a str| not(not_a_text(state.clone())).parse(input)
 }
 
-fn not_a_text<'a>(state: crate::Xrc<MarkdownParserState>) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+fn not_a_text<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
     move |input: &'a str| {
         alt((
             conditional_inline_unit(
                 state.config.inline_autolink_behavior.clone(),
                 value((), crate::parser::inline::autolink::autolink),
             ),
+            conditional_inline_unit(
+                state.config.inline_wiki_link_behavior.clone(),
+                value((), crate::parser::inline::wiki_link::wiki_link),
+            ),
+            conditional_inline_unit(
+                state.config.inline_inline_footnote_behavior.clone(),
+                value(
+                    (),
+                    crate::parser::inline::inline_footnote::inline_footnote(state.clone()),
+                ),
+            ),
             conditional_inline_unit(
                 state.config.inline_reference_link_behavior.clone(),
                 value(
@@ -0,0 +1,17 @@
+use crate::ast::Inline;
+use crate::parser::directive_util::directive;
+use crate::parser::MarkdownParserState;
+use nom::{character::complete::char, combinator::map, sequence::preceded, IResult, Parser};
+
+// :name[label]{attributes}
+pub(crate) fn text_directive<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Inline> {
+    move |input: &'a str| {
+        map(
+            preceded(char(':'), directive(state.clone())),
+            Inline::TextDirective,
+        )
+        .parse(input)
+    }
+}
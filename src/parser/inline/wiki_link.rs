@@ -0,0 +1,39 @@
+use crate::ast::{Inline, WikiLink};
+use nom::{
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::char,
+    combinator::{opt, verify},
+    sequence::{delimited, preceded},
+    IResult, Parser,
+};
+
+// [[target#fragment|alias]] or ![[target#fragment|alias]]
+pub(crate) fn wiki_link(input: &str) -> IResult<&str, Inline> {
+    let (input, embed) = opt(char('!')).parse(input)?;
+
+    let (input, (target, fragment, alias)) = verify(
+        delimited(
+            tag("[["),
+            (
+                take_while(|c| !"]|#\n".contains(c)),
+                opt(preceded(char('#'), take_while1(|c| !"]|\n".contains(c)))),
+                opt(preceded(char('|'), take_while1(|c| !"]\n".contains(c)))),
+            ),
+            tag("]]"),
+        ),
+        |(target, fragment, _): &(&str, Option<&str>, Option<&str>)| {
+            !target.trim().is_empty() || fragment.is_some()
+        },
+    )
+    .parse(input)?;
+
+    Ok((
+        input,
+        Inline::WikiLink(WikiLink {
+            target: target.trim().to_owned(),
+            fragment: fragment.map(|v| v.trim().to_owned()),
+            alias: alias.map(|v| v.trim().to_owned()),
+            embed: embed.is_some(),
+        }),
+    ))
+}
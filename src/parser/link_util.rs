@@ -14,21 +14,42 @@ pub(crate) fn link_label<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<crate::ast::Inline>> {
     move |input: &'a str| {
-        delimited(tag("["), link_label_inner(state.clone()), tag("]")).parse(input)
+        delimited(
+            tag("["),
+            link_label_inner(state.clone(), unbracketed),
+            tag("]"),
+        )
+        .parse(input)
     }
 }
 
-fn link_label_inner<'a>(
+/// Link label which is the text of a link, where extended autolinks are not
+/// recognized and balanced brackets, such as footnote references, are allowed.
+pub(crate) fn link_text<'a>(
     state: crate::Xrc<MarkdownParserState>,
 ) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<crate::ast::Inline>> {
+    let state = crate::Xrc::new(MarkdownParserState {
+        config: state.config.clone(),
+        link_text: true,
+    });
     move |input: &'a str| {
-        let (input, label_chars) = verify(
-            many1(preceded(
-                peek(not(char(']'))),
-                alt((value(']', tag("\\]")), anychar)),
-            )),
-            |chars: &[char]| chars.iter().any(|&c| c != ' ' && c != '\n') && chars.len() < 1000,
+        delimited(
+            tag("["),
+            link_label_inner(state.clone(), balanced_brackets),
+            tag("]"),
         )
+        .parse(input)
+    }
+}
+
+fn link_label_inner<'a>(
+    state: crate::Xrc<MarkdownParserState>,
+    label_chars: fn(&str) -> IResult<&str, Vec<char>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<crate::ast::Inline>> {
+    move |input: &'a str| {
+        let (input, label_chars) = verify(label_chars, |chars: &[char]| {
+            chars.iter().any(|&c| c != ' ' && c != '\n') && chars.len() < 1000
+        })
         .parse(input)?;
 
         let label = label_chars.iter().collect::<String>();
@@ -41,6 +62,52 @@ fn link_label_inner<'a>(
     }
 }
 
+/// Characters up to the first `]`.
+fn unbracketed(input: &str) -> IResult<&str, Vec<char>> {
+    many1(preceded(
+        peek(not(char(']'))),
+        alt((value(']', tag("\\]")), anychar)),
+    ))
+    .parse(input)
+}
+
+/// Characters up to the first `]` which does not close a `[`.
+fn balanced_brackets(input: &str) -> IResult<&str, Vec<char>> {
+    let mut chars = Vec::new();
+    let mut depth = 0usize;
+    let mut rest = input;
+    loop {
+        if let Some(after) = rest.strip_prefix("\\]") {
+            chars.push(']');
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("\\[") {
+            chars.extend(['\\', '[']);
+            rest = after;
+            continue;
+        }
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => break,
+            ']' => depth -= 1,
+            _ => (),
+        }
+        chars.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    if chars.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::Many1,
+        )));
+    }
+    Ok((rest, chars))
+}
+
 pub(crate) fn link_title(input: &str) -> IResult<&str, String> {
     alt((
         link_title_double_quoted,
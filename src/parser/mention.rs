@@ -0,0 +1,192 @@
+//! Recognition of user mentions (`@user`, `@org/team`), issue and pull request
+//! references (`#123`, `org/repo#45`) and commit SHAs (`a5c3785`, `org/repo@a5c3785`).
+//!
+//! [`mention_parser`] is a custom inline parser producing [`Inline::Mention`] nodes.
+//! Like GitHub, references are only recognized at the start of a word, so
+//! `user@localhost` or `page#123` stay plain text, and the parser is set with
+//! [`with_custom_inline_parser_at_word_starts`](crate::parser::config::MarkdownParserConfig::with_custom_inline_parser_at_word_starts):
+//!
+//! ```rust
+//! use markdown_ppp::parser::config::MarkdownParserConfig;
+//! use markdown_ppp::parser::mention::{mention_parser, MentionConfig};
+//!
+//! let config = MarkdownParserConfig::default()
+//!     .with_custom_inline_parser_at_word_starts(mention_parser(MentionConfig::default()));
+//! ```
+
+use crate::ast::{Inline, Mention};
+use crate::parser::config::CustomInlineParserFn;
+use nom::{
+    branch::alt,
+    bytes::complete::{take_while, take_while1, take_while_m_n},
+    character::complete::{char, digit1, satisfy},
+    combinator::{map, map_res, not, opt, peek, recognize, verify},
+    sequence::{pair, preceded, terminated},
+    IResult, Parser,
+};
+use std::cell::RefCell;
+
+/// Which kinds of references are recognized.
+#[derive(Debug, Clone)]
+pub struct MentionConfig {
+    users: bool,
+    issues: bool,
+    commits: bool,
+    min_sha_length: usize,
+}
+
+impl Default for MentionConfig {
+    fn default() -> Self {
+        Self {
+            users: true,
+            issues: true,
+            commits: true,
+            min_sha_length: 7,
+        }
+    }
+}
+
+impl MentionConfig {
+    /// Enable or disable user and team mentions (`@user`, `@org/team`).
+    pub fn with_users(self, users: bool) -> Self {
+        Self { users, ..self }
+    }
+
+    /// Enable or disable issue and pull request references (`#123`, `org/repo#45`).
+    pub fn with_issues(self, issues: bool) -> Self {
+        Self { issues, ..self }
+    }
+
+    /// Enable or disable commit references (`a5c3785`, `org/repo@a5c3785`).
+    ///
+    /// A bare SHA must contain at least one digit and one letter, so that ordinary
+    /// words and numbers are not mistaken for commits.
+    pub fn with_commits(self, commits: bool) -> Self {
+        Self { commits, ..self }
+    }
+
+    /// Set the minimal number of hex digits in a commit SHA. Default is 7.
+    pub fn with_min_sha_length(self, min_sha_length: usize) -> Self {
+        Self {
+            min_sha_length: min_sha_length.clamp(1, 40),
+            ..self
+        }
+    }
+}
+
+/// Create a custom inline parser recognizing mentions and references with the given
+/// configuration.
+pub fn mention_parser(config: MentionConfig) -> CustomInlineParserFn {
+    crate::Xrc::new(RefCell::new(Box::new(move |input: &str| {
+        mention(&config, input)
+    })))
+}
+
+fn mention<'a>(config: &MentionConfig, input: &'a str) -> IResult<&'a str, Inline> {
+    map(
+        terminated(
+            alt((
+                |i| user(config, i),
+                |i| issue(config, i),
+                |i| commit(config, i),
+            )),
+            peek(not(satisfy(is_word_char))),
+        ),
+        Inline::Mention,
+    )
+    .parse(input)
+}
+
+/// @user or @org/team
+fn user<'a>(config: &MentionConfig, input: &'a str) -> IResult<&'a str, Mention> {
+    if !config.users {
+        return fail(input);
+    }
+    map(
+        preceded(
+            char('@'),
+            recognize(pair(owner, opt(pair(char('/'), owner)))),
+        ),
+        |name: &str| Mention::User(name.to_owned()),
+    )
+    .parse(input)
+}
+
+/// #123 or org/repo#123
+fn issue<'a>(config: &MentionConfig, input: &'a str) -> IResult<&'a str, Mention> {
+    if !config.issues {
+        return fail(input);
+    }
+    map(
+        pair(
+            opt(repository),
+            preceded(char('#'), map_res(digit1, |v: &str| v.parse::<u64>())),
+        ),
+        |(repository, number)| Mention::Issue {
+            repository: repository.map(|v| v.to_owned()),
+            number,
+        },
+    )
+    .parse(input)
+}
+
+/// a5c3785 or org/repo@a5c3785
+fn commit<'a>(config: &MentionConfig, input: &'a str) -> IResult<&'a str, Mention> {
+    if !config.commits {
+        return fail(input);
+    }
+    let sha = || take_while_m_n(config.min_sha_length, 40, |c: char| c.is_ascii_hexdigit());
+    alt((
+        map(
+            (repository, char('@'), sha()),
+            |(repository, _, sha): (&str, _, &str)| Mention::Commit {
+                repository: Some(repository.to_owned()),
+                sha: sha.to_owned(),
+            },
+        ),
+        map(
+            verify(sha(), |sha: &str| {
+                sha.chars().any(|c| c.is_ascii_digit())
+                    && sha.chars().any(|c| c.is_ascii_alphabetic())
+            }),
+            |sha: &str| Mention::Commit {
+                repository: None,
+                sha: sha.to_owned(),
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+/// org/repo
+fn repository(input: &str) -> IResult<&str, &str> {
+    recognize((
+        owner,
+        char('/'),
+        take_while1(|c: char| c.is_ascii_alphanumeric() || "-_.".contains(c)),
+    ))
+    .parse(input)
+}
+
+/// User or organization name: alphanumerics and single inner hyphens.
+fn owner(input: &str) -> IResult<&str, &str> {
+    verify(
+        recognize(pair(
+            satisfy(|c| c.is_ascii_alphanumeric()),
+            take_while(|c: char| c.is_ascii_alphanumeric() || c == '-'),
+        )),
+        |v: &str| !v.ends_with('-') && !v.contains("--"),
+    )
+    .parse(input)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '@' || c == '#' || c == '/'
+}
+
+fn fail(input: &str) -> IResult<&str, Mention> {
+    Err(nom::Err::Error(nom::error::Error::new(
+        input,
+        nom::error::ErrorKind::Fail,
+    )))
+}
@@ -1,7 +1,9 @@
 mod blocks;
 pub mod config;
+mod directive_util;
 mod inline;
 mod link_util;
+pub mod mention;
 mod util;
 
 use crate::ast::Document;
@@ -17,12 +19,16 @@ use nom::{
 
 pub struct MarkdownParserState {
     pub config: crate::Xrc<MarkdownParserConfig>,
+
+    /// Whether link text is being parsed, where links may not be nested.
+    pub(crate) link_text: bool,
 }
 
 impl MarkdownParserState {
     pub fn with_config(config: MarkdownParserConfig) -> Self {
         Self {
             config: crate::Xrc::new(config),
+            link_text: false,
         }
     }
 }
@@ -39,11 +45,17 @@ pub fn parse_markdown(
     input: &str,
 ) -> Result<Document, nom::Err<nom::error::Error<&str>>> {
     let empty_lines = many0(alt((space1, line_ending)));
+    let state = crate::Xrc::new(state);
     let mut parser = terminated(
-        many0(crate::parser::blocks::block(crate::Xrc::new(state))),
+        many0(crate::parser::blocks::block(state.clone())),
         (empty_lines, eof),
     );
     let (_, blocks) = parser.parse(input)?;
 
-    Ok(Document { blocks })
+    let mut document = Document { blocks };
+    if let Some(config) = &state.config.smart_punctuation {
+        crate::ast::typographer::smart_punctuation(&mut document, config);
+    }
+
+    Ok(document)
 }
@@ -89,6 +89,8 @@ impl<'a> ToDoc<'a> for Block {
 
             Block::Empty => arena.nil(),
             Block::Table(v) => v.to_doc(config, arena),
+            Block::ContainerDirective(v) => v.to_doc(config, arena),
+            Block::LeafDirective(v) => v.to_doc(config, arena),
             Block::FootnoteDefinition(def) => arena
                 .text(format!("[^{}]: ", def.label))
                 .append(def.blocks.to_doc(config, arena)),
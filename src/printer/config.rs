@@ -1,15 +1,31 @@
 pub struct Config {
     pub(crate) width: usize,
+    pub(crate) hoist_inline_footnotes: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self { width: 80 }
+        Self {
+            width: 80,
+            hoist_inline_footnotes: false,
+        }
     }
 }
 
 impl Config {
     pub fn with_width(width: usize) -> Self {
-        Self { width }
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    /// Print inline footnotes (`^[note]`) as references with `[^n]:` definitions at
+    /// the end of the document instead of keeping them inline. Disabled by default.
+    pub fn with_hoist_inline_footnotes(self, hoist_inline_footnotes: bool) -> Self {
+        Self {
+            hoist_inline_footnotes,
+            ..self
+        }
     }
 }
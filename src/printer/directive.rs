@@ -0,0 +1,113 @@
+use crate::ast::*;
+use crate::printer::{inline::ToDocInline, ToDoc};
+use pretty::{Arena, DocAllocator, DocBuilder};
+
+impl<'a> ToDoc<'a> for ContainerDirective {
+    fn to_doc(
+        &self,
+        config: crate::Xrc<crate::printer::config::Config>,
+        arena: &'a Arena<'a>,
+    ) -> DocBuilder<'a, Arena<'a>, ()> {
+        // Outer containers get longer fences, so that nested ones are unambiguous
+        let fence = ":".repeat(3 + nesting_depth(&self.blocks));
+        let mut doc = directive_to_doc(
+            &fence,
+            &self.name,
+            &self.label,
+            &self.attributes,
+            false,
+            arena,
+        );
+        if !self.blocks.is_empty() {
+            doc = doc
+                .append(arena.hardline())
+                .append(self.blocks.to_doc(config, arena));
+        }
+        doc.append(arena.hardline()).append(arena.text(fence))
+    }
+}
+
+impl<'a> ToDoc<'a> for Directive {
+    fn to_doc(
+        &self,
+        _config: crate::Xrc<crate::printer::config::Config>,
+        arena: &'a Arena<'a>,
+    ) -> DocBuilder<'a, Arena<'a>, ()> {
+        directive_to_doc(
+            "::",
+            &self.name,
+            &self.label,
+            &self.attributes,
+            false,
+            arena,
+        )
+    }
+}
+
+/// `<marker>name[label]{attributes}`
+pub(crate) fn directive_to_doc<'a>(
+    marker: &str,
+    name: &str,
+    label: &Vec<Inline>,
+    attributes: &[(String, String)],
+    allow_newlines: bool,
+    arena: &'a Arena<'a>,
+) -> DocBuilder<'a, Arena<'a>, ()> {
+    let mut doc = arena.text(format!("{marker}{name}"));
+    if !label.is_empty() {
+        doc = doc
+            .append(arena.text("["))
+            .append(label.to_doc_inline(allow_newlines, arena))
+            .append(arena.text("]"));
+    }
+    if !attributes.is_empty() {
+        doc = doc.append(arena.text(attributes_to_string(attributes)));
+    }
+    doc
+}
+
+fn attributes_to_string(attributes: &[(String, String)]) -> String {
+    let is_shortcut = |v: &str| {
+        !v.is_empty()
+            && v.chars()
+                .all(|c| !c.is_whitespace() && !"{}#.\"'=<>`".contains(c))
+    };
+    let mut parts = Vec::new();
+    for (key, value) in attributes {
+        if key == "id" && is_shortcut(value) {
+            parts.push(format!("#{value}"));
+        } else if key == "class"
+            && value.split_whitespace().all(is_shortcut)
+            && !value.trim().is_empty()
+        {
+            parts.extend(value.split_whitespace().map(|v| format!(".{v}")));
+        } else if value.is_empty() {
+            parts.push(key.clone());
+        } else if value.contains('"') {
+            parts.push(format!("{key}='{value}'"));
+        } else {
+            parts.push(format!("{key}=\"{value}\""));
+        }
+    }
+    format!("{{{}}}", parts.join(" "))
+}
+
+/// Maximal number of container directives nested in the given blocks.
+fn nesting_depth(blocks: &[Block]) -> usize {
+    blocks
+        .iter()
+        .map(|block| match block {
+            Block::ContainerDirective(v) => 1 + nesting_depth(&v.blocks),
+            Block::BlockQuote(v) => nesting_depth(v),
+            Block::List(v) => v
+                .items
+                .iter()
+                .map(|item| nesting_depth(&item.blocks))
+                .max()
+                .unwrap_or(0),
+            Block::FootnoteDefinition(v) => nesting_depth(&v.blocks),
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+}
@@ -96,8 +96,37 @@ impl<'a> ToDocInline<'a> for Inline {
                     .append(arena.text(title_part))
                     .append(arena.text(")"))
             }
+            // `www.` extended autolinks have no scheme and are not valid inside `<…>`
+            Inline::Autolink(link) if link.starts_with("www.") => arena.text(link.clone()),
             Inline::Autolink(link) => arena.text(format!("<{}>", link)),
             Inline::FootnoteReference(label) => arena.text(format!("[^{}]", label)),
+            Inline::WikiLink(v) => {
+                let fragment = v
+                    .fragment
+                    .as_ref()
+                    .map(|f| format!("#{f}"))
+                    .unwrap_or_default();
+                let alias = v
+                    .alias
+                    .as_ref()
+                    .map(|a| format!("|{a}"))
+                    .unwrap_or_default();
+                let embed = if v.embed { "!" } else { "" };
+                arena.text(format!("{embed}[[{}{fragment}{alias}]]", v.target))
+            }
+            Inline::Mention(v) => arena.text(mention_to_string(v)),
+            Inline::InlineFootnote(v) => arena
+                .text("^[")
+                .append(v.to_doc_inline(allow_newlines, arena))
+                .append(arena.text("]")),
+            Inline::TextDirective(v) => crate::printer::directive::directive_to_doc(
+                ":",
+                &v.name,
+                &v.label,
+                &v.attributes,
+                allow_newlines,
+                arena,
+            ),
             Inline::Empty => arena.nil(),
             Inline::LinkReference(v) => {
                 if v.label == v.text {
@@ -117,6 +146,28 @@ impl<'a> ToDocInline<'a> for Inline {
     }
 }
 
+fn mention_to_string(mention: &Mention) -> String {
+    match mention {
+        Mention::User(name) => format!("@{name}"),
+        Mention::Issue {
+            repository: Some(repository),
+            number,
+        } => format!("{repository}#{number}"),
+        Mention::Issue {
+            repository: None,
+            number,
+        } => format!("#{number}"),
+        Mention::Commit {
+            repository: Some(repository),
+            sha,
+        } => format!("{repository}@{sha}"),
+        Mention::Commit {
+            repository: None,
+            sha,
+        } => sha.clone(),
+    }
+}
+
 /// Split string by spaces, but keep the spaces in the result.
 fn split_with_spaces(s: &str) -> Vec<Option<&str>> {
     let mut result = Vec::new();
@@ -1,6 +1,7 @@
 mod block;
 mod blockquote;
 pub mod config;
+mod directive;
 mod heading;
 mod inline;
 mod list;
@@ -8,17 +9,98 @@ mod table;
 mod tests;
 
 use crate::ast::*;
+use inline::ToDocInline;
 use pretty::{Arena, DocBuilder};
 
 /// Render Markdown AST to Markdown document.
 pub fn render_markdown(ast: &Document, config: crate::printer::config::Config) -> String {
+    let mut result = String::new();
+    render_markdown_to_fmt(&mut result, ast, config).expect("writing to a String never fails");
+    result
+}
+
+/// Render Markdown AST to Markdown document, writing the output to `writer` as it is
+/// produced.
+pub fn render_markdown_to<W: std::io::Write>(
+    writer: &mut W,
+    ast: &Document,
+    config: crate::printer::config::Config,
+) -> std::io::Result<()> {
+    let ast = hoisted_inline_footnotes(ast, &config);
     let config = crate::Xrc::new(config);
     let arena = Arena::new();
-    let doc = ast.to_doc(config.clone(), &arena);
+    ast.to_doc(config.clone(), &arena)
+        .render(config.width, writer)
+}
+
+/// Render Markdown AST to Markdown document, writing the output to a
+/// [`std::fmt::Write`] such as a `String` or a `Formatter`.
+pub fn render_markdown_to_fmt<W: std::fmt::Write>(
+    writer: &mut W,
+    ast: &Document,
+    config: crate::printer::config::Config,
+) -> std::fmt::Result {
+    let ast = hoisted_inline_footnotes(ast, &config);
+    let config = crate::Xrc::new(config);
+    let arena = Arena::new();
+    ast.to_doc(config.clone(), &arena)
+        .render_fmt(config.width, writer)
+}
+
+/// Render a single block to Markdown.
+pub fn render_markdown_block(block: &Block, config: crate::printer::config::Config) -> String {
+    render_markdown_blocks(std::slice::from_ref(block), config)
+}
 
-    let mut buf = Vec::new();
-    doc.render(config.width, &mut buf).unwrap();
-    String::from_utf8(buf).unwrap()
+/// Render a sequence of blocks to Markdown. Inline footnotes are printed as they are,
+/// [`Config::with_hoist_inline_footnotes`](crate::printer::config::Config::with_hoist_inline_footnotes)
+/// only applies to whole documents.
+pub fn render_markdown_blocks(blocks: &[Block], config: crate::printer::config::Config) -> String {
+    let config = crate::Xrc::new(config);
+    let arena = Arena::new();
+    let doc = blocks
+        .iter()
+        .collect::<Vec<_>>()
+        .to_doc(config.clone(), &arena);
+    let mut result = String::new();
+    doc.render_fmt(config.width, &mut result)
+        .expect("writing to a String never fails");
+    result
+}
+
+/// Render a sequence of inlines to Markdown, e.g. the content of a heading.
+///
+/// ```rust
+/// use markdown_ppp::ast::Inline;
+/// use markdown_ppp::printer::{config::Config, render_markdown_inlines};
+///
+/// let inlines = vec![
+///     Inline::Text("Hello, ".to_owned()),
+///     Inline::Strong(vec![Inline::Text("world".to_owned())]),
+/// ];
+/// assert_eq!("Hello, **world**", render_markdown_inlines(&inlines, Config::default()));
+/// ```
+pub fn render_markdown_inlines(
+    inlines: &[Inline],
+    config: crate::printer::config::Config,
+) -> String {
+    let arena = Arena::new();
+    let doc = inlines.to_vec().to_doc_inline(true, &arena);
+    let mut result = String::new();
+    doc.render_fmt(config.width, &mut result)
+        .expect("writing to a String never fails");
+    result
+}
+
+fn hoisted_inline_footnotes<'d>(
+    ast: &'d Document,
+    config: &crate::printer::config::Config,
+) -> std::borrow::Cow<'d, Document> {
+    if config.hoist_inline_footnotes {
+        crate::ast::footnotes::hoisted(ast)
+    } else {
+        std::borrow::Cow::Borrowed(ast)
+    }
 }
 
 trait ToDoc<'a> {
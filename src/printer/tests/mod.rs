@@ -133,6 +133,9 @@ let s = "hello\n";
         case(
             r#"Autolinks test: <http://example.com> and <johnlepikhin@gmail.com>"#),
 
+        case(
+            r#"Extended autolinks test: www.example.com and <https://example.com>."#),
+
 )]
 fn symmetric_round_trip(input: &str) {
     let config = crate::printer::config::Config::default();
@@ -142,3 +145,151 @@ fn symmetric_round_trip(input: &str) {
     let result = crate::printer::render_markdown(&doc, config);
     assert_eq!(input, result);
 }
+
+#[rstest(
+    input,
+    case("See [[Page]], [[Page#Heading|alias]] and ![[image.png]]."),
+    case("[[#Heading]]")
+)]
+fn wiki_link_round_trip(input: &str) {
+    use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+    let config =
+        MarkdownParserConfig::default().with_inline_wiki_link_behavior(ElementBehavior::Parse);
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(config),
+        input,
+    )
+    .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[test]
+fn mention_round_trip() {
+    use crate::parser::config::MarkdownParserConfig;
+    use crate::parser::mention::{mention_parser, MentionConfig};
+    let input = "@alice fixed #12, acme/app#3 and acme/app@a5c3785 in 0a1b2c3d.";
+    let config = MarkdownParserConfig::default()
+        .with_custom_inline_parser_at_word_starts(mention_parser(MentionConfig::default()));
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(config),
+        input,
+    )
+    .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[rstest(
+    input,
+    case("::youtube[Video of a **cat**]{#cat-video .big .wide vid=\"7cNKRGQhXNc\" autoplay}"),
+    case(":::note[Heads up]{title='A \"quoted\" title'}\nSome *text*.\n\n - item\n:::"),
+    case("::::outer\n:::inner\nText\n:::\n::::"),
+    case("Press :kbd[Ctrl]{.key} to copy, or see :abbr.")
+)]
+fn directive_round_trip(input: &str) {
+    use crate::parser::config::{ElementBehavior, MarkdownParserConfig};
+    let config = MarkdownParserConfig::default()
+        .with_block_container_directive_behavior(ElementBehavior::Parse)
+        .with_block_leaf_directive_behavior(ElementBehavior::Parse)
+        .with_inline_text_directive_behavior(ElementBehavior::Parse);
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(config),
+        input,
+    )
+    .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[test]
+fn inline_footnote_round_trip() {
+    let input = "Text^[A *short* note.] and more^[Another ^[nested] note.].";
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap();
+    let result = crate::printer::render_markdown(&doc, crate::printer::config::Config::default());
+    assert_eq!(input, result);
+}
+
+#[test]
+fn inline_footnote_hoisting() {
+    let input = "Text^[A *short* note.] and[^1] more^[Another ^[nested] note.].\n\n[^1]: Regular.";
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap();
+    let config = crate::printer::config::Config::default().with_hoist_inline_footnotes(true);
+    let result = crate::printer::render_markdown(&doc, config);
+    assert_eq!(
+        "Text[^2] and[^1] more[^3].\n\n[^1]: Regular.\n\n[^2]: A *short* note.\n\n[^3]: Another [^4] note.\n\n[^4]: nested",
+        result
+    );
+}
+
+#[test]
+fn streaming_output() {
+    let input = "# Title\n\nText with **bold**.\n\n - one\n - two";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+
+    let mut bytes = Vec::new();
+    crate::printer::render_markdown_to(&mut bytes, &doc, crate::printer::config::Config::default())
+        .unwrap();
+    assert_eq!(input, String::from_utf8(bytes).unwrap());
+
+    let mut string = String::new();
+    crate::printer::render_markdown_to_fmt(
+        &mut string,
+        &doc,
+        crate::printer::config::Config::default(),
+    )
+    .unwrap();
+    assert_eq!(input, string);
+
+    let mut buffer = [0u8; 4];
+    let error = crate::printer::render_markdown_to(
+        &mut buffer.as_mut_slice(),
+        &doc,
+        crate::printer::config::Config::default(),
+    )
+    .unwrap_err();
+    assert_eq!(std::io::ErrorKind::WriteZero, error.kind());
+}
+
+#[test]
+fn fragments() {
+    let input = "# Hello, *world*\n\nFirst paragraph.\n\nSecond [link](/url).";
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    let config = crate::printer::config::Config::default;
+    let crate::ast::Block::Heading(heading) = &doc.blocks[0] else {
+        panic!("expected a heading");
+    };
+
+    assert_eq!(
+        "Hello, *world*",
+        crate::printer::render_markdown_inlines(&heading.content, config())
+    );
+    assert_eq!(
+        "# Hello, *world*",
+        crate::printer::render_markdown_block(&doc.blocks[0], config())
+    );
+    assert_eq!(
+        "First paragraph.\n\nSecond [link](/url).",
+        crate::printer::render_markdown_blocks(&doc.blocks[1..], config())
+    );
+}
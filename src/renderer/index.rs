@@ -0,0 +1,190 @@
+//! Footnote numbering and link definitions of a document.
+
+use crate::ast::*;
+use std::collections::HashMap;
+
+/// Definitions and numbering shared by all renderers.
+///
+/// Footnotes are numbered by their first reference. References in the document body
+/// come first, then references inside footnotes, in the order the footnotes are
+/// rendered. Unreferenced footnotes and references without a definition get no number.
+#[derive(Debug, Clone)]
+pub struct Index {
+    /// Mapping of footnote labels to their numbers, in order of first reference.
+    footnote_indices: HashMap<String, usize>,
+    /// Number of references to each footnote.
+    footnote_reference_counts: HashMap<String, usize>,
+    /// Referenced footnote definitions, ordered by number.
+    footnotes: Vec<FootnoteDefinition>,
+    /// Mapping of link labels to their definitions.
+    link_definitions: HashMap<Vec<Inline>, LinkDefinition>,
+    footnote_definitions: HashMap<String, FootnoteDefinition>,
+}
+
+impl Index {
+    /// Index the document.
+    pub fn new(ast: &Document) -> Self {
+        let mut index = Index::empty();
+
+        for block in &ast.blocks {
+            get_definitions(&mut index, block);
+        }
+
+        for block in &ast.blocks {
+            get_block_indicies(&mut index, block);
+        }
+
+        let mut i = 0;
+        while i < index.footnotes.len() {
+            let blocks = index.footnotes[i].blocks.clone();
+            for block in &blocks {
+                get_block_indicies(&mut index, block);
+            }
+            i += 1;
+        }
+
+        index
+    }
+
+    /// Number of the footnote, starting from 1.
+    pub fn footnote_index(&self, label: &str) -> Option<usize> {
+        self.footnote_indices.get(label).copied()
+    }
+
+    /// Number of references to the footnote in the whole document.
+    pub fn footnote_reference_count(&self, label: &str) -> usize {
+        self.footnote_reference_counts
+            .get(label)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Referenced footnote definitions, ordered by number.
+    pub fn footnotes(&self) -> &[FootnoteDefinition] {
+        &self.footnotes
+    }
+
+    /// Definition of the link label.
+    pub fn link_definition(&self, label: &[Inline]) -> Option<&LinkDefinition> {
+        self.link_definitions.get(label)
+    }
+
+    fn empty() -> Self {
+        Index {
+            footnote_indices: HashMap::new(),
+            footnote_reference_counts: HashMap::new(),
+            footnotes: Vec::new(),
+            link_definitions: HashMap::new(),
+            footnote_definitions: HashMap::new(),
+        }
+    }
+
+    fn add_footnote_reference(&mut self, label: &str) {
+        let Some(definition) = self.footnote_definitions.get(label) else {
+            return;
+        };
+        *self
+            .footnote_reference_counts
+            .entry(label.to_owned())
+            .or_default() += 1;
+        if !self.footnote_indices.contains_key(label) {
+            self.footnote_indices
+                .insert(label.to_owned(), self.footnotes.len() + 1);
+            self.footnotes.push(definition.clone());
+        }
+    }
+}
+
+fn get_definitions(index: &mut Index, block: &Block) {
+    match block {
+        Block::Definition(v) => {
+            index.link_definitions.insert(v.label.clone(), v.clone());
+        }
+        Block::FootnoteDefinition(v) => {
+            index
+                .footnote_definitions
+                .entry(v.label.clone())
+                .or_insert_with(|| v.clone());
+            for block in &v.blocks {
+                get_definitions(index, block);
+            }
+        }
+        Block::BlockQuote(v) => {
+            for block in v {
+                get_definitions(index, block);
+            }
+        }
+        Block::List(v) => {
+            for item in &v.items {
+                for block in &item.blocks {
+                    get_definitions(index, block);
+                }
+            }
+        }
+        Block::ContainerDirective(v) => {
+            for block in &v.blocks {
+                get_definitions(index, block);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn get_block_indicies(index: &mut Index, block: &Block) {
+    match block {
+        Block::Paragraph(v) => get_inlines_indicies(index, v),
+        Block::Heading(v) => get_inlines_indicies(index, &v.content),
+        Block::ThematicBreak => (),
+        Block::BlockQuote(v) => {
+            for block in v {
+                get_block_indicies(index, block);
+            }
+        }
+        Block::List(v) => {
+            for item in &v.items {
+                for block in &item.blocks {
+                    get_block_indicies(index, block);
+                }
+            }
+        }
+        Block::CodeBlock(_) => (),
+        Block::HtmlBlock(_) => (),
+        Block::Definition(_) => (),
+        Block::Table(v) => {
+            for row in &v.rows {
+                for cell in row {
+                    get_inlines_indicies(index, cell);
+                }
+            }
+        }
+        // Footnote contents are indexed when the footnote is referenced
+        Block::FootnoteDefinition(_) => (),
+        Block::ContainerDirective(v) => {
+            get_inlines_indicies(index, &v.label);
+            for block in &v.blocks {
+                get_block_indicies(index, block);
+            }
+        }
+        Block::LeafDirective(v) => get_inlines_indicies(index, &v.label),
+        Block::Empty => (),
+    }
+}
+
+fn get_inlines_indicies(index: &mut Index, inlines: &[Inline]) {
+    for inline in inlines {
+        get_inline_indicies(index, inline);
+    }
+}
+
+fn get_inline_indicies(index: &mut Index, inline: &Inline) {
+    match inline {
+        Inline::FootnoteReference(label) => index.add_footnote_reference(label),
+        Inline::Emphasis(v) | Inline::Strong(v) | Inline::Strikethrough(v) => {
+            get_inlines_indicies(index, v)
+        }
+        Inline::Link(v) => get_inlines_indicies(index, &v.children),
+        Inline::LinkReference(v) => get_inlines_indicies(index, &v.text),
+        Inline::TextDirective(v) => get_inlines_indicies(index, &v.label),
+        _ => (),
+    }
+}
@@ -0,0 +1,510 @@
+//! Building blocks for output formats.
+//!
+//! A renderer converts the AST to a [`pretty`] document, which is then laid out for
+//! the target line width. [`Renderer`] has a default implementation for every node:
+//! blocks and inlines are traversed recursively and rendered as plain text, so an
+//! output format only overrides the nodes it renders differently. [`Index`] numbers
+//! footnotes and resolves link references the same way for every output format.
+//!
+//! ```rust
+//! use markdown_ppp::ast::Inline;
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//! use markdown_ppp::renderer::{render_to_string, Doc, Index, Renderer};
+//! use markdown_ppp::renderer::pretty::{Arena, DocAllocator};
+//!
+//! /// Renders emphasis with underscores and footnote references as `(n)`.
+//! struct Plain<'a> {
+//!     arena: &'a Arena<'a>,
+//!     index: Index,
+//! }
+//!
+//! impl<'a> Renderer<'a> for Plain<'a> {
+//!     fn arena(&self) -> &'a Arena<'a> {
+//!         self.arena
+//!     }
+//!
+//!     fn index(&self) -> Option<&Index> {
+//!         Some(&self.index)
+//!     }
+//!
+//!     fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+//!         self.arena
+//!             .text("_")
+//!             .append(self.render_inlines(children))
+//!             .append(self.arena.text("_"))
+//!     }
+//!
+//!     fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+//!         match self.index.footnote_index(label) {
+//!             Some(n) => self.arena.text(format!("({n})")),
+//!             None => self.arena.nil(),
+//!         }
+//!     }
+//! }
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "Some *emphasis*[^1].\n\n[^1]: A note.",
+//! )
+//! .unwrap();
+//! let arena = Arena::new();
+//! let renderer = Plain {
+//!     arena: &arena,
+//!     index: Index::new(&doc),
+//! };
+//! assert_eq!(
+//!     "Some _emphasis_(1).\n\n[1] A note.",
+//!     render_to_string(&renderer, &doc, 80)
+//! );
+//! ```
+
+pub mod index;
+#[cfg(any(
+    feature = "asciidoc-printer",
+    feature = "docx-printer",
+    feature = "rst-printer",
+    feature = "slack-printer",
+    feature = "telegram-printer"
+))]
+pub(crate) mod plain;
+mod tests;
+
+use crate::ast::*;
+pub use index::Index;
+/// The `pretty` crate, for implementing [`Renderer`] with a matching version.
+pub use pretty;
+use pretty::{Arena, DocAllocator, DocBuilder};
+
+/// Document built by a [`Renderer`].
+pub type Doc<'a> = DocBuilder<'a, Arena<'a>, ()>;
+
+/// Conversion of the AST to a [`pretty`] document.
+///
+/// Every method has a default implementation. Dispatching methods
+/// ([`render_block`](Renderer::render_block), [`render_inline`](Renderer::render_inline))
+/// call the method of the node kind, container nodes render their children, and
+/// leaf nodes render their text content. Text is split at spaces with soft line
+/// breaks, so paragraphs are wrapped at the width passed to [`render_to_string`].
+pub trait Renderer<'a> {
+    /// Arena all documents are allocated in.
+    fn arena(&self) -> &'a Arena<'a>;
+
+    /// Index of the rendered document, used to resolve link references and number
+    /// footnotes.
+    fn index(&self) -> Option<&Index> {
+        None
+    }
+
+    /// Render the document body followed by the referenced footnotes.
+    fn render_document(&self, document: &Document) -> Doc<'a> {
+        let body = self.render_blocks(&document.blocks);
+        match self.index() {
+            Some(index) if !index.footnotes().is_empty() => body
+                .append(self.block_separator())
+                .append(self.render_footnotes(index.footnotes())),
+            _ => body,
+        }
+    }
+
+    /// Render blocks separated by [`block_separator`](Renderer::block_separator).
+    /// Blocks rendered as nothing, such as link definitions, are skipped.
+    fn render_blocks(&self, blocks: &[Block]) -> Doc<'a> {
+        self.arena().intersperse(
+            blocks
+                .iter()
+                .map(|block| self.render_block(block))
+                .filter(|doc| !matches!(**doc, pretty::Doc::Nil)),
+            self.block_separator(),
+        )
+    }
+
+    /// Separator between blocks, an empty line by default.
+    fn block_separator(&self) -> Doc<'a> {
+        self.arena().hardline().append(self.arena().hardline())
+    }
+
+    /// Render a block with the method of its kind.
+    fn render_block(&self, block: &Block) -> Doc<'a> {
+        match block {
+            Block::Paragraph(v) => self.render_paragraph(v),
+            Block::Heading(v) => self.render_heading(v),
+            Block::ThematicBreak => self.render_thematic_break(),
+            Block::BlockQuote(v) => self.render_block_quote(v),
+            Block::List(v) => self.render_list(v),
+            Block::CodeBlock(v) => self.render_code_block(v),
+            Block::HtmlBlock(v) => self.render_html_block(v),
+            Block::Definition(v) => self.render_link_definition(v),
+            Block::Table(v) => self.render_table(v),
+            Block::FootnoteDefinition(v) => self.render_footnote_definition(v),
+            Block::ContainerDirective(v) => self.render_container_directive(v),
+            Block::LeafDirective(v) => self.render_leaf_directive(v),
+            Block::Empty => self.arena().nil(),
+        }
+    }
+
+    fn render_paragraph(&self, inlines: &[Inline]) -> Doc<'a> {
+        self.render_inlines(inlines).group()
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        self.render_inlines(&heading.content).group()
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena().text("---")
+    }
+
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        self.render_blocks(blocks)
+    }
+
+    /// Render list items separated by line breaks.
+    fn render_list(&self, list: &List) -> Doc<'a> {
+        let start = match &list.kind {
+            ListKind::Ordered(options) => Some(options.start),
+            ListKind::Bullet(_) => None,
+        };
+        self.arena().intersperse(
+            list.items.iter().enumerate().map(|(i, item)| {
+                self.render_list_item(list, start.map(|start| start + i as u64), item)
+            }),
+            self.arena().hardline(),
+        )
+    }
+
+    /// Render a list item with `- ` or `n. ` as the marker. `number` is the number of
+    /// the item in an ordered list.
+    fn render_list_item(&self, _list: &List, number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let marker = match number {
+            Some(n) => format!("{n}. "),
+            None => "- ".to_owned(),
+        };
+        let task = match item.task {
+            Some(TaskState::Complete) => "[x] ",
+            Some(TaskState::Incomplete) => "[ ] ",
+            None => "",
+        };
+        let indent = marker.len();
+        self.arena()
+            .text(marker)
+            .append(self.arena().text(task))
+            .append(self.render_blocks(&item.blocks).nest(indent as isize))
+    }
+
+    /// Render the code lines as they are.
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        self.arena().intersperse(
+            code_block
+                .literal
+                .lines()
+                .map(|line| self.arena().text(line.to_owned())),
+            self.arena().hardline(),
+        )
+    }
+
+    fn render_html_block(&self, _html: &str) -> Doc<'a> {
+        self.arena().nil()
+    }
+
+    fn render_link_definition(&self, _definition: &LinkDefinition) -> Doc<'a> {
+        self.arena().nil()
+    }
+
+    /// Render rows separated by line breaks, cells separated by ` | `.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        self.arena().intersperse(
+            table.rows.iter().map(|row| {
+                self.arena().intersperse(
+                    row.iter().enumerate().map(|(i, cell)| {
+                        let alignment = table.alignments.get(i).copied().unwrap_or_default();
+                        self.render_table_cell(cell, alignment)
+                    }),
+                    self.arena().text(" | "),
+                )
+            }),
+            self.arena().hardline(),
+        )
+    }
+
+    fn render_table_cell(&self, cell: &[Inline], _alignment: Alignment) -> Doc<'a> {
+        self.render_inlines(cell)
+    }
+
+    /// Render a footnote definition where it appears in the document. Referenced
+    /// footnotes are rendered at the end by
+    /// [`render_document`](Renderer::render_document) instead.
+    fn render_footnote_definition(&self, _definition: &FootnoteDefinition) -> Doc<'a> {
+        self.arena().nil()
+    }
+
+    /// Render the referenced footnotes, ordered by number.
+    fn render_footnotes(&self, footnotes: &[FootnoteDefinition]) -> Doc<'a> {
+        self.arena().intersperse(
+            footnotes
+                .iter()
+                .map(|footnote| self.render_footnote(footnote)),
+            self.block_separator(),
+        )
+    }
+
+    /// Render a referenced footnote as `[n] content`.
+    fn render_footnote(&self, footnote: &FootnoteDefinition) -> Doc<'a> {
+        let number = self
+            .index()
+            .and_then(|index| index.footnote_index(&footnote.label))
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| footnote.label.clone());
+        let marker = format!("[{number}] ");
+        let indent = marker.len();
+        self.arena()
+            .text(marker)
+            .append(self.render_blocks(&footnote.blocks).nest(indent as isize))
+    }
+
+    fn render_container_directive(&self, directive: &ContainerDirective) -> Doc<'a> {
+        self.render_blocks(&directive.blocks)
+    }
+
+    fn render_leaf_directive(&self, directive: &Directive) -> Doc<'a> {
+        self.render_inlines(&directive.label)
+    }
+
+    fn render_inlines(&self, inlines: &[Inline]) -> Doc<'a> {
+        self.arena()
+            .concat(inlines.iter().map(|inline| self.render_inline(inline)))
+    }
+
+    /// Render an inline with the method of its kind.
+    fn render_inline(&self, inline: &Inline) -> Doc<'a> {
+        match inline {
+            Inline::Text(v) => self.render_text(v),
+            Inline::LineBreak => self.render_line_break(),
+            Inline::Code(v) => self.render_code(v),
+            Inline::Html(v) => self.render_inline_html(v),
+            Inline::Link(v) => self.render_link(v),
+            Inline::LinkReference(v) => self.render_link_reference(v),
+            Inline::Image(v) => self.render_image(v),
+            Inline::Emphasis(v) => self.render_emphasis(v),
+            Inline::Strong(v) => self.render_strong(v),
+            Inline::Strikethrough(v) => self.render_strikethrough(v),
+            Inline::Autolink(v) => self.render_autolink(v),
+            Inline::FootnoteReference(v) => self.render_footnote_reference(v),
+            Inline::InlineFootnote(v) => self.render_inline_footnote(v),
+            Inline::TextDirective(v) => self.render_text_directive(v),
+            Inline::WikiLink(v) => self.render_wiki_link(v),
+            Inline::Mention(v) => self.render_mention(v),
+            Inline::Empty => self.arena().nil(),
+        }
+    }
+
+    /// Render text with soft line breaks at spaces and line breaks.
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena().intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena().text(word.to_owned())),
+            self.arena().softline(),
+        )
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena().hardline()
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        self.arena().text(code.to_owned())
+    }
+
+    fn render_inline_html(&self, _html: &str) -> Doc<'a> {
+        self.arena().nil()
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        self.render_inlines(&link.children)
+    }
+
+    /// Render a resolved link reference with [`render_link`](Renderer::render_link),
+    /// or the link text if the label has no definition.
+    fn render_link_reference(&self, link: &LinkReference) -> Doc<'a> {
+        match self
+            .index()
+            .and_then(|index| index.link_definition(&link.label))
+        {
+            Some(definition) => self.render_link(&Link {
+                destination: definition.destination.clone(),
+                title: definition.title.clone(),
+                children: link.text.clone(),
+            }),
+            None => self.render_inlines(&link.text),
+        }
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        self.render_text(&image.alt)
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.render_inlines(children)
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.render_inlines(children)
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.render_inlines(children)
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        self.arena().text(link.to_owned())
+    }
+
+    /// Render a footnote reference as `[n]`, or `[^label]` without a number.
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        match self.index().and_then(|index| index.footnote_index(label)) {
+            Some(n) => self.render_text(&format!("[{n}]")),
+            None => self.render_text(&format!("[^{label}]")),
+        }
+    }
+
+    /// Render an inline footnote in parentheses. Use
+    /// [`hoist_inline_footnotes`](crate::ast::footnotes::hoist_inline_footnotes) to
+    /// number them like regular footnotes instead.
+    fn render_inline_footnote(&self, content: &[Inline]) -> Doc<'a> {
+        self.arena()
+            .text("(")
+            .append(self.render_inlines(content))
+            .append(self.arena().text(")"))
+    }
+
+    fn render_text_directive(&self, directive: &Directive) -> Doc<'a> {
+        self.render_inlines(&directive.label)
+    }
+
+    /// Render the alias of the wiki-link, or its target.
+    fn render_wiki_link(&self, link: &WikiLink) -> Doc<'a> {
+        let text = match (&link.alias, &link.fragment) {
+            (Some(alias), _) => alias.clone(),
+            (None, Some(fragment)) if link.target.is_empty() => fragment.clone(),
+            (None, Some(fragment)) => format!("{} > {}", link.target, fragment),
+            (None, None) => link.target.clone(),
+        };
+        self.render_text(&text)
+    }
+
+    /// Render a mention as written: `@user`, `org/repo#45`, `org/repo@a5c3785`.
+    fn render_mention(&self, mention: &Mention) -> Doc<'a> {
+        let text = match mention {
+            Mention::User(name) => format!("@{name}"),
+            Mention::Issue { repository, number } => {
+                format!("{}#{number}", repository.as_deref().unwrap_or_default())
+            }
+            Mention::Commit {
+                repository: Some(repository),
+                sha,
+            } => format!("{repository}@{sha}"),
+            Mention::Commit {
+                repository: None,
+                sha,
+            } => sha.clone(),
+        };
+        self.render_text(&text)
+    }
+}
+
+/// Render the document and lay it out for the given line width. Trailing spaces are
+/// removed from every line, including the indentation of blank lines inside nested
+/// blocks.
+pub fn render_to_string<'a, R: Renderer<'a> + ?Sized>(
+    renderer: &R,
+    document: &Document,
+    width: usize,
+) -> String {
+    let mut result = TrimTrailingSpaces::new(String::new());
+    renderer
+        .render_document(document)
+        .render_fmt(width, &mut result)
+        .expect("writing to a String never fails");
+    result.inner
+}
+
+/// Render the document and write it to `writer` as it is laid out, see
+/// [`render_to_string`].
+pub fn render_to<'a, R: Renderer<'a> + ?Sized, W: std::io::Write>(
+    renderer: &R,
+    document: &Document,
+    width: usize,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    renderer
+        .render_document(document)
+        .render(width, &mut TrimTrailingSpaces::new(writer))
+}
+
+/// Writer which holds back spaces until something other than a line break follows.
+struct TrimTrailingSpaces<W> {
+    inner: W,
+    pending_spaces: usize,
+}
+
+impl<W> TrimTrailingSpaces<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pending_spaces: 0,
+        }
+    }
+
+    /// Split `text` into runs to write, dropping spaces before line breaks.
+    fn process<'t>(&mut self, text: &'t str, mut write: impl FnMut(&'t str)) {
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            match c {
+                ' ' => {
+                    if start < i {
+                        write(&text[start..i]);
+                    }
+                    start = i + 1;
+                    self.pending_spaces += 1;
+                }
+                '\n' => self.pending_spaces = 0,
+                _ if self.pending_spaces > 0 => {
+                    for _ in 0..self.pending_spaces {
+                        write(" ");
+                    }
+                    self.pending_spaces = 0;
+                }
+                _ => (),
+            }
+        }
+        if start < text.len() {
+            write(&text[start..]);
+        }
+    }
+}
+
+impl<W: std::fmt::Write> std::fmt::Write for TrimTrailingSpaces<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let mut runs = Vec::new();
+        self.process(s, |run| runs.push(run));
+        runs.into_iter()
+            .try_for_each(|run| self.inner.write_str(run))
+    }
+}
+
+impl<W: std::io::Write> std::io::Write for TrimTrailingSpaces<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // `pretty` writes whole strings, so the buffer is valid UTF-8
+        let text = std::str::from_utf8(buf)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        let mut runs = Vec::new();
+        self.process(text, |run| runs.push(run));
+        for run in runs {
+            self.inner.write_all(run.as_bytes())?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,103 @@
+//! Plain text for formats without a representation of some nodes.
+
+use super::{Index, Renderer};
+use crate::ast::{Alignment, Inline, Table};
+use pretty::{Arena, DocAllocator};
+
+/// The default [`Renderer`], which renders plain text.
+struct PlainRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Option<&'a Index>,
+}
+
+impl<'a> Renderer<'a> for PlainRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        self.index
+    }
+}
+
+/// Text content of inlines on a single line.
+pub(crate) fn plain_text(inlines: &[Inline], index: Option<&Index>) -> String {
+    let arena = Arena::new();
+    let renderer = PlainRenderer {
+        arena: &arena,
+        index,
+    };
+    let mut result = String::new();
+    renderer
+        .render_inlines(inlines)
+        .render_fmt(usize::MAX, &mut result)
+        .expect("writing to a String never fails");
+    result.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Width of text in columns, as measured by the layout.
+#[cfg_attr(
+    not(any(
+        feature = "rst-printer",
+        feature = "slack-printer",
+        feature = "telegram-printer"
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn text_width(text: &str) -> usize {
+    let arena: Arena = Arena::new();
+    match *arena.text(text.to_owned()) {
+        pretty::Doc::RenderLen(width, _) => width,
+        _ => text.len(),
+    }
+}
+
+/// Lines of a table laid out for a monospace font, with cells padded according to
+/// the column alignment and a dashed line below the header row.
+#[cfg_attr(
+    not(any(feature = "slack-printer", feature = "telegram-printer")),
+    allow(dead_code)
+)]
+pub(crate) fn text_table(table: &Table, index: Option<&Index>) -> Vec<String> {
+    let rows: Vec<Vec<String>> = table
+        .rows
+        .iter()
+        .map(|row| row.iter().map(|cell| plain_text(cell, index)).collect())
+        .collect();
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| text_width(cell))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let cells: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(i, column_width)| {
+                    let cell = row.get(i).map(String::as_str).unwrap_or_default();
+                    let padding = column_width - text_width(cell);
+                    let (left, right) = match table.alignments.get(i).copied().unwrap_or_default() {
+                        Alignment::Right => (padding, 0),
+                        Alignment::Center => (padding / 2, padding - padding / 2),
+                        Alignment::Left | Alignment::None => (0, padding),
+                    };
+                    format!("{}{cell}{}", " ".repeat(left), " ".repeat(right))
+                })
+                .collect();
+            cells.join(" | ").trim_end().to_owned()
+        })
+        .collect();
+    if lines.len() > 1 {
+        let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+        lines.insert(1, separator.join("-|-"));
+    }
+    lines
+}
@@ -0,0 +1,135 @@
+#![cfg(test)]
+use crate::renderer::{render_to, render_to_string, Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+
+struct Plain<'a> {
+    arena: &'a Arena<'a>,
+    index: Option<Index>,
+}
+
+impl<'a> Renderer<'a> for Plain<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        self.index.as_ref()
+    }
+}
+
+/// Overrides a few nodes, everything else uses the default traversal.
+struct Tagged<'a> {
+    arena: &'a Arena<'a>,
+}
+
+impl<'a> Renderer<'a> for Tagged<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn render_heading(&self, heading: &crate::ast::Heading) -> Doc<'a> {
+        self.arena
+            .text("== ")
+            .append(self.render_inlines(&heading.content))
+    }
+
+    fn render_strong(&self, children: &[crate::ast::Inline]) -> Doc<'a> {
+        self.arena
+            .text("<")
+            .append(self.render_inlines(children))
+            .append(self.arena.text(">"))
+    }
+
+    fn render_link(&self, link: &crate::ast::Link) -> Doc<'a> {
+        self.render_inlines(&link.children)
+            .append(self.arena.text(format!(" ({})", link.destination)))
+    }
+}
+
+fn parse(input: &str) -> crate::ast::Document {
+    crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap()
+}
+
+#[test]
+fn default_traversal() {
+    let doc = parse(
+        "# Title\n\nSome *emphasis* and **strong** [text][ref]^[inline].\n\n1. one\n2. two\n\n    - [x] nested\n\n> quoted `code`\n\n| a | b |\n|---|---|\n| c | d |\n\n[ref]: /url",
+    );
+    let arena = Arena::new();
+    let renderer = Plain {
+        arena: &arena,
+        index: None,
+    };
+    assert_eq!(
+        "Title\n\nSome emphasis and strong text(inline).\n\n1. one\n2. two\n\n   - [x] nested\n\nquoted code\n\na | b\nc | d",
+        render_to_string(&renderer, &doc, 80)
+    );
+}
+
+#[test]
+fn shared_index() {
+    let doc = parse("Text[^b] and [link][ref][^a][^b].\n\n[ref]: /url\n\n[^a]: First.\n\n[^b]: Second\n    line.");
+    let arena = Arena::new();
+    let renderer = Plain {
+        arena: &arena,
+        index: Some(Index::new(&doc)),
+    };
+    assert_eq!(
+        "Text[1] and link[2][1].\n\n[1] Second line.\n\n[2] First.",
+        render_to_string(&renderer, &doc, 80)
+    );
+
+    let index = Index::new(&doc);
+    assert_eq!(Some(1), index.footnote_index("b"));
+    assert_eq!(2, index.footnote_reference_count("b"));
+    assert_eq!(None, index.footnote_index("missing"));
+    assert_eq!(
+        "/url",
+        index
+            .link_definition(&[crate::ast::Inline::Text("ref".to_owned())])
+            .unwrap()
+            .destination
+    );
+}
+
+#[test]
+fn overrides() {
+    let doc = parse("# Hello **world**\n\nSee [the docs](https://example.com).");
+    let arena = Arena::new();
+    let renderer = Tagged { arena: &arena };
+    assert_eq!(
+        "== Hello <world>\n\nSee the docs (https://example.com).",
+        render_to_string(&renderer, &doc, 80)
+    );
+}
+
+#[test]
+fn width_aware_layout() {
+    let doc =
+        parse("one two three four five six seven eight nine ten\n\n- list item with several words");
+    let arena = Arena::new();
+    let renderer = Plain {
+        arena: &arena,
+        index: None,
+    };
+    assert_eq!(
+        "one two three\nfour five six\nseven eight\nnine ten\n\n- list item\n  with several\n  words",
+        render_to_string(&renderer, &doc, 14)
+    );
+
+    let mut bytes = Vec::new();
+    render_to(&renderer, &doc, 14, &mut bytes).unwrap();
+    assert_eq!(
+        render_to_string(&renderer, &doc, 14),
+        String::from_utf8(bytes).unwrap()
+    );
+}
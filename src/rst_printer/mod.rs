@@ -0,0 +1,439 @@
+//! Rendering of the AST to reStructuredText.
+//!
+//! Paragraphs are wrapped at the configured width. Code blocks become `code-block`
+//! directives, tables become `list-table` directives (without column alignment),
+//! GitHub alerts (see [`alerts`](crate::ast::alerts)) become admonitions and
+//! directives keep their name. Inline markup cannot be nested in reST, so nested
+//! markup is rendered as plain text, and strikethrough has no equivalent.
+//!
+//! ```rust
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//! use markdown_ppp::rst_printer::{config::Config, render_rst};
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "# Usage\n\nSee the [guide](https://example.com)[^1].\n\n[^1]: Or **ask**.",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     "Usage\n=====\n\nSee the `guide <https://example.com>`__\\ [1]_.\n\n.. [1] Or **ask**.",
+//!     render_rst(&doc, Config::default())
+//! );
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::ast::alerts::{alert, AlertKind};
+use crate::ast::*;
+use crate::renderer::plain::{plain_text, text_width};
+use crate::renderer::{Doc, Index, Renderer};
+use crate::rst_printer::config::Config;
+use pretty::{Arena, DocAllocator};
+use std::cell::{Cell, RefCell};
+
+/// Render the given Markdown AST to reStructuredText.
+pub fn render_rst(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = RstRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+        markup: Cell::new(false),
+        sections: RefCell::new(Vec::new()),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+/// Escape a word for reST. Inline markup characters are escaped, as are words which
+/// would start a list item, directive or section underline at the start of a line,
+/// since wrapping may put any word there.
+pub fn escape(word: &str) -> String {
+    let mut result = String::with_capacity(word.len());
+    let count = word.chars().count();
+    let line_start = starts_construct(word);
+    for (i, c) in word.chars().enumerate() {
+        let escape = match c {
+            '\\' | '*' | '`' | '|' => true,
+            // References `name_` and inline targets `_name`
+            '_' => i == 0 || i + 1 == count,
+            // `::` at the end of a paragraph starts a literal block
+            ':' => i + 1 == count && word.ends_with("::"),
+            _ => false,
+        };
+        if escape || (i == 0 && line_start) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+/// Whether the word starts a block construct at the start of a line.
+fn starts_construct(word: &str) -> bool {
+    if matches!(word, "-" | "+" | "\u{2022}" | "\u{2023}" | "\u{2043}") || word.starts_with("..") {
+        return true;
+    }
+    // Section adornments, doctest blocks and option lists
+    if word.chars().count() >= 2 && word.chars().all(|c| "=-~^\"'#<>+:.".contains(c)) {
+        return true;
+    }
+    // Enumerators `1.`, `a)`, `(iv)`, `#.`
+    let enumerator = word
+        .strip_suffix(['.', ')'])
+        .map(|rest| rest.strip_prefix('(').unwrap_or(rest));
+    match enumerator {
+        Some("#") => true,
+        Some(rest) if !rest.is_empty() => {
+            rest.chars().all(|c| c.is_ascii_digit())
+                || rest.chars().count() == 1 && rest.chars().all(|c| c.is_ascii_alphabetic())
+                || rest.chars().all(|c| "ivxlcdm".contains(c))
+                || rest.chars().all(|c| "IVXLCDM".contains(c))
+        }
+        _ => false,
+    }
+}
+
+/// Whether inline markup may follow the end of this inline directly.
+fn may_precede_markup(inline: &Inline) -> bool {
+    match inline {
+        Inline::Text(text) => text
+            .chars()
+            .next_back()
+            .is_none_or(|c| c.is_whitespace() || "-:/'\"<([{".contains(c)),
+        inline => !is_markup(inline),
+    }
+}
+
+/// Whether this inline may directly follow the end of inline markup.
+fn may_follow_markup(inline: &Inline) -> bool {
+    match inline {
+        Inline::Text(text) => text
+            .chars()
+            .next()
+            .is_none_or(|c| c.is_whitespace() || "-.,:;!?\\/'\")]}>".contains(c)),
+        _ => true,
+    }
+}
+
+/// Inlines rendered as inline markup, which must be separated from adjacent words.
+fn is_markup(inline: &Inline) -> bool {
+    matches!(
+        inline,
+        Inline::Emphasis(_)
+            | Inline::Strong(_)
+            | Inline::Code(_)
+            | Inline::Link(_)
+            | Inline::LinkReference(_)
+            | Inline::Image(_)
+            | Inline::FootnoteReference(_)
+            | Inline::TextDirective(_)
+    )
+}
+
+struct RstRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+    // Whether inline markup is being rendered, as markup cannot be nested.
+    markup: Cell<bool>,
+    // Levels of the enclosing sections, to not skip adornments for skipped levels.
+    sections: RefCell<Vec<u8>>,
+}
+
+impl<'a> RstRenderer<'a> {
+    /// Content between `start` and `end`, or only the content inside other markup.
+    fn markup(&self, start: &str, end: &str, content: &[Inline]) -> Doc<'a> {
+        if self.markup.replace(true) {
+            return self.render_inlines(content);
+        }
+        let content = self.render_inlines(content);
+        self.markup.set(false);
+        self.arena
+            .text(start.to_owned())
+            .append(content)
+            .append(self.arena.text(end.to_owned()))
+    }
+
+    /// Directive with an optional argument, options and content indented below.
+    fn directive(
+        &self,
+        name: &str,
+        argument: &str,
+        options: &[(String, String)],
+        content: Option<Doc<'a>>,
+    ) -> Doc<'a> {
+        let head = match argument {
+            "" => format!(".. {name}::"),
+            argument => format!(".. {name}:: {argument}"),
+        };
+        let mut body = self.arena.nil();
+        for (key, value) in options {
+            body = body
+                .append(self.arena.hardline())
+                .append(self.arena.text(format!(":{key}: {value}")));
+        }
+        if let Some(content) = content {
+            body = body
+                .append(self.arena.hardline())
+                .append(self.arena.hardline())
+                .append(content);
+        }
+        self.arena.text(head).append(body.nest(3))
+    }
+
+    /// Lines of a literal text, such as code, on separate lines.
+    fn literal_lines(&self, text: &str) -> Doc<'a> {
+        let text = text.strip_suffix('\n').unwrap_or(text);
+        self.arena.intersperse(
+            text.split('\n')
+                .map(|line| self.arena.text(line.to_owned())),
+            self.arena.hardline(),
+        )
+    }
+
+    /// Anonymous hyperlink reference `` `text <url>`__ ``, or the bare URL.
+    fn link(&self, url: &str, text: &str) -> Doc<'a> {
+        if text.is_empty() || text == url {
+            return self.arena.text(url.to_owned());
+        }
+        let words = text
+            .split(' ')
+            .map(|word| self.arena.text(escape(word).replace('<', "\\<")));
+        self.arena
+            .text("`")
+            .append(self.arena.intersperse(words, self.arena.softline()))
+            .append(self.arena.softline())
+            .append(self.arena.text(format!(
+                "<{}>`__",
+                url.replace('\\', "\\\\").replace('`', "\\`").replace('>', "%3E")
+            )))
+    }
+}
+
+impl<'a> Renderer<'a> for RstRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// A paragraph with only an image is rendered as an `image` directive.
+    fn render_paragraph(&self, inlines: &[Inline]) -> Doc<'a> {
+        match inlines {
+            [Inline::Image(image)] => {
+                let mut options = Vec::new();
+                if !image.alt.is_empty() {
+                    options.push(("alt".to_owned(), image.alt.clone()));
+                }
+                self.directive("image", &image.destination, &options, None)
+            }
+            inlines => self.render_inlines(inlines).group(),
+        }
+    }
+
+    /// Section titles are underlined with `=`, `-`, `~`, `^`, `"` and `'` by nesting
+    /// depth, so a skipped heading level does not skip an adornment.
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        let mut title = String::new();
+        self.render_inlines(&heading.content)
+            .render_fmt(usize::MAX, &mut title)
+            .expect("writing to a String never fails");
+        let title = title.replace('\n', " ");
+        let depth = {
+            let mut sections = self.sections.borrow_mut();
+            while sections.last().is_some_and(|&open| open >= level) {
+                sections.pop();
+            }
+            sections.push(level);
+            sections.len()
+        };
+        let adornment = ['=', '-', '~', '^', '"', '\''][depth.min(6) - 1];
+        let underline = adornment.to_string().repeat(text_width(&title).max(1));
+        self.arena
+            .text(title)
+            .append(self.arena.hardline())
+            .append(self.arena.text(underline))
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("----")
+    }
+
+    /// GitHub alerts are rendered as admonitions, other quotes are indented.
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        if let Some((kind, content)) = alert(blocks) {
+            let name = match kind {
+                AlertKind::Note => "note",
+                AlertKind::Tip => "tip",
+                AlertKind::Important => "important",
+                AlertKind::Warning => "warning",
+                AlertKind::Caution => "caution",
+            };
+            return self.directive(name, "", &[], Some(self.render_blocks(&content)));
+        }
+        self.arena
+            .text("    ")
+            .append(self.render_blocks(blocks))
+            .nest(4)
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let language = match &code_block.kind {
+            CodeBlockKind::Fenced { info: Some(info) } => info.split_whitespace().next(),
+            _ => None,
+        };
+        let content = self.literal_lines(&code_block.literal);
+        match language {
+            Some(language) => self.directive("code-block", language, &[], Some(content)),
+            None => self.arena.text("::").append(
+                self.arena
+                    .hardline()
+                    .append(self.arena.hardline())
+                    .append(content)
+                    .nest(3),
+            ),
+        }
+    }
+
+    fn render_html_block(&self, html: &str) -> Doc<'a> {
+        self.directive("raw", "html", &[], Some(self.literal_lines(html)))
+    }
+
+    /// Tables are rendered as `list-table` directives with a header row.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let rows = table.rows.iter().map(|row| {
+            let cells = row.iter().map(|cell| {
+                self.arena
+                    .text("- ")
+                    .append(self.render_inlines(cell).nest(2))
+                    .group()
+            });
+            self.arena
+                .text("* ")
+                .append(self.arena.intersperse(cells, self.arena.hardline()).nest(2))
+        });
+        let options = [("header-rows".to_owned(), "1".to_owned())];
+        self.directive(
+            "list-table",
+            "",
+            &options,
+            Some(self.arena.intersperse(rows, self.arena.hardline())),
+        )
+    }
+
+    /// Render a referenced footnote as `.. [n] content`.
+    fn render_footnote(&self, footnote: &FootnoteDefinition) -> Doc<'a> {
+        let number = self
+            .index
+            .footnote_index(&footnote.label)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| footnote.label.clone());
+        self.arena
+            .text(format!(".. [{number}] "))
+            .append(self.render_blocks(&footnote.blocks))
+            .nest(3)
+    }
+
+    fn render_container_directive(&self, directive: &ContainerDirective) -> Doc<'a> {
+        let label = plain_text(&directive.label, Some(&self.index));
+        let content = match directive.blocks.is_empty() {
+            true => None,
+            false => Some(self.render_blocks(&directive.blocks)),
+        };
+        self.directive(&directive.name, &label, &directive.attributes, content)
+    }
+
+    fn render_leaf_directive(&self, directive: &Directive) -> Doc<'a> {
+        let label = plain_text(&directive.label, Some(&self.index));
+        self.directive(&directive.name, &label, &directive.attributes, None)
+    }
+
+    /// Inline markup is separated from adjacent words with escaped spaces.
+    fn render_inlines(&self, inlines: &[Inline]) -> Doc<'a> {
+        let mut doc = self.arena.nil();
+        let separate = !self.markup.get();
+        for (i, inline) in inlines.iter().enumerate() {
+            let markup = separate && is_markup(inline);
+            if markup && i > 0 && !may_precede_markup(&inlines[i - 1]) {
+                doc = doc.append(self.arena.text("\\ "));
+            }
+            doc = doc.append(self.render_inline(inline));
+            if markup && !inlines.get(i + 1).is_none_or(may_follow_markup) {
+                doc = doc.append(self.arena.text("\\ "));
+            }
+        }
+        doc
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            self.arena.softline(),
+        )
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena.hardline()
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        if code.contains("``") || code.starts_with(' ') || code.ends_with(' ') {
+            let code = code.replace('\\', "\\\\").replace('`', "\\`");
+            self.arena.text(format!(":literal:`{code}`"))
+        } else {
+            self.arena.text(format!("``{code}``"))
+        }
+    }
+
+    /// Link text cannot contain markup, so only its text is rendered.
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let text = plain_text(&link.children, Some(&self.index));
+        self.link(&link.destination, &text)
+    }
+
+    /// A paragraph with only an image is rendered by
+    /// [`render_paragraph`](Renderer::render_paragraph), other images as links.
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        self.link(&image.destination, &image.alt)
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.markup("*", "*", children)
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.markup("**", "**", children)
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        if link.starts_with("www.") {
+            self.arena.text(format!("http://{link}"))
+        } else {
+            self.arena.text(link.to_owned())
+        }
+    }
+
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        match self.index.footnote_index(label) {
+            Some(n) => self.arena.text(format!("[{n}]_")),
+            None => self.render_text(&format!("[^{label}]")),
+        }
+    }
+
+    /// Text directives are rendered as interpreted text with the directive name as
+    /// the role.
+    fn render_text_directive(&self, directive: &Directive) -> Doc<'a> {
+        let label = plain_text(&directive.label, Some(&self.index));
+        let label = label.replace('\\', "\\\\").replace('`', "\\`");
+        self.arena.text(format!(":{}:`{label}`", directive.name))
+    }
+}
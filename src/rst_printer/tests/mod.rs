@@ -0,0 +1,119 @@
+#![cfg(test)]
+use crate::rst_printer::config::Config;
+use crate::rst_printer::render_rst;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_rst(&doc, Config::default())
+}
+
+#[rstest]
+#[case("# Title", "Title\n=====")]
+#[case("## A\n\n### Größe *x*", "A\n=\n\nGröße *x*\n---------")]
+#[case(
+    "# A\n\n## B\n\n### C\n\n#### D\n\n##### E\n\n###### Six",
+    "A\n=\n\nB\n-\n\nC\n~\n\nD\n^\n\nE\n\"\n\nSix\n'''"
+)]
+#[case("*em* **strong _both_** ~~gone~~", "*em* **strong both** gone")]
+#[case("a`b`c [d](u)e", "a\\ ``b``\\ c `d <u>`__\\ e")]
+#[case("`` a`b ``", "``a`b``")]
+#[case("2 * 3 = `x` | y_ _z a\\b", "2 \\* 3 = ``x`` \\| y\\_ \\_z a\\\\b")]
+#[case("x - 1. .. IV) -- == foo::", "x \\- \\1. \\.. \\IV) \\-- \\== foo:\\:")]
+#[case(
+    "[docs](https://example.com) <https://example.com>",
+    "`docs <https://example.com>`__ https://example.com"
+)]
+#[case("![Logo](logo.png)", ".. image:: logo.png\n   :alt: Logo")]
+#[case("See ![Logo](logo.png).", "See `Logo <logo.png>`__.")]
+#[case("> quoted\n>\n> more", "    quoted\n\n    more")]
+#[case("> [!TIP]\n> Use it.", ".. tip::\n\n   Use it.")]
+#[case(
+    "```rust\nfn a() {}\n\nfn b() {}\n```",
+    ".. code-block:: rust\n\n   fn a() {}\n\n   fn b() {}"
+)]
+#[case("```\nplain\n```", "::\n\n   plain")]
+#[case("<div>\n</div>", ".. raw:: html\n\n   <div>\n   </div>")]
+#[case("a\n\n***\n\nb", "a\n\n----\n\nb")]
+fn rst(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input));
+}
+
+/// Skipped heading levels do not skip adornments, which would make the section
+/// levels inconsistent.
+#[test]
+fn skipped_heading_levels() {
+    assert_eq!(
+        "A\n=\n\nC\n-\n\nB\n-\n\nD\n=\n\nE\n-",
+        render("# A\n\n### C\n\n## B\n\n# D\n\n## E")
+    );
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        "- a\n\n  3. b\n  4. c\n- [x] done",
+        render("- a\n    3. b\n    4. c\n- [x] done")
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        ".. list-table::\n   :header-rows: 1\n\n   * - Name\n     - Size\n   * - ``a``\n     - 1",
+        render("| Name | Size |\n|------|-----:|\n| `a` | 1 |")
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        "Text\\ [1]_ and [2]_.\n\n.. [1] First.\n\n.. [2] Second line.",
+        render("Text[^a] and [^b].\n\n[^a]: First.\n\n[^b]: Second\n    line.")
+    );
+}
+
+#[test]
+fn wrapping() {
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::default(),
+        "- one two three four five six",
+    )
+    .unwrap();
+    assert_eq!(
+        "- one two\n  three four\n  five six",
+        render_rst(&doc, Config::default().with_width(12))
+    );
+}
+
+#[test]
+fn directives() {
+    use crate::ast::*;
+    let doc = Document {
+        blocks: vec![
+            Block::ContainerDirective(ContainerDirective {
+                name: "note".to_owned(),
+                label: vec![],
+                attributes: vec![("class".to_owned(), "wide".to_owned())],
+                blocks: vec![Block::Paragraph(vec![
+                    Inline::Text("See ".to_owned()),
+                    Inline::TextDirective(Directive {
+                        name: "ref".to_owned(),
+                        label: vec![Inline::Text("intro".to_owned())],
+                        attributes: vec![],
+                    }),
+                ])],
+            }),
+            Block::LeafDirective(Directive {
+                name: "include".to_owned(),
+                label: vec![Inline::Text("other.rst".to_owned())],
+                attributes: vec![],
+            }),
+        ],
+    };
+    assert_eq!(
+        ".. note::\n   :class: wide\n\n   See :ref:`intro`\n\n.. include:: other.rst",
+        render_rst(&doc, Config::default())
+    );
+}
@@ -0,0 +1,233 @@
+//! Rendering of the AST to Slack mrkdwn.
+//!
+//! mrkdwn has no headings, tables or nested lists. Headings are rendered in bold,
+//! tables as code blocks laid out with spaces, and nested list items are indented
+//! with a different bullet. Lines are never wrapped, as Slack wraps messages itself.
+//!
+//! ```rust
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//! use markdown_ppp::slack_printer::render_slack;
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "# Report\n\nSee **the [dashboard](https://example.com)** for *details*.",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     "*Report*\n\nSee *the <https://example.com|dashboard>* for _details_.",
+//!     render_slack(&doc)
+//! );
+//! ```
+
+mod tests;
+
+use crate::ast::*;
+use crate::renderer::plain::{plain_text, text_table};
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+use std::cell::Cell;
+
+/// Render the given Markdown AST to Slack mrkdwn.
+pub fn render_slack(ast: &Document) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = SlackRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+        bold: Cell::new(false),
+        italic: Cell::new(false),
+        strike: Cell::new(false),
+        quoted: Cell::new(false),
+        list_depth: Cell::new(0),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, usize::MAX)
+}
+
+/// Escape the characters Slack reserves for links and mentions. Formatting
+/// characters cannot be escaped in mrkdwn.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Link destination with the characters ending it percent-encoded.
+fn destination(url: &str) -> String {
+    url.replace('|', "%7C")
+        .replace('<', "%3C")
+        .replace('>', "%3E")
+}
+
+struct SlackRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+    // Styles being rendered, as mrkdwn styles cannot be nested in themselves.
+    bold: Cell<bool>,
+    italic: Cell<bool>,
+    strike: Cell<bool>,
+    // Whether a block quote is being rendered, as quotes cannot be nested.
+    quoted: Cell<bool>,
+    list_depth: Cell<usize>,
+}
+
+impl<'a> SlackRenderer<'a> {
+    /// Content between `marker`s, unless already rendered in the style.
+    fn styled(&self, style: &Cell<bool>, marker: &'static str, content: &[Inline]) -> Doc<'a> {
+        if style.replace(true) {
+            return self.render_inlines(content);
+        }
+        let content = self.render_inlines(content);
+        style.set(false);
+        self.arena
+            .text(marker)
+            .append(content)
+            .append(self.arena.text(marker))
+    }
+
+    /// Code block fenced with triple backticks.
+    fn fenced<'l>(&self, lines: impl Iterator<Item = &'l str>) -> Doc<'a> {
+        self.arena
+            .text("```")
+            .append(self.arena.hardline())
+            .append(self.arena.intersperse(
+                lines.map(|line| self.arena.text(escape(line))),
+                self.arena.hardline(),
+            ))
+            .append(self.arena.hardline())
+            .append(self.arena.text("```"))
+    }
+
+    fn link(&self, url: &str, text: &str) -> Doc<'a> {
+        if text.is_empty() || text == url {
+            self.arena.text(format!("<{}>", destination(url)))
+        } else {
+            self.arena
+                .text(format!("<{}|{}>", destination(url), escape(text)))
+        }
+    }
+}
+
+impl<'a> Renderer<'a> for SlackRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// Blocks in list items are not separated by empty lines, so nested lists stay
+    /// attached to their item.
+    fn block_separator(&self) -> Doc<'a> {
+        match self.list_depth.get() {
+            0 => self.arena.hardline().append(self.arena.hardline()),
+            _ => self.arena.hardline(),
+        }
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        self.styled(&self.bold, "*", &heading.content)
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("\u{2500}".repeat(10))
+    }
+
+    /// Every line is prefixed with `>`, nested quotes are merged into the outer one.
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        if self.quoted.replace(true) {
+            return self.render_blocks(blocks);
+        }
+        let mut content = String::new();
+        self.render_blocks(blocks)
+            .render_fmt(usize::MAX, &mut content)
+            .expect("writing to a String never fails");
+        self.quoted.set(false);
+        self.arena.intersperse(
+            content.split('\n').map(|line| match line.trim_end() {
+                "" => self.arena.text(">"),
+                line => self.arena.text(format!("> {line}")),
+            }),
+            self.arena.hardline(),
+        )
+    }
+
+    /// Items are marked with bullets, numbers or checkboxes. Nested items are
+    /// indented, with a different bullet for every level.
+    fn render_list_item(&self, _list: &List, number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let depth = self.list_depth.get();
+        let checkbox = item.task.map(|task| match task {
+            TaskState::Complete => "\u{2611}",
+            TaskState::Incomplete => "\u{2610}",
+        });
+        let marker = match (number, checkbox) {
+            (Some(n), Some(checkbox)) => format!("{n}. {checkbox}"),
+            (Some(n), None) => format!("{n}."),
+            (None, Some(checkbox)) => checkbox.to_owned(),
+            (None, None) => ["\u{2022}", "\u{25e6}", "\u{25aa}"][depth % 3].to_owned(),
+        };
+        let indent = marker.chars().count() + 1;
+        self.list_depth.set(depth + 1);
+        let content = self.render_blocks(&item.blocks);
+        self.list_depth.set(depth);
+        self.arena
+            .text(format!("{marker} "))
+            .append(content.nest(indent as isize))
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        self.fenced(literal.unwrap_or(&code_block.literal).split('\n'))
+    }
+
+    /// Tables are laid out as text in a code block.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let lines = text_table(table, Some(&self.index));
+        self.fenced(lines.iter().map(String::as_str))
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            self.arena.softline(),
+        )
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        self.arena.text(format!("`{}`", escape(code)))
+    }
+
+    /// Link text cannot be formatted, so only its text is rendered.
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let text = plain_text(&link.children, Some(&self.index));
+        self.link(&link.destination, &text)
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        self.link(&image.destination, &image.alt)
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.italic, "_", children)
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.bold, "*", children)
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.strike, "~", children)
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        if link.starts_with("www.") {
+            self.link(&format!("http://{link}"), link)
+        } else if !link.contains(':') && link.contains('@') {
+            self.link(&format!("mailto:{link}"), link)
+        } else {
+            self.link(link, link)
+        }
+    }
+}
@@ -0,0 +1,57 @@
+#![cfg(test)]
+use crate::slack_printer::render_slack;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_slack(&doc)
+}
+
+#[rstest]
+#[case("# Title", "*Title*")]
+#[case("## Title with **bold**", "*Title with bold*")]
+#[case("*em* **strong _both_** ~~gone~~", "_em_ *strong _both_* ~gone~")]
+#[case("a < b && c > d", "a &lt; b &amp;&amp; c &gt; d")]
+#[case("`a<b>`", "`a&lt;b&gt;`")]
+#[case("one\ntwo  \nthree", "one two\nthree")]
+#[case(
+    "[**docs** here](https://example.com/a|b)",
+    "<https://example.com/a%7Cb|docs here>"
+)]
+#[case(
+    "<https://example.com> <user@example.com>",
+    "<https://example.com> <mailto:user@example.com|user@example.com>"
+)]
+#[case("![logo](logo.png)", "<logo.png|logo>")]
+#[case("> quoted\n>\n> > nested", "> quoted\n>\n> nested")]
+#[case("```rust\nlet a = b < c;\n```", "```\nlet a = b &lt; c;\n```")]
+#[case("---", "──────────")]
+#[case("Text[^n]\n\n[^n]: Note", "Text[1]\n\n[1] Note")]
+fn slack(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input));
+}
+
+#[test]
+fn lists() {
+    let input = "- a\n    - b\n        - c\n- [x] done\n- [ ] todo\n\n3. three\n4. [x] four";
+    assert_eq!(
+        "• a\n  ◦ b\n    ▪ c\n☑ done\n☐ todo\n\n3. three\n4. ☑ four",
+        render(input)
+    );
+}
+
+#[test]
+fn tables() {
+    let input = "| Name | Size |\n|:-----|-----:|\n| `a` | 1 |\n| **long** name | 1024 |";
+    assert_eq!(
+        "```\nName      | Size\n----------|-----\na         |    1\nlong name | 1024\n```",
+        render(input)
+    );
+}
+
+#[test]
+fn no_wrapping() {
+    let text = "word ".repeat(40);
+    assert_eq!(text.trim_end(), render(&text));
+}
@@ -0,0 +1,328 @@
+//! Rendering of the AST to Telegram MarkdownV2.
+//!
+//! MarkdownV2 has no headings, tables or nested lists. Headings are rendered in
+//! bold, tables as preformatted blocks laid out with spaces, and nested list items
+//! are indented with a different bullet. Lines are never wrapped, as Telegram wraps
+//! messages itself.
+//!
+//! ```rust
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//! use markdown_ppp::telegram_printer::render_telegram;
+//!
+//! let doc = parse_markdown(
+//!     MarkdownParserState::default(),
+//!     "# Report v1.2\n\nSee the [dashboard](https://example.com/(1)) for *details*!",
+//! )
+//! .unwrap();
+//! assert_eq!(
+//!     "*Report v1\\.2*\n\nSee the [dashboard](https://example.com/(1\\)) for _details_\\!",
+//!     render_telegram(&doc)
+//! );
+//! ```
+
+mod tests;
+
+use crate::ast::*;
+use crate::renderer::plain::text_table;
+use crate::renderer::{Doc, Index, Renderer};
+use pretty::{Arena, DocAllocator};
+use std::cell::Cell;
+
+/// Render the given Markdown AST to Telegram MarkdownV2.
+pub fn render_telegram(ast: &Document) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = TelegramRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+        bold: Cell::new(false),
+        italic: Cell::new(false),
+        strike: Cell::new(false),
+        quoted: Cell::new(false),
+        list_depth: Cell::new(0),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, usize::MAX)
+}
+
+/// Escape text for MarkdownV2, where every reserved character must be escaped
+/// outside of code, even where it would not start an entity.
+pub fn escape(text: &str) -> String {
+    escape_chars(text, "_*[]()~`>#+-=|{}.!\\")
+}
+
+/// Escape text inside code and preformatted blocks.
+fn escape_code(text: &str) -> String {
+    escape_chars(text, "`\\")
+}
+
+fn escape_chars(text: &str, reserved: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        if reserved.contains(c) {
+            result.push('\\');
+        }
+        result.push(c);
+    }
+    result
+}
+
+struct TelegramRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+    // Styles being rendered, nesting a style in itself would end it.
+    bold: Cell<bool>,
+    italic: Cell<bool>,
+    strike: Cell<bool>,
+    // Whether a block quote is being rendered, as quotes cannot be nested.
+    quoted: Cell<bool>,
+    list_depth: Cell<usize>,
+}
+
+impl<'a> TelegramRenderer<'a> {
+    /// Content between `marker`s, unless already rendered in the style. Code cannot
+    /// be styled, so the style is ended around it.
+    fn styled(&self, style: &Cell<bool>, marker: &'static str, content: &[Inline]) -> Doc<'a> {
+        if style.get() {
+            return self.render_inlines(content);
+        }
+        let content = without_styled_code(content);
+        let parts = content.split_inclusive(|inline| matches!(inline, Inline::Code(_)));
+        self.arena.concat(parts.map(|part| {
+            let (code, styled) = match part.split_last() {
+                Some((code @ Inline::Code(_), styled)) => (Some(code), styled),
+                _ => (None, part),
+            };
+            let styled = match styled.is_empty() {
+                true => self.arena.nil(),
+                false => {
+                    style.set(true);
+                    let content = self.render_inlines(styled);
+                    style.set(false);
+                    self.arena
+                        .text(marker)
+                        .append(content)
+                        .append(self.arena.text(marker))
+                }
+            };
+            let code = code.map_or(self.arena.nil(), |code| self.render_inline(code));
+            styled.append(code)
+        }))
+    }
+
+    /// Preformatted block with an optional language.
+    fn pre<'l>(&self, language: &str, lines: impl Iterator<Item = &'l str>) -> Doc<'a> {
+        self.arena
+            .text(format!("```{language}"))
+            .append(self.arena.hardline())
+            .append(self.arena.intersperse(
+                lines.map(|line| self.arena.text(escape_code(line))),
+                self.arena.hardline(),
+            ))
+            .append(self.arena.hardline())
+            .append(self.arena.text("```"))
+    }
+
+    /// `[text](url)`, where only `)` and `\` are escaped in the URL.
+    fn link(&self, url: &str, text: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text("[")
+            .append(text)
+            .append(self.arena.text(format!("]({})", escape_chars(url, ")\\"))))
+    }
+}
+
+/// Inlines with code moved out of emphasis, strong and strikethrough, which are split
+/// around it.
+fn without_styled_code(inlines: &[Inline]) -> Vec<Inline> {
+    let mut result = Vec::new();
+    for inline in inlines {
+        let (wrap, children): (fn(Vec<Inline>) -> Inline, _) = match inline {
+            Inline::Emphasis(children) => (Inline::Emphasis, children),
+            Inline::Strong(children) => (Inline::Strong, children),
+            Inline::Strikethrough(children) => (Inline::Strikethrough, children),
+            inline => {
+                result.push(inline.clone());
+                continue;
+            }
+        };
+        let children = without_styled_code(children);
+        for part in children.split_inclusive(|inline| matches!(inline, Inline::Code(_))) {
+            match part.split_last() {
+                Some((code @ Inline::Code(_), styled)) => {
+                    if !styled.is_empty() {
+                        result.push(wrap(styled.to_vec()));
+                    }
+                    result.push(code.clone());
+                }
+                _ => result.push(wrap(part.to_vec())),
+            }
+        }
+    }
+    result
+}
+
+impl<'a> Renderer<'a> for TelegramRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// Blocks in list items are not separated by empty lines, so nested lists stay
+    /// attached to their item.
+    fn block_separator(&self) -> Doc<'a> {
+        match self.list_depth.get() {
+            0 => self.arena.hardline().append(self.arena.hardline()),
+            _ => self.arena.hardline(),
+        }
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        self.styled(&self.bold, "*", &heading.content)
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("\u{2500}".repeat(10))
+    }
+
+    /// Every line is prefixed with `>`, nested quotes are merged into the outer one.
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        if self.quoted.replace(true) {
+            return self.render_blocks(blocks);
+        }
+        let mut content = String::new();
+        self.render_blocks(blocks)
+            .render_fmt(usize::MAX, &mut content)
+            .expect("writing to a String never fails");
+        self.quoted.set(false);
+        self.arena.intersperse(
+            content.split('\n').map(|line| match line.trim_end() {
+                "" => self.arena.text(">"),
+                line => self.arena.text(format!(">{line}")),
+            }),
+            self.arena.hardline(),
+        )
+    }
+
+    /// Items are marked with bullets, numbers or checkboxes. Nested items are
+    /// indented, with a different bullet for every level.
+    fn render_list_item(&self, _list: &List, number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let depth = self.list_depth.get();
+        let checkbox = item.task.map(|task| match task {
+            TaskState::Complete => "\u{2611}",
+            TaskState::Incomplete => "\u{2610}",
+        });
+        let marker = match (number, checkbox) {
+            (Some(n), Some(checkbox)) => format!("{n}. {checkbox}"),
+            (Some(n), None) => format!("{n}."),
+            (None, Some(checkbox)) => checkbox.to_owned(),
+            (None, None) => ["\u{2022}", "\u{25e6}", "\u{25aa}"][depth % 3].to_owned(),
+        };
+        let indent = marker.chars().count() + 1;
+        self.list_depth.set(depth + 1);
+        let content = self.render_blocks(&item.blocks);
+        self.list_depth.set(depth);
+        self.arena
+            .text(format!("{} ", escape(&marker)))
+            .append(content.nest(indent as isize))
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let language = match &code_block.kind {
+            CodeBlockKind::Fenced { info: Some(info) } => {
+                info.split_whitespace().next().unwrap_or_default()
+            }
+            _ => "",
+        };
+        // The language ends at the first special character
+        let language = match language
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '+' | '-' | '_' | '#'))
+        {
+            true => language,
+            false => "",
+        };
+        self.pre(language, literal.unwrap_or(&code_block.literal).split('\n'))
+    }
+
+    /// Tables are laid out as text in a preformatted block.
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let lines = text_table(table, Some(&self.index));
+        self.pre("", lines.iter().map(String::as_str))
+    }
+
+    /// Render a referenced footnote as `[n] content`.
+    fn render_footnote(&self, footnote: &FootnoteDefinition) -> Doc<'a> {
+        let number = self
+            .index
+            .footnote_index(&footnote.label)
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| footnote.label.clone());
+        let marker = format!("[{number}] ");
+        let indent = marker.chars().count();
+        self.arena
+            .text(escape(&marker))
+            .append(self.render_blocks(&footnote.blocks).nest(indent as isize))
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            self.arena.softline(),
+        )
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        self.arena.text(format!("`{}`", escape_code(code)))
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        let text = match link.children.is_empty() {
+            true => self.render_text(&link.destination),
+            false => self.render_inlines(&link.children),
+        };
+        self.link(&link.destination, text)
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        let text = match image.alt.is_empty() {
+            true => &image.destination,
+            false => &image.alt,
+        };
+        self.link(&image.destination, self.render_text(text))
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.italic, "_", children)
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.bold, "*", children)
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.styled(&self.strike, "~", children)
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        let destination = if link.starts_with("www.") {
+            format!("http://{link}")
+        } else if !link.contains(':') && link.contains('@') {
+            format!("mailto:{link}")
+        } else {
+            link.to_owned()
+        };
+        self.link(&destination, self.render_text(link))
+    }
+
+    fn render_inline_footnote(&self, content: &[Inline]) -> Doc<'a> {
+        self.render_text("(")
+            .append(self.render_inlines(content))
+            .append(self.render_text(")"))
+    }
+}
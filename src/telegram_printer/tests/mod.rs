@@ -0,0 +1,61 @@
+#![cfg(test)]
+use crate::telegram_printer::render_telegram;
+use rstest::rstest;
+
+fn render(input: &str) -> String {
+    let doc = crate::parser::parse_markdown(crate::parser::MarkdownParserState::default(), input)
+        .unwrap();
+    render_telegram(&doc)
+}
+
+#[rstest]
+#[case("# Title", "*Title*")]
+#[case("## Title with **bold**", "*Title with bold*")]
+#[case("*em* **strong _both_** ~~gone~~", "_em_ *strong _both_* ~gone~")]
+#[case("# a `b` c", "*a *`b`* c*")]
+#[case("**a _b `c`_**", "*a _b _*`c`")]
+#[case(
+    "1 + 1 = 2. (a) {b} #c |d| e-f a>b!",
+    "1 \\+ 1 \\= 2\\. \\(a\\) \\{b\\} \\#c \\|d\\| e\\-f a\\>b\\!"
+)]
+#[case("`a\\b` ``c`d``", "`a\\\\b` `c\\`d`")]
+#[case("one\ntwo  \nthree", "one two\nthree")]
+#[case(
+    "[**docs** here](https://example.com/a_(b))",
+    "[*docs* here](https://example.com/a_(b\\))"
+)]
+#[case(
+    "<https://example.com>",
+    "[https://example\\.com](https://example.com)"
+)]
+#[case("![logo](logo.png)", "[logo](logo.png)")]
+#[case("> quoted\n>\n> > nested", ">quoted\n>\n>nested")]
+#[case(
+    "```rust\nlet a = \"\\\\\";\n```",
+    "```rust\nlet a = \"\\\\\\\\\";\n```"
+)]
+#[case("```c++ main\nx\n```", "```c++\nx\n```")]
+#[case("```{.rust}\nx\n```", "```\nx\n```")]
+#[case("Text[^n]\n\n[^n]: Note.", "Text\\[1\\]\n\n\\[1\\] Note\\.")]
+#[case("@user and org/repo#45", "@user and org/repo\\#45")]
+fn telegram(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input));
+}
+
+#[test]
+fn lists() {
+    let input = "- a\n    - b\n- [x] done\n\n9. nine\n10. [ ] ten";
+    assert_eq!(
+        "• a\n  ◦ b\n☑ done\n\n9\\. nine\n10\\. ☐ ten",
+        render(input)
+    );
+}
+
+#[test]
+fn tables() {
+    let input = "| Name | Size |\n|:----:|------|\n| `a` | 1 |\n| long`name | 1024 |";
+    assert_eq!(
+        "```\n  Name    | Size\n----------|-----\n    a     | 1\nlong\\`name | 1024\n```",
+        render(input)
+    );
+}
@@ -0,0 +1,15 @@
+pub struct Config {
+    pub(crate) width: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self { width: 80 }
+    }
+}
+
+impl Config {
+    pub fn with_width(self, width: usize) -> Self {
+        Self { width }
+    }
+}
@@ -0,0 +1,369 @@
+//! Rendering of the AST to Typst markup.
+//!
+//! ```rust
+//! use markdown_ppp::parser::{parse_markdown, MarkdownParserState};
+//! use markdown_ppp::typst_printer::{config::Config, render_typst};
+//!
+//! let doc = parse_markdown(MarkdownParserState::default(), "# Report\n\nSee *this*.").unwrap();
+//! assert_eq!("= Report\n\nSee #emph[this].", render_typst(&doc, Config::default()));
+//! ```
+
+pub mod config;
+mod tests;
+
+use crate::ast::*;
+use crate::renderer::{Doc, Index, Renderer};
+use crate::typst_printer::config::Config;
+use pretty::{Arena, DocAllocator};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+/// Render the given Markdown AST to Typst markup.
+pub fn render_typst(ast: &Document, config: Config) -> String {
+    let ast = crate::ast::footnotes::hoisted(ast);
+
+    let arena = Arena::new();
+    let renderer = TypstRenderer {
+        arena: &arena,
+        index: Index::new(&ast),
+        footnote_references_rendered: RefCell::new(HashMap::new()),
+        open_footnotes: RefCell::new(Vec::new()),
+        wrap: Cell::new(true),
+    };
+    crate::renderer::render_to_string(&renderer, &ast, config.width)
+}
+
+/// Escape a word for use in Typst markup. Characters which start markup anywhere are
+/// always escaped, list and heading markers only at the start of the word.
+pub fn escape(word: &str) -> String {
+    let mut result = String::with_capacity(word.len());
+    let mut chars = word.chars().peekable();
+    let mut first = true;
+    while let Some(c) = chars.next() {
+        let next = chars.peek().copied();
+        let escape = match c {
+            '\\' | '#' | '*' | '_' | '`' | '$' | '<' | '>' | '@' | '[' | ']' | '~' => true,
+            // Term list items and comments
+            '/' => first || matches!(next, Some('/' | '*')),
+            // Dashes and soft hyphens, list items
+            '-' => first || matches!(next, Some('-' | '?')),
+            // Headings and numbered list items
+            '=' | '+' => first,
+            _ => false,
+        };
+        if escape {
+            result.push('\\');
+        }
+        result.push(c);
+        // `1.` at the start of a line is a numbered list item
+        if first && c.is_ascii_digit() {
+            let digits: String =
+                std::iter::from_fn(|| chars.next_if(char::is_ascii_digit)).collect();
+            result.push_str(&digits);
+            if chars.clone().eq(['.']) {
+                chars.next();
+                result.push_str("\\.");
+            }
+        }
+        first = false;
+    }
+    result
+}
+
+/// Typst string literal.
+fn string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                result.push('\\');
+                result.push(c);
+            }
+            '\n' => result.push_str("\\n"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Backtick fence longer than any backtick run in the code.
+fn fence(code: &str) -> String {
+    let longest = code
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    "`".repeat((longest + 1).max(3))
+}
+
+struct TypstRenderer<'a> {
+    arena: &'a Arena<'a>,
+    index: Index,
+    // Number of already rendered references to each footnote.
+    footnote_references_rendered: RefCell<HashMap<String, usize>>,
+    // Footnotes being rendered, to stop on footnotes referencing themselves.
+    open_footnotes: RefCell<Vec<String>>,
+    // Whether text may be wrapped at spaces.
+    wrap: Cell<bool>,
+}
+
+impl<'a> TypstRenderer<'a> {
+    /// `#function[content]`
+    fn function(&self, function: &str, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text(format!("#{function}"))
+            .append(self.content(content))
+    }
+
+    /// Content block `[content]`
+    fn content(&self, content: Doc<'a>) -> Doc<'a> {
+        self.arena
+            .text("[")
+            .append(content)
+            .append(self.arena.text("]"))
+    }
+}
+
+impl<'a> Renderer<'a> for TypstRenderer<'a> {
+    fn arena(&self) -> &'a Arena<'a> {
+        self.arena
+    }
+
+    fn index(&self) -> Option<&Index> {
+        Some(&self.index)
+    }
+
+    /// Footnotes are rendered at their references, so only the body is rendered.
+    fn render_document(&self, document: &Document) -> Doc<'a> {
+        self.render_blocks(&document.blocks)
+    }
+
+    fn render_heading(&self, heading: &Heading) -> Doc<'a> {
+        let level = match heading.kind {
+            HeadingKind::Atx(level) => level as usize,
+            HeadingKind::Setext(SetextHeading::Level1) => 1,
+            HeadingKind::Setext(SetextHeading::Level2) => 2,
+        };
+        // Headings end at the line break, so they are never wrapped
+        self.wrap.set(false);
+        let content = self.render_inlines(&heading.content);
+        self.wrap.set(true);
+        self.arena
+            .text(format!("{} ", "=".repeat(level)))
+            .append(content)
+    }
+
+    fn render_thematic_break(&self) -> Doc<'a> {
+        self.arena.text("#line(length: 100%)")
+    }
+
+    fn render_block_quote(&self, blocks: &[Block]) -> Doc<'a> {
+        self.arena
+            .text("#quote(block: true)[")
+            .append(
+                self.arena
+                    .hardline()
+                    .append(self.render_blocks(blocks))
+                    .nest(2),
+            )
+            .append(self.arena.hardline())
+            .append(self.arena.text("]"))
+    }
+
+    fn render_list_item(&self, list: &List, number: Option<u64>, item: &ListItem) -> Doc<'a> {
+        let marker = match (&list.kind, number) {
+            (ListKind::Ordered(_), Some(n)) => format!("{n}. "),
+            _ => "- ".to_owned(),
+        };
+        let task = match item.task {
+            Some(TaskState::Complete) => "\u{2612} ",
+            Some(TaskState::Incomplete) => "\u{2610} ",
+            None => "",
+        };
+        // List item content is continued by indentation
+        let indent = marker.len();
+        self.arena
+            .text(marker)
+            .append(self.arena.text(task))
+            .append(self.render_blocks(&item.blocks).nest(indent as isize))
+    }
+
+    fn render_code_block(&self, code_block: &CodeBlock) -> Doc<'a> {
+        let literal = code_block.literal.strip_suffix('\n');
+        let literal = literal.unwrap_or(&code_block.literal);
+        let language = match &code_block.kind {
+            CodeBlockKind::Fenced { info: Some(info) } => {
+                info.split_whitespace().next().unwrap_or_default()
+            }
+            _ => "",
+        };
+        let fence = fence(literal);
+        self.arena
+            .text(format!("{fence}{language}"))
+            .append(self.arena.hardline())
+            .append(
+                self.arena.intersperse(
+                    literal
+                        .split('\n')
+                        .map(|line| self.arena.text(line.to_owned())),
+                    self.arena.hardline(),
+                ),
+            )
+            .append(self.arena.hardline())
+            .append(self.arena.text(fence))
+    }
+
+    fn render_table(&self, table: &Table) -> Doc<'a> {
+        let columns = table.rows.first().map(|row| row.len()).unwrap_or_default();
+        let align = (0..columns)
+            .map(
+                |i| match table.alignments.get(i).copied().unwrap_or_default() {
+                    Alignment::Left => "left",
+                    Alignment::Center => "center",
+                    Alignment::Right => "right",
+                    Alignment::None => "auto",
+                },
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+        let cells = |row: &TableRow| {
+            self.arena.intersperse(
+                row.iter()
+                    .map(|cell| self.content(self.render_inlines(cell)).group()),
+                self.arena.text(", "),
+            )
+        };
+
+        let mut arguments = vec![
+            self.arena.text(format!("columns: {columns},")),
+            // A single value applies to all columns, an array needs a trailing comma
+            self.arena.text(match columns {
+                1 => format!("align: ({align},),"),
+                _ => format!("align: ({align}),"),
+            }),
+        ];
+        if let Some((header, rows)) = table.rows.split_first() {
+            arguments.push(
+                self.arena
+                    .text("table.header(")
+                    .append(cells(header))
+                    .append(self.arena.text("),")),
+            );
+            arguments.extend(
+                rows.iter()
+                    .map(|row| cells(row).append(self.arena.text(","))),
+            );
+        }
+
+        self.arena
+            .text("#table(")
+            .append(
+                self.arena
+                    .hardline()
+                    .append(self.arena.intersperse(arguments, self.arena.hardline()))
+                    .nest(2),
+            )
+            .append(self.arena.hardline())
+            .append(self.arena.text(")"))
+    }
+
+    fn render_text(&self, text: &str) -> Doc<'a> {
+        self.arena.intersperse(
+            text.split([' ', '\n'])
+                .map(|word| self.arena.text(escape(word))),
+            match self.wrap.get() {
+                true => self.arena.softline(),
+                false => self.arena.text(" "),
+            },
+        )
+    }
+
+    fn render_line_break(&self) -> Doc<'a> {
+        self.arena.text("\\").append(self.arena.hardline())
+    }
+
+    fn render_code(&self, code: &str) -> Doc<'a> {
+        if code.contains('`') {
+            self.arena.text(format!("#raw({})", string(code)))
+        } else {
+            self.arena.text(format!("`{code}`"))
+        }
+    }
+
+    fn render_link(&self, link: &Link) -> Doc<'a> {
+        self.arena
+            .text(format!("#link({})", string(&link.destination)))
+            .append(self.content(self.render_inlines(&link.children)))
+    }
+
+    fn render_image(&self, image: &Image) -> Doc<'a> {
+        let mut arguments = string(&image.destination);
+        if !image.alt.is_empty() {
+            arguments.push_str(&format!(", alt: {}", string(&image.alt)));
+        }
+        self.arena.text(format!("#image({arguments})"))
+    }
+
+    fn render_emphasis(&self, children: &[Inline]) -> Doc<'a> {
+        self.function("emph", self.render_inlines(children))
+    }
+
+    fn render_strong(&self, children: &[Inline]) -> Doc<'a> {
+        self.function("strong", self.render_inlines(children))
+    }
+
+    fn render_strikethrough(&self, children: &[Inline]) -> Doc<'a> {
+        self.function("strike", self.render_inlines(children))
+    }
+
+    fn render_autolink(&self, link: &str) -> Doc<'a> {
+        let destination = if link.starts_with("www.") {
+            format!("http://{link}")
+        } else if !link.contains(':') && link.contains('@') {
+            format!("mailto:{link}")
+        } else {
+            link.to_owned()
+        };
+        let mut doc = self.arena.text(format!("#link({})", string(&destination)));
+        if destination != link {
+            doc = doc.append(self.content(self.arena.text(escape(link))));
+        }
+        doc
+    }
+
+    /// The first reference to a footnote renders its content with `#footnote` and a
+    /// `<fn-n>` label, further references refer to the label.
+    fn render_footnote_reference(&self, label: &str) -> Doc<'a> {
+        let Some(n) = self.index.footnote_index(label) else {
+            return self.render_text(&format!("[^{label}]"));
+        };
+        let first = {
+            let mut rendered = self.footnote_references_rendered.borrow_mut();
+            let counter = rendered.entry(label.to_owned()).or_default();
+            *counter += 1;
+            *counter == 1
+        };
+        let open = self.open_footnotes.borrow().iter().any(|v| v == label);
+        let definition = self
+            .index
+            .footnotes()
+            .iter()
+            .find(|footnote| footnote.label == label);
+        match definition {
+            Some(definition) if first && !open => {
+                self.open_footnotes.borrow_mut().push(label.to_owned());
+                let content = self.render_blocks(&definition.blocks);
+                self.open_footnotes.borrow_mut().pop();
+                self.function("footnote", content)
+                    .append(self.arena.text(format!(" <fn-{n}>")))
+            }
+            _ => self.arena.text(format!("#footnote(<fn-{n}>)")),
+        }
+    }
+
+    fn render_inline_footnote(&self, content: &[Inline]) -> Doc<'a> {
+        self.function("footnote", self.render_inlines(content))
+    }
+}
@@ -0,0 +1,115 @@
+#![cfg(test)]
+use crate::typst_printer::config::Config;
+use crate::typst_printer::render_typst;
+use rstest::rstest;
+
+fn render(input: &str, config: Config) -> String {
+    let doc = crate::parser::parse_markdown(
+        crate::parser::MarkdownParserState::with_config(
+            crate::parser::config::MarkdownParserConfig::default()
+                .with_inline_inline_footnote_behavior(
+                    crate::parser::config::ElementBehavior::Parse,
+                ),
+        ),
+        input,
+    )
+    .unwrap();
+    render_typst(&doc, config)
+}
+
+#[rstest]
+#[case("# One", "= One")]
+#[case("### Three *x*", "=== Three #emph[x]")]
+#[case("Setext\n---", "== Setext")]
+#[case(
+    "*em* **strong** ~~gone~~ `code`",
+    "#emph[em] #strong[strong] #strike[gone] `code`"
+)]
+#[case("``a ` b``", "#raw(\"a ` b\")")]
+#[case(
+    "#1 $5 a_b *[x]* <t> @me ~ a//b -- - = + 1. 3.14 a\\b",
+    "\\#1 \\$5 a\\_b #emph[x] \\<t\\> \\@me \\~ a\\//b \\-- \\- \\= \\+ 1\\. 3.14 a\\\\b"
+)]
+#[case("/ term: a/b", "\\/ term: a/b")]
+#[case(
+    "[docs](https://example.com \"Title\") ![A \"logo\"](img/logo.png)",
+    "#link(\"https://example.com\")[docs] #image(\"img/logo.png\", alt: \"A \\\"logo\\\"\")"
+)]
+#[case(
+    "<https://example.com> www.example.com",
+    "#link(\"https://example.com\") #link(\"http://www.example.com\")[www.example.com]"
+)]
+#[case("line  \nbreak", "line\\\nbreak")]
+#[case("> quoted\n>\n> more", "#quote(block: true)[\n  quoted\n\n  more\n]")]
+#[case("***", "#line(length: 100%)")]
+fn typst(#[case] input: &str, #[case] expected: &str) {
+    assert_eq!(expected, render(input, Config::default()));
+}
+
+#[test]
+fn lists() {
+    assert_eq!(
+        "- one\n- two\n\n  3. three\n  4. four",
+        render(
+            "- one\n- two\n\n    3. three\n    4. four",
+            Config::default()
+        )
+    );
+    assert_eq!(
+        "- \u{2612} done\n- \u{2610} todo",
+        render("- [x] done\n- [ ] todo", Config::default())
+    );
+}
+
+#[test]
+fn code_blocks() {
+    assert_eq!(
+        "```rust\nfn main() {}\n```",
+        render("```rust\nfn main() {}\n```", Config::default())
+    );
+    assert_eq!(
+        "````\nlet s = ```raw```;\n````",
+        render("~~~\nlet s = ```raw```;\n~~~", Config::default())
+    );
+}
+
+#[test]
+fn tables() {
+    assert_eq!(
+        concat!(
+            "#table(\n",
+            "  columns: 3,\n",
+            "  align: (left, center, auto),\n",
+            "  table.header([Name], [Kind], [Size]),\n",
+            "  [`a.rs`], [#emph[file]], [10],\n",
+            "  [b], [dir], [0],\n",
+            ")",
+        ),
+        render(
+            "| Name | Kind | Size |\n|:--|:-:|---|\n| `a.rs` | *file* | 10 |\n| b | dir | 0 |",
+            Config::default()
+        )
+    );
+}
+
+#[test]
+fn footnotes() {
+    assert_eq!(
+        "Text#footnote[First #emph[note].] <fn-1> again#footnote(<fn-1>)\nand#footnote[Inline.] <fn-2> missing \\[^x\\].",
+        render(
+            "Text[^a] again[^a] and^[Inline.] missing [^x].\n\n[^a]: First *note*.",
+            Config::default()
+        )
+    );
+}
+
+#[test]
+fn wrapping() {
+    assert_eq!(
+        "= A heading that is never wrapped\n\none two three\nfour #emph[five]\nsix",
+        render(
+            "# A heading that is never wrapped\n\none two three four *five* six",
+            Config::default().with_width(16)
+        )
+    );
+}